@@ -0,0 +1,517 @@
+use crate::wm::CycleDirection;
+use himewm_layout::variant::Direction;
+use serde::{de::Error, Deserialize, Deserializer, Serialize};
+
+/// An action that a keybind runs, written in keybinds.json as the name of the
+/// command and its arguments, like
+/// `{ "command": "swap", "args": { "direction": "Next" } }`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "command", content = "args", rename_all = "snake_case")]
+pub enum Command {
+    Focus {
+        direction: CycleDirection,
+    },
+    Swap {
+        direction: CycleDirection,
+    },
+    /// Focuses the nearest tile in a direction, crossing to the next monitor
+    /// at the edge of the workspace.
+    FocusDirection {
+        direction: Direction,
+    },
+    SwapDirection {
+        direction: Direction,
+    },
+    CycleLayout {
+        direction: CycleDirection,
+    },
+    /// Switches to the layout with this name, which is its path relative to
+    /// the layouts directory.
+    SetLayout {
+        name: String,
+    },
+    /// Cycles the variant at `index` in the focused workspace's variant
+    /// index.
+    CycleVariant {
+        index: usize,
+        direction: CycleDirection,
+    },
+    /// Switches to the variant at this variant index, like `[1, 0]`.
+    SetVariant {
+        index: Vec<usize>,
+    },
+    CycleFocusedMonitor {
+        direction: CycleDirection,
+    },
+    /// Focuses the monitor at `index` in the order monitors are enumerated.
+    FocusMonitor {
+        index: usize,
+    },
+    MoveToMonitor {
+        direction: CycleDirection,
+    },
+    GrabWindow,
+    ReleaseWindow,
+    ToggleWindow,
+    ToggleWorkspace,
+    RefreshWorkspace,
+    RestartHimewm,
+    Grow {
+        direction: Direction,
+    },
+    Shrink {
+        direction: Direction,
+    },
+    IncreaseWeight,
+    DecreaseWeight,
+    ResetWeights,
+    ToggleMonocle,
+    JoinGroup {
+        direction: CycleDirection,
+    },
+    SplitGroup,
+    CycleGroup {
+        direction: CycleDirection,
+    },
+    IncreaseGaps,
+    DecreaseGaps,
+    ToggleGaps,
+    PromoteToMaster,
+    IncreaseMasterCount,
+    DecreaseMasterCount,
+    GrowMaster,
+    ShrinkMaster,
+    Rotate {
+        direction: CycleDirection,
+    },
+    /// Moves the focused window to the tile at `index`.
+    MoveToIndex {
+        index: usize,
+    },
+    /// Moves the focused window to the previous or next virtual desktop,
+    /// switching to it if `follow`.
+    MoveToDesktop {
+        direction: CycleDirection,
+        #[serde(default)]
+        follow: bool,
+    },
+    /// Moves the focused window to the virtual desktop at `index`, switching
+    /// to it if `follow`.
+    MoveToDesktopIndex {
+        index: usize,
+        #[serde(default)]
+        follow: bool,
+    },
+    /// Shows or hides the scratchpad with this name in the settings.
+    ToggleScratchpad {
+        name: String,
+    },
+    Spawn {
+        program: String,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        args: Vec<String>,
+    },
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct UserKeybind {
+    /// Modifiers followed by a key, like `alt shift j`.
+    pub keys: String,
+    #[serde(flatten)]
+    pub command: Command,
+}
+
+impl UserKeybind {
+    fn new(keys: &str, command: Command) -> Self {
+        Self {
+            keys: keys.to_owned(),
+            command,
+        }
+    }
+}
+
+/// The contents of keybinds.json, which is a list of keybinds. A file in the
+/// format used before keybinds were commands is migrated when it is read.
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct UserKeybinds {
+    pub keybinds: Vec<UserKeybind>,
+}
+
+impl<'de> Deserialize<'de> for UserKeybinds {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let keybinds = match value {
+            serde_json::Value::Array(_) => {
+                serde_json::from_value(value).map_err(D::Error::custom)?
+            }
+            _ => serde_json::from_value::<LegacyUserKeybinds>(value)
+                .map_err(D::Error::custom)?
+                .migrate(),
+        };
+        return Ok(Self { keybinds });
+    }
+}
+
+impl Default for UserKeybinds {
+    fn default() -> Self {
+        let mut keybinds = vec![
+            UserKeybind::new(
+                "alt j",
+                Command::Focus {
+                    direction: CycleDirection::Previous,
+                },
+            ),
+            UserKeybind::new(
+                "alt k",
+                Command::Focus {
+                    direction: CycleDirection::Next,
+                },
+            ),
+            UserKeybind::new(
+                "alt shift j",
+                Command::Swap {
+                    direction: CycleDirection::Previous,
+                },
+            ),
+            UserKeybind::new(
+                "alt shift k",
+                Command::Swap {
+                    direction: CycleDirection::Next,
+                },
+            ),
+            UserKeybind::new(
+                "alt y",
+                Command::CycleLayout {
+                    direction: CycleDirection::Previous,
+                },
+            ),
+            UserKeybind::new(
+                "alt o",
+                Command::CycleLayout {
+                    direction: CycleDirection::Next,
+                },
+            ),
+            UserKeybind::new(
+                "alt h",
+                Command::CycleVariant {
+                    index: 0,
+                    direction: CycleDirection::Previous,
+                },
+            ),
+            UserKeybind::new(
+                "alt l",
+                Command::CycleVariant {
+                    index: 0,
+                    direction: CycleDirection::Next,
+                },
+            ),
+            UserKeybind::new(
+                "alt shift h",
+                Command::CycleVariant {
+                    index: 1,
+                    direction: CycleDirection::Previous,
+                },
+            ),
+            UserKeybind::new(
+                "alt shift l",
+                Command::CycleVariant {
+                    index: 1,
+                    direction: CycleDirection::Next,
+                },
+            ),
+            UserKeybind::new(
+                "alt u",
+                Command::CycleFocusedMonitor {
+                    direction: CycleDirection::Previous,
+                },
+            ),
+            UserKeybind::new(
+                "alt i",
+                Command::CycleFocusedMonitor {
+                    direction: CycleDirection::Next,
+                },
+            ),
+            UserKeybind::new(
+                "alt shift u",
+                Command::MoveToMonitor {
+                    direction: CycleDirection::Previous,
+                },
+            ),
+            UserKeybind::new(
+                "alt shift i",
+                Command::MoveToMonitor {
+                    direction: CycleDirection::Next,
+                },
+            ),
+            UserKeybind::new("alt p", Command::GrabWindow),
+            UserKeybind::new("alt shift p", Command::ReleaseWindow),
+            UserKeybind::new("alt shift space", Command::ToggleWindow),
+            UserKeybind::new("alt n", Command::ToggleWorkspace),
+            UserKeybind::new("alt r", Command::RefreshWorkspace),
+            UserKeybind::new("alt shift r", Command::RestartHimewm),
+        ];
+        for (direction, key) in [
+            (Direction::Left, "h"),
+            (Direction::Down, "j"),
+            (Direction::Up, "k"),
+            (Direction::Right, "l"),
+        ] {
+            keybinds.push(UserKeybind::new(
+                &format!("alt ctrl {key}"),
+                Command::Grow {
+                    direction: direction.to_owned(),
+                },
+            ));
+            keybinds.push(UserKeybind::new(
+                &format!("alt ctrl shift {key}"),
+                Command::Shrink { direction },
+            ));
+        }
+        // Windows reserves most chords with the win key, so the directional
+        // commands use the arrow keys, but not with only ctrl and alt, which
+        // graphics drivers use to rotate the screen
+        for (direction, key) in [
+            (Direction::Left, "left"),
+            (Direction::Down, "down"),
+            (Direction::Up, "up"),
+            (Direction::Right, "right"),
+        ] {
+            keybinds.push(UserKeybind::new(
+                &format!("alt shift {key}"),
+                Command::FocusDirection {
+                    direction: direction.to_owned(),
+                },
+            ));
+            keybinds.push(UserKeybind::new(
+                &format!("alt ctrl shift {key}"),
+                Command::SwapDirection { direction },
+            ));
+        }
+        keybinds.extend([
+            UserKeybind::new("alt ctrl w", Command::IncreaseWeight),
+            UserKeybind::new("alt ctrl shift w", Command::DecreaseWeight),
+            UserKeybind::new("alt ctrl e", Command::ResetWeights),
+            UserKeybind::new("alt m", Command::ToggleMonocle),
+            UserKeybind::new(
+                "alt ctrl y",
+                Command::JoinGroup {
+                    direction: CycleDirection::Previous,
+                },
+            ),
+            UserKeybind::new(
+                "alt ctrl o",
+                Command::JoinGroup {
+                    direction: CycleDirection::Next,
+                },
+            ),
+            UserKeybind::new("alt ctrl g", Command::SplitGroup),
+            UserKeybind::new(
+                "alt ctrl u",
+                Command::CycleGroup {
+                    direction: CycleDirection::Previous,
+                },
+            ),
+            UserKeybind::new(
+                "alt ctrl i",
+                Command::CycleGroup {
+                    direction: CycleDirection::Next,
+                },
+            ),
+            UserKeybind::new("alt ctrl b", Command::IncreaseGaps),
+            UserKeybind::new("alt ctrl shift b", Command::DecreaseGaps),
+            UserKeybind::new("alt ctrl n", Command::ToggleGaps),
+            UserKeybind::new("alt ctrl space", Command::PromoteToMaster),
+            UserKeybind::new("alt ctrl a", Command::IncreaseMasterCount),
+            UserKeybind::new("alt ctrl shift a", Command::DecreaseMasterCount),
+            UserKeybind::new("alt ctrl m", Command::GrowMaster),
+            UserKeybind::new("alt ctrl shift m", Command::ShrinkMaster),
+            UserKeybind::new(
+                "alt ctrl shift u",
+                Command::Rotate {
+                    direction: CycleDirection::Previous,
+                },
+            ),
+            UserKeybind::new(
+                "alt ctrl shift i",
+                Command::Rotate {
+                    direction: CycleDirection::Next,
+                },
+            ),
+            UserKeybind::new(
+                "alt shift y",
+                Command::MoveToDesktop {
+                    direction: CycleDirection::Previous,
+                    follow: false,
+                },
+            ),
+            UserKeybind::new(
+                "alt shift o",
+                Command::MoveToDesktop {
+                    direction: CycleDirection::Next,
+                    follow: false,
+                },
+            ),
+            UserKeybind::new(
+                "alt ctrl shift y",
+                Command::MoveToDesktop {
+                    direction: CycleDirection::Previous,
+                    follow: true,
+                },
+            ),
+            UserKeybind::new(
+                "alt ctrl shift o",
+                Command::MoveToDesktop {
+                    direction: CycleDirection::Next,
+                    follow: true,
+                },
+            ),
+        ]);
+        for index in 0..9 {
+            keybinds.push(UserKeybind::new(
+                &format!("alt {}", index + 1),
+                Command::MoveToIndex { index },
+            ));
+            keybinds.push(UserKeybind::new(
+                &format!("alt shift {}", index + 1),
+                Command::MoveToDesktopIndex {
+                    index,
+                    follow: false,
+                },
+            ));
+        }
+        return Self { keybinds };
+    }
+}
+
+#[derive(Deserialize)]
+struct LegacyVariantKeybinds {
+    previous: String,
+    next: String,
+}
+
+#[derive(Deserialize)]
+struct LegacyVariantKeybind {
+    index: usize,
+    keybinds: LegacyVariantKeybinds,
+}
+
+/// The format of keybinds.json before keybinds were commands, with a field
+/// for each action.
+#[derive(Deserialize)]
+struct LegacyUserKeybinds {
+    focus_previous: String,
+    focus_next: String,
+    swap_previous: String,
+    swap_next: String,
+    layout_previous: String,
+    layout_next: String,
+    focus_previous_monitor: String,
+    focus_next_monitor: String,
+    move_to_previous_monitor: String,
+    move_to_next_monitor: String,
+    grab_window: String,
+    release_window: String,
+    toggle_window: String,
+    toggle_workspace: String,
+    refresh_workspace: String,
+    restart_himewm: String,
+    variant_keybinds: Vec<LegacyVariantKeybind>,
+}
+
+impl LegacyUserKeybinds {
+    /// Converts each field into the keybind for its command, in the order of
+    /// the fields. Fields that are empty were never bound, so they are left
+    /// out.
+    fn migrate(self) -> Vec<UserKeybind> {
+        let mut ret = Vec::new();
+        let mut push = |keys: String, command: Command| {
+            if !keys.is_empty() {
+                ret.push(UserKeybind { keys, command });
+            }
+        };
+        push(
+            self.focus_previous,
+            Command::Focus {
+                direction: CycleDirection::Previous,
+            },
+        );
+        push(
+            self.focus_next,
+            Command::Focus {
+                direction: CycleDirection::Next,
+            },
+        );
+        push(
+            self.swap_previous,
+            Command::Swap {
+                direction: CycleDirection::Previous,
+            },
+        );
+        push(
+            self.swap_next,
+            Command::Swap {
+                direction: CycleDirection::Next,
+            },
+        );
+        push(
+            self.layout_previous,
+            Command::CycleLayout {
+                direction: CycleDirection::Previous,
+            },
+        );
+        push(
+            self.layout_next,
+            Command::CycleLayout {
+                direction: CycleDirection::Next,
+            },
+        );
+        push(
+            self.focus_previous_monitor,
+            Command::CycleFocusedMonitor {
+                direction: CycleDirection::Previous,
+            },
+        );
+        push(
+            self.focus_next_monitor,
+            Command::CycleFocusedMonitor {
+                direction: CycleDirection::Next,
+            },
+        );
+        push(
+            self.move_to_previous_monitor,
+            Command::MoveToMonitor {
+                direction: CycleDirection::Previous,
+            },
+        );
+        push(
+            self.move_to_next_monitor,
+            Command::MoveToMonitor {
+                direction: CycleDirection::Next,
+            },
+        );
+        push(self.grab_window, Command::GrabWindow);
+        push(self.release_window, Command::ReleaseWindow);
+        push(self.toggle_window, Command::ToggleWindow);
+        push(self.toggle_workspace, Command::ToggleWorkspace);
+        push(self.refresh_workspace, Command::RefreshWorkspace);
+        push(self.restart_himewm, Command::RestartHimewm);
+        for keybind in self.variant_keybinds {
+            push(
+                keybind.keybinds.previous,
+                Command::CycleVariant {
+                    index: keybind.index,
+                    direction: CycleDirection::Previous,
+                },
+            );
+            push(
+                keybind.keybinds.next,
+                Command::CycleVariant {
+                    index: keybind.index,
+                    direction: CycleDirection::Next,
+                },
+            );
+        }
+        return ret;
+    }
+}
@@ -4,6 +4,7 @@ pub struct Directories {
     pub himewm_dir: std::path::PathBuf,
     pub config_dir: std::path::PathBuf,
     pub layouts_dir: std::path::PathBuf,
+    pub recordings_dir: std::path::PathBuf,
 }
 
 impl Directories {
@@ -12,18 +13,26 @@ impl Directories {
         let himewm_dir = base_dirs.config_dir().join("himewm");
         let config_dir = himewm_dir.join("config");
         let layouts_dir = himewm_dir.join("layouts");
+        let recordings_dir = himewm_dir.join("recordings");
         return Self {
             himewm_dir,
             config_dir,
             layouts_dir,
+            recordings_dir,
         };
     }
 }
 
+impl Default for Directories {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
 pub fn create_dirs() -> std::io::Result<()> {
     let dirs = Directories::new();
-    let _himewm_dir = std::fs::create_dir(dirs.himewm_dir)?;
-    let _config_dir = std::fs::create_dir(dirs.config_dir)?;
-    let _layouts_dir = std::fs::create_dir(dirs.layouts_dir)?;
+    std::fs::create_dir(dirs.himewm_dir)?;
+    std::fs::create_dir(dirs.config_dir)?;
+    std::fs::create_dir(dirs.layouts_dir)?;
     return Ok(());
 }
@@ -26,20 +26,21 @@ pub fn initialize_layouts(
                                 continue;
                             }
                         };
-                    let layout = match Layout::try_from(user_layout) {
-                        Ok(l) => l,
-                        Err(e) => {
-                            util::add_to_message(
-                                warnings_string,
-                                &format!(
-                                    "Warning: An error occurred when parsing layout {}:\n{}",
-                                    layout_name.display(),
-                                    e
-                                ),
+                    let layout = Layout::from(user_layout);
+                    match layout.validate() {
+                        Ok(_) => (),
+                        Err(errors) => {
+                            let mut message = format!(
+                                "Warning: Layout {} is invalid and will not be used:",
+                                layout_name.display()
                             );
+                            for error in errors {
+                                message += &format!("\n{}", error);
+                            }
+                            util::add_to_message(warnings_string, &message);
                             continue;
                         }
-                    };
+                    }
                     ret.push((layout_name, layout));
                 }
                 Err(_) => continue,
@@ -55,7 +56,7 @@ pub fn initialize_layouts(
 }
 
 pub fn get_layout_idx_map(
-    layout_vector: &Vec<(std::path::PathBuf, Layout)>,
+    layout_vector: &[(std::path::PathBuf, Layout)],
 ) -> std::collections::HashMap<String, usize> {
     let mut ret = std::collections::HashMap::new();
     for (i, (layout_name, _)) in layout_vector.iter().enumerate() {
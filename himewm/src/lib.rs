@@ -1,10 +1,16 @@
+pub mod commands;
 pub mod directories;
+#[cfg(windows)]
 pub mod keybinds;
 pub mod layouts;
+pub mod platform;
 pub mod settings;
+#[cfg(windows)]
 pub mod tray_icon;
+#[cfg(windows)]
 pub mod user_config;
 pub mod util;
 pub mod window_rules;
+#[cfg(windows)]
 pub mod windows_api;
 pub mod wm;
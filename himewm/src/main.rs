@@ -1,6 +1,14 @@
+#[cfg(windows)]
 use himewm::*;
+#[cfg(windows)]
 use windows::Win32::UI::WindowsAndMessaging::*;
 
+#[cfg(not(windows))]
+fn main() {
+    eprintln!("himewm only runs on Windows");
+}
+
+#[cfg(windows)]
 fn main() {
     let console_hwnd = util::get_console_hwnd();
     let _hide_console_window = windows_api::show_window(console_hwnd, SW_HIDE);
@@ -27,6 +35,7 @@ fn main() {
         windows_api::post_quit_message(0);
     }
     let mut window_manager: Option<wm::WindowManager> = None;
+    let mut recorder: Option<wm::replay::Recorder> = None;
     tray_icon::set_menu_event_handler();
     let mut previous_keybinds = None;
     let mut msg = MSG::default();
@@ -37,6 +46,9 @@ fn main() {
         windows_api::dispatch_message(&msg);
         match &mut window_manager {
             Some(wm) if !wm.restart_requested() => {
+                if let Some(r) = &mut recorder {
+                    r.record(msg.message, msg.wParam.0, wm.platform());
+                }
                 wm::message_handler::handle_message(msg, wm);
             }
             _ => {
@@ -51,10 +63,14 @@ fn main() {
                     mut warnings,
                     errors,
                 } = user_config::get_user_config();
+                if let (Some(r), Some(wm)) = (recorder.take(), &window_manager) {
+                    save_recording(r, wm, &mut warnings);
+                }
                 if let Some(registered_keybinds) = previous_keybinds {
                     keybinds::unregister_hotkeys(registered_keybinds, &mut warnings);
                 }
                 keybinds::register_hotkeys(&keybinds, &mut warnings);
+                let commands = keybinds.commands();
                 previous_keybinds = Some(keybinds);
                 let mut message_type = util::MessageType::None;
                 let mut message = String::new();
@@ -74,19 +90,18 @@ fn main() {
                         windows_api::post_quit_message(0);
                     }
                     _ => {
-                        let mut existing_event_hook = None;
-                        let mut existing_vd_manager = None;
-                        if let Some(wm) = window_manager {
-                            existing_vd_manager = Some(wm.virtual_desktop_manager().to_owned());
-                            existing_event_hook = Some(wm.event_hook());
-                        }
-                        window_manager = Some(wm::WindowManager::new(
-                            settings,
-                            window_rules,
-                            existing_event_hook,
-                            existing_vd_manager,
-                        ));
+                        let platform: Box<dyn platform::Platform> = match window_manager {
+                            Some(wm) => wm.into_platform(),
+                            None => Box::new(platform::win32::Win32Platform::new()),
+                        };
+                        window_manager =
+                            Some(wm::WindowManager::new(settings, window_rules, platform));
                         if let Some(wm) = &mut window_manager {
+                            wm.set_commands(commands);
+                            if wm.settings().record_events {
+                                recorder =
+                                    Some(wm::replay::Recorder::in_recordings_dir(wm.platform()));
+                            }
                             wm.initialize(layouts);
                         }
                     }
@@ -95,6 +110,28 @@ fn main() {
         }
     }
     if let Some(wm) = window_manager {
+        if let Some(r) = recorder {
+            let mut warnings = String::new();
+            save_recording(r, &wm, &mut warnings);
+            if !warnings.is_empty() {
+                util::display_message(console_hwnd, &util::MessageType::Warning, &warnings);
+            }
+        }
         wm.exit();
     }
 }
+
+#[cfg(windows)]
+fn save_recording(recorder: wm::replay::Recorder, wm: &wm::WindowManager, warnings: &mut String) {
+    let path = recorder.path().to_owned();
+    if let Err(e) = recorder.save(wm) {
+        util::add_to_message(
+            warnings,
+            &format!(
+                "Warning: Failed to save event recording to {}:\n{}",
+                path.display(),
+                e
+            ),
+        );
+    }
+}
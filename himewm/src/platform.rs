@@ -0,0 +1,165 @@
+use himewm_layout::position::Position;
+
+#[cfg(windows)]
+mod cb;
+pub mod fake;
+#[cfg(windows)]
+mod virtual_desktops;
+#[cfg(windows)]
+pub mod win32;
+
+pub use windows_core::GUID;
+
+#[cfg(windows)]
+pub use windows::Win32::{
+    Foundation::{COLORREF, HWND},
+    Graphics::{
+        Dwm::{DWMWA_COLOR_DEFAULT, DWMWA_COLOR_NONE},
+        Gdi::HMONITOR,
+    },
+    UI::WindowsAndMessaging::{WM_APP, WM_HOTKEY},
+};
+
+#[cfg(not(windows))]
+pub use stand_ins::*;
+
+/// Layout-compatible stand-ins for the Win32 types used outside of the Win32
+/// platform, so that the window manager can be built on other targets.
+#[cfg(not(windows))]
+mod stand_ins {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct HWND(pub *mut core::ffi::c_void);
+
+    impl HWND {
+        pub fn is_invalid(&self) -> bool {
+            self.0.is_null()
+        }
+    }
+
+    impl Default for HWND {
+        fn default() -> Self {
+            Self(std::ptr::null_mut())
+        }
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct HMONITOR(pub *mut core::ffi::c_void);
+
+    impl HMONITOR {
+        pub fn is_invalid(&self) -> bool {
+            self.0 == -1 as _ || self.0.is_null()
+        }
+    }
+
+    impl Default for HMONITOR {
+        fn default() -> Self {
+            Self(std::ptr::null_mut())
+        }
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+    pub struct COLORREF(pub u32);
+
+    pub const DWMWA_COLOR_DEFAULT: u32 = 4294967295u32;
+    pub const DWMWA_COLOR_NONE: u32 = 4294967294u32;
+    pub const WM_APP: u32 = 32768u32;
+    pub const WM_HOTKEY: u32 = 786u32;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    AccessDenied,
+    Failed,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShowWindowCommand {
+    Hide,
+    Show,
+    Minimize,
+    Restore,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CornerPreference {
+    Default,
+    DoNotRound,
+}
+
+/// Everything the window manager needs from the desktop it is managing.
+pub trait Platform {
+    fn enum_display_monitors(&self) -> Vec<HMONITOR>;
+
+    fn enum_windows(&self) -> Vec<HWND>;
+
+    fn get_monitor_work_area(&self, hmonitor: HMONITOR) -> Position;
+
+    /// The whole area of the monitor, including the taskbar.
+    fn get_monitor_rect(&self, hmonitor: HMONITOR) -> Position;
+
+    /// The name of the display device, like `\\.\DISPLAY1`.
+    fn get_monitor_device_name(&self, hmonitor: HMONITOR) -> String;
+
+    fn get_dpi_for_monitor(&self, hmonitor: HMONITOR) -> u32;
+
+    fn get_dpi_for_window(&self, hwnd: HWND) -> u32;
+
+    fn get_foreground_window(&self) -> HWND;
+
+    fn set_foreground_window(&self, hwnd: HWND) -> bool;
+
+    /// Returns `None` if the desktop of the window could not be queried.
+    fn get_window_desktop_id(&self, hwnd: HWND) -> Option<GUID>;
+
+    /// The virtual desktops in the order they are shown in Task View.
+    fn get_desktop_ids(&self) -> Vec<GUID>;
+
+    /// Returns `None` if the desktop has not been given a name.
+    fn get_desktop_name(&self, guid: GUID) -> Option<String>;
+
+    /// Moves a window of any process to another virtual desktop.
+    fn move_window_to_desktop(&self, hwnd: HWND, guid: GUID) -> Result<(), Error>;
+
+    fn switch_desktop(&self, guid: GUID) -> Result<(), Error>;
+
+    /// Returns an invalid handle if the window is not on any monitor.
+    fn monitor_from_window(&self, hwnd: HWND) -> HMONITOR;
+
+    fn get_window_rect(&self, hwnd: HWND) -> Option<Position>;
+
+    fn set_window_pos(&self, hwnd: HWND, x: i32, y: i32, w: i32, h: i32) -> Result<(), Error>;
+
+    fn show_window(&self, hwnd: HWND, command: ShowWindowCommand) -> bool;
+
+    fn is_window(&self, hwnd: HWND) -> bool;
+
+    fn is_window_visible(&self, hwnd: HWND) -> bool;
+
+    /// A window is restored if it is visible, resizable and not minimized,
+    /// maximized or snapped.
+    fn is_restored(&self, hwnd: HWND) -> bool;
+
+    fn has_sizebox(&self, hwnd: HWND) -> bool;
+
+    fn is_overlapped_window(&self, hwnd: HWND) -> bool;
+
+    fn get_window_title(&self, hwnd: HWND) -> Option<String>;
+
+    fn get_exe_name(&self, hwnd: HWND) -> Option<String>;
+
+    /// The smallest size the window can be resized to, as reported by the
+    /// window itself.
+    fn get_min_size(&self, hwnd: HWND) -> Option<(i32, i32)>;
+
+    fn set_border_colour(&self, hwnd: HWND, colour: COLORREF);
+
+    fn set_corner_preference(&self, hwnd: HWND, preference: CornerPreference);
+
+    /// Starts a process without waiting for it.
+    fn spawn(&self, command: &str, args: &[String]) -> Result<(), Error>;
+
+    /// Posts a message to the thread running the window manager.
+    fn post_message(&self, msg: u32, wparam: usize);
+
+    fn uninitialize(&self);
+}
@@ -1,11 +1,8 @@
-use crate::{windows_api, wm};
-use windows::{
-    core::*,
-    Win32::{
-        Foundation::*,
-        Graphics::Gdi::*,
-        UI::{Accessibility::*, WindowsAndMessaging::*},
-    },
+use crate::{platform::win32, windows_api, wm};
+use windows::Win32::{
+    Foundation::*,
+    Graphics::Gdi::*,
+    UI::{Accessibility::*, WindowsAndMessaging::*},
 };
 
 pub extern "system" fn event_handler(
@@ -18,10 +15,10 @@ pub extern "system" fn event_handler(
     _dwmseventtime: u32,
 ) {
     if event == EVENT_OBJECT_DESTROY {
-        if !wm::util::has_sizebox(hwnd) {
+        if !win32::has_sizebox(hwnd) {
             return;
         }
-    } else if !wm::util::is_overlapped_window(hwnd) {
+    } else if !win32::is_overlapped_window(hwnd) {
         return;
     }
     match event {
@@ -44,7 +41,7 @@ pub extern "system" fn event_handler(
             .unwrap();
         }
         EVENT_OBJECT_LOCATIONCHANGE => {
-            if wm::util::is_restored(hwnd) {
+            if win32::is_restored(hwnd) {
                 windows_api::post_message(
                     None,
                     wm::messages::messages::WINDOW_RESTORED,
@@ -112,19 +109,8 @@ pub extern "system" fn event_handler(
 }
 
 pub unsafe extern "system" fn enum_windows_callback(hwnd: HWND, lparam: LPARAM) -> BOOL {
-    let wm = &mut *(lparam.0 as *mut wm::WindowManager);
-    let desktop_id = match windows_api::get_window_desktop_id(wm.virtual_desktop_manager(), hwnd) {
-        Ok(guid) if guid != GUID::zeroed() => guid,
-        _ => return true.into(),
-    };
-    let monitor_handle = windows_api::monitor_from_window(hwnd, MONITOR_DEFAULTTONULL);
-    if monitor_handle.is_invalid()
-        || !windows_api::is_window_visible(hwnd).as_bool()
-        || !wm::util::is_overlapped_window(hwnd)
-    {
-        return true.into();
-    }
-    wm.manage_new_window(desktop_id, monitor_handle, hwnd);
+    let hwnds = &mut *(lparam.0 as *mut Vec<HWND>);
+    hwnds.push(hwnd);
     return true.into();
 }
 
@@ -134,8 +120,37 @@ pub unsafe extern "system" fn enum_display_monitors_callback(
     _hdc_monitor: *mut RECT,
     dw_data: LPARAM,
 ) -> BOOL {
-    let wm = &mut *(dw_data.0 as *mut wm::WindowManager);
-    wm.monitor_handles_mut().push(hmonitor);
-    wm.layouts_mut().insert(hmonitor.0, Vec::new());
+    let hmonitors = &mut *(dw_data.0 as *mut Vec<HMONITOR>);
+    hmonitors.push(hmonitor);
     return true.into();
 }
+
+/// The window procedure of the hidden window that is notified when monitors
+/// are added, removed or change their work areas.
+pub unsafe extern "system" fn display_change_window_proc(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    match msg {
+        WM_DISPLAYCHANGE => {
+            let _ = windows_api::post_message(
+                None,
+                wm::messages::messages::DISPLAY_CHANGED,
+                WPARAM::default(),
+                LPARAM::default(),
+            );
+        }
+        WM_SETTINGCHANGE if wparam.0 as u32 == SPI_SETWORKAREA.0 => {
+            let _ = windows_api::post_message(
+                None,
+                wm::messages::messages::DISPLAY_CHANGED,
+                WPARAM::default(),
+                LPARAM::default(),
+            );
+        }
+        _ => (),
+    }
+    return windows_api::def_window_proc(hwnd, msg, wparam, lparam);
+}
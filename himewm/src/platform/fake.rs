@@ -0,0 +1,613 @@
+use crate::{
+    platform::{self, COLORREF, GUID, HMONITOR, HWND},
+    wm,
+};
+use himewm_layout::position::Position;
+
+#[derive(Clone, Debug)]
+pub struct FakeMonitor {
+    pub work_area: Position,
+    pub rect: Position,
+    pub dpi: u32,
+    pub device_name: String,
+}
+
+impl FakeMonitor {
+    /// A monitor without a taskbar, so its rect is its work area.
+    pub fn new(work_area: Position, dpi: u32) -> Self {
+        Self {
+            rect: work_area.clone(),
+            work_area,
+            dpi,
+            device_name: String::new(),
+        }
+    }
+
+    pub fn with_rect(mut self, rect: Position) -> Self {
+        self.rect = rect;
+        self
+    }
+
+    pub fn with_device_name(mut self, device_name: &str) -> Self {
+        self.device_name = device_name.to_owned();
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FakeWindow {
+    pub desktop_id: GUID,
+    pub rect: Position,
+    pub title: String,
+    pub exe_name: String,
+    pub visible: bool,
+    pub cloaked: bool,
+    pub minimized: bool,
+    pub maximized: bool,
+    pub arranged: bool,
+    pub has_sizebox: bool,
+    pub overlapped: bool,
+    pub access_denied: bool,
+    pub min_size: Option<(i32, i32)>,
+    pub border_colour: Option<COLORREF>,
+    pub corner_preference: Option<platform::CornerPreference>,
+}
+
+impl FakeWindow {
+    pub fn new(desktop_id: GUID, rect: Position) -> Self {
+        Self {
+            desktop_id,
+            rect,
+            title: String::new(),
+            exe_name: String::new(),
+            visible: true,
+            cloaked: false,
+            minimized: false,
+            maximized: false,
+            arranged: false,
+            has_sizebox: true,
+            overlapped: true,
+            access_denied: false,
+            min_size: None,
+            border_colour: None,
+            corner_preference: None,
+        }
+    }
+
+    pub fn with_title(mut self, title: &str) -> Self {
+        self.title = title.to_owned();
+        self
+    }
+
+    pub fn with_exe_name(mut self, exe_name: &str) -> Self {
+        self.exe_name = exe_name.to_owned();
+        self
+    }
+
+    pub fn with_min_size(mut self, w: i32, h: i32) -> Self {
+        self.min_size = Some((w, h));
+        self
+    }
+
+    fn is_restored(&self) -> bool {
+        self.has_sizebox && !self.minimized && !self.maximized && !self.arranged && self.visible
+    }
+}
+
+#[derive(Default)]
+struct FakeDesktop {
+    next_handle: usize,
+    monitors: Vec<(HMONITOR, FakeMonitor)>,
+    windows: Vec<(HWND, FakeWindow)>,
+    desktops: Vec<GUID>,
+    desktop_names: std::collections::HashMap<GUID, String>,
+    current_desktop: usize,
+    foreground_window: HWND,
+    messages: std::collections::VecDeque<(u32, usize)>,
+    spawned: Vec<(String, Vec<String>)>,
+    uninitialized: bool,
+}
+
+impl FakeDesktop {
+    fn new_handle(&mut self) -> *mut core::ffi::c_void {
+        self.next_handle += 1;
+        return self.next_handle as *mut core::ffi::c_void;
+    }
+
+    fn reserve_handle(&mut self, handle: *mut core::ffi::c_void) {
+        self.next_handle = std::cmp::max(self.next_handle, handle as usize);
+    }
+
+    fn window(&self, hwnd: HWND) -> Option<&FakeWindow> {
+        self.windows
+            .iter()
+            .find(|(h, _)| *h == hwnd)
+            .map(|(_, w)| w)
+    }
+
+    fn window_mut(&mut self, hwnd: HWND) -> Option<&mut FakeWindow> {
+        self.windows
+            .iter_mut()
+            .find(|(h, _)| *h == hwnd)
+            .map(|(_, w)| w)
+    }
+
+    fn monitor_from_rect(&self, rect: &Position) -> HMONITOR {
+        let mut ret = (HMONITOR::default(), 0);
+        for (hmonitor, monitor) in &self.monitors {
+            let area = &monitor.work_area;
+            let w = std::cmp::min(rect.x() + rect.w(), area.x() + area.w())
+                - std::cmp::max(rect.x(), area.x());
+            let h = std::cmp::min(rect.y() + rect.h(), area.y() + area.h())
+                - std::cmp::max(rect.y(), area.y());
+            if w > 0 && h > 0 && w * h > ret.1 {
+                ret = (*hmonitor, w * h);
+            }
+        }
+        return ret.0;
+    }
+
+    fn post_event(&mut self, msg: u32, hwnd: HWND) {
+        match self.window(hwnd) {
+            Some(window) if window.overlapped => {
+                self.messages.push_back((msg, hwnd.0 as usize));
+            }
+            _ => (),
+        }
+    }
+}
+
+/// An in-memory desktop that can stand in for Windows.
+///
+/// Clones share the same desktop, so a test can keep a handle to the desktop
+/// after giving the window manager its own. Actions that a user would perform
+/// queue the messages that the Win32 event hook would post for them, which can
+/// be read back with `take_messages`.
+#[derive(Clone, Default)]
+pub struct FakePlatform {
+    desktop: std::rc::Rc<std::cell::RefCell<FakeDesktop>>,
+}
+
+impl FakePlatform {
+    pub fn new() -> Self {
+        let ret = Self::default();
+        ret.add_desktop();
+        return ret;
+    }
+
+    pub fn add_monitor(&self, work_area: Position, dpi: u32) -> HMONITOR {
+        return self.push_monitor(FakeMonitor::new(work_area, dpi));
+    }
+
+    /// Adds a monitor, naming it after its position in the list of monitors if
+    /// it has no device name.
+    pub fn push_monitor(&self, mut monitor: FakeMonitor) -> HMONITOR {
+        let mut desktop = self.desktop.borrow_mut();
+        let hmonitor = HMONITOR(desktop.new_handle());
+        if monitor.device_name.is_empty() {
+            monitor.device_name = format!("\\\\.\\DISPLAY{}", desktop.monitors.len() + 1);
+        }
+        desktop.monitors.push((hmonitor, monitor));
+        return hmonitor;
+    }
+
+    /// Adds a monitor with a specific handle, for example one taken from a
+    /// recording.
+    pub fn insert_monitor(&self, hmonitor: HMONITOR, monitor: FakeMonitor) {
+        let mut desktop = self.desktop.borrow_mut();
+        desktop.reserve_handle(hmonitor.0);
+        desktop.monitors.retain(|(h, _)| *h != hmonitor);
+        desktop.monitors.push((hmonitor, monitor));
+    }
+
+    pub fn remove_monitor(&self, hmonitor: HMONITOR) {
+        self.desktop
+            .borrow_mut()
+            .monitors
+            .retain(|(h, _)| *h != hmonitor);
+    }
+
+    pub fn monitor(&self, hmonitor: HMONITOR) -> Option<FakeMonitor> {
+        self.desktop
+            .borrow()
+            .monitors
+            .iter()
+            .find(|(h, _)| *h == hmonitor)
+            .map(|(_, m)| m.to_owned())
+    }
+
+    pub fn add_desktop(&self) -> GUID {
+        let mut desktop = self.desktop.borrow_mut();
+        let guid = GUID::from_u128(desktop.desktops.len() as u128 + 1);
+        desktop.desktops.push(guid);
+        return guid;
+    }
+
+    pub fn set_desktop_name(&self, guid: GUID, name: &str) {
+        self.desktop
+            .borrow_mut()
+            .desktop_names
+            .insert(guid, name.to_owned());
+    }
+
+    pub fn desktops(&self) -> Vec<GUID> {
+        self.desktop.borrow().desktops.to_owned()
+    }
+
+    pub fn current_desktop(&self) -> GUID {
+        let desktop = self.desktop.borrow();
+        return desktop.desktops[desktop.current_desktop];
+    }
+
+    /// Creates a window without posting any messages, as if it existed before
+    /// the window manager started.
+    pub fn add_window(&self, window: FakeWindow) -> HWND {
+        let mut desktop = self.desktop.borrow_mut();
+        let hwnd = HWND(desktop.new_handle());
+        desktop.windows.push((hwnd, window));
+        return hwnd;
+    }
+
+    /// Adds or replaces the window with a specific handle without posting any
+    /// messages.
+    pub fn insert_window(&self, hwnd: HWND, window: FakeWindow) {
+        let mut desktop = self.desktop.borrow_mut();
+        desktop.reserve_handle(hwnd.0);
+        match desktop.window_mut(hwnd) {
+            Some(w) => *w = window,
+            None => desktop.windows.push((hwnd, window)),
+        }
+    }
+
+    /// Removes a window without posting any messages.
+    pub fn remove_window(&self, hwnd: HWND) {
+        let mut desktop = self.desktop.borrow_mut();
+        desktop.windows.retain(|(h, _)| *h != hwnd);
+        if desktop.foreground_window == hwnd {
+            desktop.foreground_window = HWND::default();
+        }
+    }
+
+    pub fn create_window(&self, window: FakeWindow) -> HWND {
+        let hwnd = self.add_window(window);
+        self.desktop
+            .borrow_mut()
+            .post_event(wm::messages::messages::WINDOW_CREATED, hwnd);
+        return hwnd;
+    }
+
+    pub fn destroy_window(&self, hwnd: HWND) {
+        let mut desktop = self.desktop.borrow_mut();
+        if desktop.window(hwnd).is_some_and(|w| w.has_sizebox) {
+            desktop
+                .messages
+                .push_back((wm::messages::messages::WINDOW_DESTROYED, hwnd.0 as usize));
+        }
+        desktop.windows.retain(|(h, _)| *h != hwnd);
+        if desktop.foreground_window == hwnd {
+            desktop.foreground_window = HWND::default();
+        }
+    }
+
+    pub fn window(&self, hwnd: HWND) -> Option<FakeWindow> {
+        self.desktop.borrow().window(hwnd).map(|w| w.to_owned())
+    }
+
+    pub fn update_window<F: FnOnce(&mut FakeWindow)>(&self, hwnd: HWND, cb: F) {
+        if let Some(window) = self.desktop.borrow_mut().window_mut(hwnd) {
+            cb(window);
+        }
+    }
+
+    /// Moves a window the way a user dragging it would.
+    pub fn move_window(&self, hwnd: HWND, rect: Position) {
+        let mut desktop = self.desktop.borrow_mut();
+        if let Some(window) = desktop.window_mut(hwnd) {
+            window.rect = rect;
+            desktop.post_event(wm::messages::messages::WINDOW_MOVE_FINISHED, hwnd);
+        }
+    }
+
+    pub fn move_window_to_desktop(&self, hwnd: HWND, guid: GUID) {
+        let mut desktop = self.desktop.borrow_mut();
+        let current_desktop = desktop.desktops[desktop.current_desktop];
+        let window = match desktop.window_mut(hwnd) {
+            Some(w) => w,
+            None => return,
+        };
+        window.desktop_id = guid;
+        if guid != current_desktop && !window.cloaked {
+            window.cloaked = true;
+            desktop.post_event(wm::messages::messages::WINDOW_CLOAKED, hwnd);
+        }
+    }
+
+    /// Switches to another virtual desktop, cloaking the windows on the current
+    /// desktop before uncloaking the windows on the new one.
+    pub fn switch_desktop(&self, guid: GUID) {
+        let mut desktop = self.desktop.borrow_mut();
+        let idx = match desktop.desktops.iter().position(|g| *g == guid) {
+            Some(i) => i,
+            None => return,
+        };
+        desktop.current_desktop = idx;
+        let mut cloaked = Vec::new();
+        let mut uncloaked = Vec::new();
+        for (hwnd, window) in desktop.windows.iter_mut() {
+            if window.desktop_id == guid && window.cloaked {
+                window.cloaked = false;
+                uncloaked.push(*hwnd);
+            } else if window.desktop_id != guid && !window.cloaked {
+                window.cloaked = true;
+                cloaked.push(*hwnd);
+            }
+        }
+        for hwnd in cloaked {
+            desktop.post_event(wm::messages::messages::WINDOW_CLOAKED, hwnd);
+        }
+        for hwnd in uncloaked {
+            desktop.post_event(wm::messages::messages::WINDOW_UNCLOAKED, hwnd);
+        }
+    }
+
+    /// Changes the foreground window without posting any messages.
+    pub fn set_foreground(&self, hwnd: HWND) {
+        self.desktop.borrow_mut().foreground_window = hwnd;
+    }
+
+    pub fn messages(&self) -> Vec<(u32, usize)> {
+        self.desktop.borrow().messages.iter().copied().collect()
+    }
+
+    pub fn take_messages(&self) -> Vec<(u32, usize)> {
+        self.desktop.borrow_mut().messages.drain(..).collect()
+    }
+
+    pub fn pop_message(&self) -> Option<(u32, usize)> {
+        self.desktop.borrow_mut().messages.pop_front()
+    }
+
+    pub fn uninitialized(&self) -> bool {
+        self.desktop.borrow().uninitialized
+    }
+
+    /// The commands and arguments of every process started, in order.
+    pub fn spawned(&self) -> Vec<(String, Vec<String>)> {
+        self.desktop.borrow().spawned.to_owned()
+    }
+}
+
+impl platform::Platform for FakePlatform {
+    fn enum_display_monitors(&self) -> Vec<HMONITOR> {
+        self.desktop
+            .borrow()
+            .monitors
+            .iter()
+            .map(|(h, _)| *h)
+            .collect()
+    }
+
+    fn enum_windows(&self) -> Vec<HWND> {
+        self.desktop
+            .borrow()
+            .windows
+            .iter()
+            .map(|(h, _)| *h)
+            .collect()
+    }
+
+    fn get_monitor_work_area(&self, hmonitor: HMONITOR) -> Position {
+        match self.monitor(hmonitor) {
+            Some(monitor) => monitor.work_area,
+            None => Position::new(0, 0, 0, 0),
+        }
+    }
+
+    fn get_monitor_rect(&self, hmonitor: HMONITOR) -> Position {
+        match self.monitor(hmonitor) {
+            Some(monitor) => monitor.rect,
+            None => Position::new(0, 0, 0, 0),
+        }
+    }
+
+    fn get_monitor_device_name(&self, hmonitor: HMONITOR) -> String {
+        match self.monitor(hmonitor) {
+            Some(monitor) => monitor.device_name,
+            None => String::new(),
+        }
+    }
+
+    fn get_dpi_for_monitor(&self, hmonitor: HMONITOR) -> u32 {
+        match self.monitor(hmonitor) {
+            Some(monitor) => monitor.dpi,
+            None => 96,
+        }
+    }
+
+    fn get_dpi_for_window(&self, hwnd: HWND) -> u32 {
+        self.get_dpi_for_monitor(self.monitor_from_window(hwnd))
+    }
+
+    fn get_foreground_window(&self) -> HWND {
+        self.desktop.borrow().foreground_window
+    }
+
+    fn set_foreground_window(&self, hwnd: HWND) -> bool {
+        let mut desktop = self.desktop.borrow_mut();
+        if desktop.window(hwnd).is_none() {
+            return false;
+        }
+        if desktop.foreground_window != hwnd {
+            desktop.foreground_window = hwnd;
+            desktop.post_event(wm::messages::messages::FOREGROUND_WINDOW_CHANGED, hwnd);
+        }
+        return true;
+    }
+
+    fn get_window_desktop_id(&self, hwnd: HWND) -> Option<GUID> {
+        self.desktop.borrow().window(hwnd).map(|w| w.desktop_id)
+    }
+
+    fn get_desktop_ids(&self) -> Vec<GUID> {
+        self.desktops()
+    }
+
+    fn get_desktop_name(&self, guid: GUID) -> Option<String> {
+        self.desktop.borrow().desktop_names.get(&guid).cloned()
+    }
+
+    fn move_window_to_desktop(&self, hwnd: HWND, guid: GUID) -> Result<(), platform::Error> {
+        if self.window(hwnd).is_none() || !self.desktops().contains(&guid) {
+            return Err(platform::Error::Failed);
+        }
+        FakePlatform::move_window_to_desktop(self, hwnd, guid);
+        return Ok(());
+    }
+
+    fn switch_desktop(&self, guid: GUID) -> Result<(), platform::Error> {
+        if !self.desktops().contains(&guid) {
+            return Err(platform::Error::Failed);
+        }
+        FakePlatform::switch_desktop(self, guid);
+        return Ok(());
+    }
+
+    fn monitor_from_window(&self, hwnd: HWND) -> HMONITOR {
+        let desktop = self.desktop.borrow();
+        match desktop.window(hwnd) {
+            Some(window) => desktop.monitor_from_rect(&window.rect),
+            None => HMONITOR::default(),
+        }
+    }
+
+    fn get_window_rect(&self, hwnd: HWND) -> Option<Position> {
+        self.desktop
+            .borrow()
+            .window(hwnd)
+            .map(|w| w.rect.to_owned())
+    }
+
+    fn set_window_pos(
+        &self,
+        hwnd: HWND,
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+    ) -> Result<(), platform::Error> {
+        match self.desktop.borrow_mut().window_mut(hwnd) {
+            Some(window) if window.access_denied => return Err(platform::Error::AccessDenied),
+            Some(window) => {
+                window.rect = Position::new(x, y, w, h);
+                return Ok(());
+            }
+            None => return Err(platform::Error::Failed),
+        }
+    }
+
+    fn show_window(&self, hwnd: HWND, command: platform::ShowWindowCommand) -> bool {
+        let mut desktop = self.desktop.borrow_mut();
+        let window = match desktop.window_mut(hwnd) {
+            Some(w) => w,
+            None => return false,
+        };
+        let was_visible = window.visible;
+        let was_restored = window.is_restored();
+        match command {
+            platform::ShowWindowCommand::Hide => window.visible = false,
+            platform::ShowWindowCommand::Show => window.visible = true,
+            platform::ShowWindowCommand::Minimize => window.minimized = true,
+            platform::ShowWindowCommand::Restore => {
+                window.visible = true;
+                window.minimized = false;
+                window.maximized = false;
+                window.arranged = false;
+            }
+        }
+        let is_restored = window.is_restored();
+        if was_visible && !window.visible {
+            desktop.post_event(wm::messages::messages::STOP_MANAGING_WINDOW, hwnd);
+        } else if !was_visible && window.visible {
+            desktop.post_event(wm::messages::messages::WINDOW_CREATED, hwnd);
+        } else if was_restored && !is_restored {
+            desktop.post_event(wm::messages::messages::STOP_MANAGING_WINDOW, hwnd);
+        } else if !was_restored && is_restored {
+            desktop.post_event(wm::messages::messages::WINDOW_RESTORED, hwnd);
+        }
+        return was_visible;
+    }
+
+    fn is_window(&self, hwnd: HWND) -> bool {
+        self.desktop.borrow().window(hwnd).is_some()
+    }
+
+    fn is_window_visible(&self, hwnd: HWND) -> bool {
+        self.desktop
+            .borrow()
+            .window(hwnd)
+            .is_some_and(|w| w.visible)
+    }
+
+    fn is_restored(&self, hwnd: HWND) -> bool {
+        self.desktop
+            .borrow()
+            .window(hwnd)
+            .is_some_and(|w| w.is_restored())
+    }
+
+    fn has_sizebox(&self, hwnd: HWND) -> bool {
+        self.desktop
+            .borrow()
+            .window(hwnd)
+            .is_some_and(|w| w.has_sizebox)
+    }
+
+    fn is_overlapped_window(&self, hwnd: HWND) -> bool {
+        self.desktop
+            .borrow()
+            .window(hwnd)
+            .is_some_and(|w| w.overlapped)
+    }
+
+    fn get_window_title(&self, hwnd: HWND) -> Option<String> {
+        self.desktop
+            .borrow()
+            .window(hwnd)
+            .map(|w| w.title.to_owned())
+    }
+
+    fn get_exe_name(&self, hwnd: HWND) -> Option<String> {
+        self.desktop
+            .borrow()
+            .window(hwnd)
+            .map(|w| w.exe_name.to_owned())
+    }
+
+    fn get_min_size(&self, hwnd: HWND) -> Option<(i32, i32)> {
+        self.desktop.borrow().window(hwnd).and_then(|w| w.min_size)
+    }
+
+    fn set_border_colour(&self, hwnd: HWND, colour: COLORREF) {
+        self.update_window(hwnd, |w| w.border_colour = Some(colour));
+    }
+
+    fn set_corner_preference(&self, hwnd: HWND, preference: platform::CornerPreference) {
+        self.update_window(hwnd, |w| w.corner_preference = Some(preference));
+    }
+
+    fn spawn(&self, command: &str, args: &[String]) -> Result<(), platform::Error> {
+        self.desktop
+            .borrow_mut()
+            .spawned
+            .push((command.to_owned(), args.to_owned()));
+        return Ok(());
+    }
+
+    fn post_message(&self, msg: u32, wparam: usize) {
+        self.desktop.borrow_mut().messages.push_back((msg, wparam));
+    }
+
+    fn uninitialize(&self) {
+        self.desktop.borrow_mut().uninitialized = true;
+    }
+}
@@ -0,0 +1,132 @@
+//! The undocumented interfaces that Explorer uses to move windows between
+//! virtual desktops and to switch between them, which `IVirtualDesktopManager`
+//! cannot do for the windows of other processes.
+//!
+//! Their identifiers and layouts change between builds of Windows. These match
+//! Windows 11 24H2, and on other builds the interfaces are not found, so the
+//! commands that need them do nothing.
+
+// The methods keep the names they have in Windows
+#![allow(non_snake_case)]
+
+use crate::{platform, windows_api};
+use windows::Win32::{Foundation::*, System::Com::*};
+use windows_core::*;
+
+const CLSID_IMMERSIVE_SHELL: GUID = GUID::from_u128(0xc2f03a33_21f5_47fa_b4bb_156362a2f239);
+
+const CLSID_VIRTUAL_DESKTOP_MANAGER_INTERNAL: GUID =
+    GUID::from_u128(0xc5e0cdca_7b6e_41b2_9fc4_d93975cc467b);
+
+#[interface("372E1D3B-38D3-42E4-A15B-8AB2B178F513")]
+unsafe trait IApplicationView: IUnknown {}
+
+#[interface("1841C6D7-4F9D-42C0-AF41-8747538F10E5")]
+unsafe trait IApplicationViewCollection: IUnknown {
+    fn GetViews(&self, views: *mut *mut core::ffi::c_void) -> HRESULT;
+    fn GetViewsByZOrder(&self, views: *mut *mut core::ffi::c_void) -> HRESULT;
+    fn GetViewsByAppUserModelId(&self, id: PCWSTR, views: *mut *mut core::ffi::c_void) -> HRESULT;
+    fn GetViewForHwnd(&self, hwnd: HWND, view: *mut Option<IApplicationView>) -> HRESULT;
+}
+
+#[interface("3F07F4BE-B107-441A-AF0F-39D82529072C")]
+unsafe trait IVirtualDesktop: IUnknown {}
+
+// Only the methods up to `FindDesktop` are declared, but every one before it
+// is needed to keep the layout of the vtable
+#[interface("53F5CA0B-158F-4124-900C-057158060B27")]
+unsafe trait IVirtualDesktopManagerInternal: IUnknown {
+    fn GetCount(&self, count: *mut i32) -> HRESULT;
+    fn MoveViewToDesktop(
+        &self,
+        view: *mut core::ffi::c_void,
+        desktop: *mut core::ffi::c_void,
+    ) -> HRESULT;
+    fn CanViewMoveDesktops(&self, view: *mut core::ffi::c_void, can_move: *mut BOOL) -> HRESULT;
+    fn GetCurrentDesktop(&self, desktop: *mut *mut core::ffi::c_void) -> HRESULT;
+    fn GetDesktops(&self, desktops: *mut *mut core::ffi::c_void) -> HRESULT;
+    fn GetAdjacentDesktop(
+        &self,
+        from: *mut core::ffi::c_void,
+        direction: i32,
+        desktop: *mut *mut core::ffi::c_void,
+    ) -> HRESULT;
+    fn SwitchDesktop(&self, desktop: *mut core::ffi::c_void) -> HRESULT;
+    fn SwitchDesktopAndMoveForegroundView(&self, desktop: *mut core::ffi::c_void) -> HRESULT;
+    fn CreateDesktop(&self, desktop: *mut *mut core::ffi::c_void) -> HRESULT;
+    fn MoveDesktop(&self, desktop: *mut core::ffi::c_void, idx: i32) -> HRESULT;
+    fn RemoveDesktop(
+        &self,
+        desktop: *mut core::ffi::c_void,
+        fallback: *mut core::ffi::c_void,
+    ) -> HRESULT;
+    fn FindDesktop(&self, id: *const GUID, desktop: *mut Option<IVirtualDesktop>) -> HRESULT;
+}
+
+pub struct VirtualDesktopManagerInternal {
+    manager: IVirtualDesktopManagerInternal,
+    views: IApplicationViewCollection,
+}
+
+impl VirtualDesktopManagerInternal {
+    /// Returns `None` if the interfaces are not available on this build of
+    /// Windows.
+    pub fn new() -> Option<Self> {
+        let shell: IServiceProvider =
+            windows_api::co_create_instance(&CLSID_IMMERSIVE_SHELL, None, CLSCTX_LOCAL_SERVER)
+                .ok()?;
+        unsafe {
+            let manager = shell
+                .QueryService(&CLSID_VIRTUAL_DESKTOP_MANAGER_INTERNAL)
+                .ok()?;
+            let views = shell.QueryService(&IApplicationViewCollection::IID).ok()?;
+            return Some(Self { manager, views });
+        }
+    }
+
+    fn find_desktop(&self, guid: GUID) -> std::result::Result<IVirtualDesktop, platform::Error> {
+        let mut desktop = None;
+        unsafe {
+            if self.manager.FindDesktop(&guid, &mut desktop).is_err() {
+                return Err(platform::Error::Failed);
+            }
+        }
+        return desktop.ok_or(platform::Error::Failed);
+    }
+
+    pub fn move_window_to_desktop(
+        &self,
+        hwnd: HWND,
+        guid: GUID,
+    ) -> std::result::Result<(), platform::Error> {
+        let desktop = self.find_desktop(guid)?;
+        let mut view = None;
+        unsafe {
+            if self.views.GetViewForHwnd(hwnd, &mut view).is_err() {
+                return Err(platform::Error::Failed);
+            }
+            let view = match view {
+                Some(val) => val,
+                None => return Err(platform::Error::Failed),
+            };
+            if self
+                .manager
+                .MoveViewToDesktop(view.as_raw(), desktop.as_raw())
+                .is_err()
+            {
+                return Err(platform::Error::Failed);
+            }
+        }
+        return Ok(());
+    }
+
+    pub fn switch_desktop(&self, guid: GUID) -> std::result::Result<(), platform::Error> {
+        let desktop = self.find_desktop(guid)?;
+        unsafe {
+            if self.manager.SwitchDesktop(desktop.as_raw()).is_err() {
+                return Err(platform::Error::Failed);
+            }
+        }
+        return Ok(());
+    }
+}
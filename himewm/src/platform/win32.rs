@@ -0,0 +1,415 @@
+use crate::{
+    platform::{self, cb, virtual_desktops},
+    windows_api,
+};
+use himewm_layout::position::Position;
+use windows::{
+    core::*,
+    Win32::{
+        Foundation::*,
+        Graphics::{Dwm::*, Gdi::*},
+        System::{Com::*, Registry::*, Threading::*},
+        UI::{Accessibility::*, HiDpi::*, Shell::*, WindowsAndMessaging::*},
+    },
+};
+
+const MAX_PATH_LEN: usize = 1024;
+
+const MIN_SIZE_TIMEOUT_MS: u32 = 100;
+
+/// Where Explorer keeps the order and names of virtual desktops, which are not
+/// available through `IVirtualDesktopManager`.
+const VIRTUAL_DESKTOPS_KEY: &str =
+    "Software\\Microsoft\\Windows\\CurrentVersion\\Explorer\\VirtualDesktops";
+
+fn get_registry_value(subkey: &str, value: &str, flags: REG_ROUTINE_FLAGS) -> Option<Vec<u8>> {
+    let subkey = std::ffi::CString::new(subkey).ok()?;
+    let value = std::ffi::CString::new(value).ok()?;
+    let subkey = PCSTR(subkey.as_ptr() as *const u8);
+    let value = PCSTR(value.as_ptr() as *const u8);
+    let mut size = 0;
+    if windows_api::reg_get_value(
+        HKEY_CURRENT_USER,
+        subkey,
+        value,
+        flags,
+        None,
+        Some(&mut size),
+    )
+    .is_err()
+    {
+        return None;
+    }
+    let mut buf = vec![0u8; size as usize];
+    if windows_api::reg_get_value(
+        HKEY_CURRENT_USER,
+        subkey,
+        value,
+        flags,
+        Some(buf.as_mut_ptr() as *mut core::ffi::c_void),
+        Some(&mut size),
+    )
+    .is_err()
+    {
+        return None;
+    }
+    buf.truncate(size as usize);
+    return Some(buf);
+}
+
+pub fn is_restored(hwnd: HWND) -> bool {
+    return has_sizebox(hwnd)
+        && !windows_api::is_iconic(hwnd).as_bool()
+        && !windows_api::is_zoomed(hwnd).as_bool()
+        && !windows_api::is_window_arranged(hwnd).as_bool()
+        && windows_api::is_window_visible(hwnd).as_bool();
+}
+
+pub fn has_sizebox(hwnd: HWND) -> bool {
+    windows_api::get_window_long_ptr(hwnd, GWL_STYLE) & WS_SIZEBOX.0 as isize != 0
+}
+
+pub fn is_overlapped_window(hwnd: HWND) -> bool {
+    windows_api::get_window_long_ptr(hwnd, GWL_STYLE) & WS_OVERLAPPEDWINDOW.0 as isize != 0
+}
+
+const DISPLAY_CHANGE_WINDOW_CLASS: PCSTR = s!("himewm_display_change");
+
+/// Creates a hidden top level window, since only those are sent
+/// `WM_DISPLAYCHANGE` and `WM_SETTINGCHANGE`.
+fn create_display_change_window() -> Option<HWND> {
+    let hinstance = windows_api::get_module_handle(None).ok()?;
+    let window_class = WNDCLASSA {
+        lpfnWndProc: Some(cb::display_change_window_proc),
+        hInstance: hinstance.into(),
+        lpszClassName: DISPLAY_CHANGE_WINDOW_CLASS,
+        ..Default::default()
+    };
+    windows_api::register_class(&window_class);
+    return windows_api::create_window_ex(
+        WS_EX_TOOLWINDOW | WS_EX_NOACTIVATE,
+        DISPLAY_CHANGE_WINDOW_CLASS,
+        PCSTR::null(),
+        WS_OVERLAPPED,
+        0,
+        0,
+        0,
+        0,
+        Some(hinstance.into()),
+    )
+    .ok();
+}
+
+pub struct Win32Platform {
+    event_hook: HWINEVENTHOOK,
+    virtual_desktop_manager: IVirtualDesktopManager,
+    virtual_desktop_manager_internal: Option<virtual_desktops::VirtualDesktopManagerInternal>,
+    display_change_window: Option<HWND>,
+}
+
+impl Win32Platform {
+    pub fn new() -> Self {
+        let event_hook = windows_api::set_win_event_hook(
+            EVENT_MIN,
+            EVENT_MAX,
+            None,
+            Some(cb::event_handler),
+            0,
+            0,
+            WINEVENT_OUTOFCONTEXT,
+        );
+        let _ = windows_api::co_initialize_ex(None, COINIT_MULTITHREADED);
+        let virtual_desktop_manager =
+            windows_api::co_create_instance(&VirtualDesktopManager, None, CLSCTX_INPROC_SERVER)
+                .unwrap();
+        Self {
+            event_hook,
+            virtual_desktop_manager,
+            virtual_desktop_manager_internal: virtual_desktops::VirtualDesktopManagerInternal::new(
+            ),
+            display_change_window: create_display_change_window(),
+        }
+    }
+}
+
+impl platform::Platform for Win32Platform {
+    fn enum_display_monitors(&self) -> Vec<HMONITOR> {
+        let mut hmonitors: Vec<HMONITOR> = Vec::new();
+        let _ = windows_api::enum_display_monitors(
+            None,
+            None,
+            Some(cb::enum_display_monitors_callback),
+            LPARAM(&mut hmonitors as *mut Vec<HMONITOR> as isize),
+        );
+        return hmonitors;
+    }
+
+    fn enum_windows(&self) -> Vec<HWND> {
+        let mut hwnds: Vec<HWND> = Vec::new();
+        let _ = windows_api::enum_windows(
+            Some(cb::enum_windows_callback),
+            LPARAM(&mut hwnds as *mut Vec<HWND> as isize),
+        );
+        return hwnds;
+    }
+
+    fn get_monitor_work_area(&self, hmonitor: HMONITOR) -> Position {
+        let mut monitor_info = MONITORINFO {
+            cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+            ..Default::default()
+        };
+        let _ = windows_api::get_monitor_info(hmonitor, &mut monitor_info);
+        return Position::from(monitor_info.rcWork);
+    }
+
+    fn get_monitor_rect(&self, hmonitor: HMONITOR) -> Position {
+        let mut monitor_info = MONITORINFO {
+            cbSize: std::mem::size_of::<MONITORINFO>() as u32,
+            ..Default::default()
+        };
+        let _ = windows_api::get_monitor_info(hmonitor, &mut monitor_info);
+        return Position::from(monitor_info.rcMonitor);
+    }
+
+    fn get_monitor_device_name(&self, hmonitor: HMONITOR) -> String {
+        let mut monitor_info = MONITORINFOEXA {
+            monitorInfo: MONITORINFO {
+                cbSize: std::mem::size_of::<MONITORINFOEXA>() as u32,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let _ = windows_api::get_monitor_info(
+            hmonitor,
+            &mut monitor_info as *mut MONITORINFOEXA as *mut MONITORINFO,
+        );
+        let bytes = monitor_info
+            .szDevice
+            .iter()
+            .take_while(|c| **c != 0)
+            .map(|c| *c as u8)
+            .collect::<Vec<u8>>();
+        return String::from_utf8_lossy(&bytes).into_owned();
+    }
+
+    fn get_dpi_for_monitor(&self, hmonitor: HMONITOR) -> u32 {
+        let mut dpi_x = 0;
+        let mut dpi_y = 0;
+        let _ =
+            windows_api::get_dpi_for_monitor(hmonitor, MDT_EFFECTIVE_DPI, &mut dpi_x, &mut dpi_y);
+        return dpi_x;
+    }
+
+    fn get_dpi_for_window(&self, hwnd: HWND) -> u32 {
+        windows_api::get_dpi_for_window(hwnd)
+    }
+
+    fn get_foreground_window(&self) -> HWND {
+        windows_api::get_foreground_window()
+    }
+
+    fn set_foreground_window(&self, hwnd: HWND) -> bool {
+        windows_api::set_foreground_window(hwnd).as_bool()
+    }
+
+    fn get_window_desktop_id(&self, hwnd: HWND) -> Option<GUID> {
+        windows_api::get_window_desktop_id(&self.virtual_desktop_manager, hwnd).ok()
+    }
+
+    fn get_desktop_ids(&self) -> Vec<GUID> {
+        let data = match get_registry_value(
+            VIRTUAL_DESKTOPS_KEY,
+            "VirtualDesktopIDs",
+            RRF_RT_REG_BINARY,
+        ) {
+            Some(val) => val,
+            None => return Vec::new(),
+        };
+        return data
+            .chunks_exact(std::mem::size_of::<GUID>())
+            .map(|bytes| {
+                GUID::from_values(
+                    u32::from_le_bytes(bytes[0..4].try_into().unwrap()),
+                    u16::from_le_bytes(bytes[4..6].try_into().unwrap()),
+                    u16::from_le_bytes(bytes[6..8].try_into().unwrap()),
+                    bytes[8..16].try_into().unwrap(),
+                )
+            })
+            .collect();
+    }
+
+    fn move_window_to_desktop(
+        &self,
+        hwnd: HWND,
+        guid: GUID,
+    ) -> std::result::Result<(), platform::Error> {
+        match &self.virtual_desktop_manager_internal {
+            Some(val) => return val.move_window_to_desktop(hwnd, guid),
+            None => return Err(platform::Error::Failed),
+        }
+    }
+
+    fn switch_desktop(&self, guid: GUID) -> std::result::Result<(), platform::Error> {
+        match &self.virtual_desktop_manager_internal {
+            Some(val) => return val.switch_desktop(guid),
+            None => return Err(platform::Error::Failed),
+        }
+    }
+
+    fn get_desktop_name(&self, guid: GUID) -> Option<String> {
+        let subkey = format!("{}\\Desktops\\{{{:?}}}", VIRTUAL_DESKTOPS_KEY, guid);
+        let data = get_registry_value(&subkey, "Name", RRF_RT_REG_SZ)?;
+        let name = data.split(|byte| *byte == 0).next()?;
+        if name.is_empty() {
+            return None;
+        }
+        return String::from_utf8(name.to_vec()).ok();
+    }
+
+    fn monitor_from_window(&self, hwnd: HWND) -> HMONITOR {
+        windows_api::monitor_from_window(hwnd, MONITOR_DEFAULTTONULL)
+    }
+
+    fn get_window_rect(&self, hwnd: HWND) -> Option<Position> {
+        let mut rect = RECT::default();
+        match windows_api::get_window_rect(hwnd, &mut rect) {
+            Ok(_) => return Some(Position::from(rect)),
+            Err(_) => return None,
+        }
+    }
+
+    fn set_window_pos(
+        &self,
+        hwnd: HWND,
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+    ) -> std::result::Result<(), platform::Error> {
+        match windows_api::set_window_pos(hwnd, None, x, y, w, h, SWP_NOZORDER) {
+            Ok(_) => return Ok(()),
+            Err(_) if windows_api::get_last_error() == ERROR_ACCESS_DENIED => {
+                return Err(platform::Error::AccessDenied);
+            }
+            Err(_) => return Err(platform::Error::Failed),
+        }
+    }
+
+    fn show_window(&self, hwnd: HWND, command: platform::ShowWindowCommand) -> bool {
+        let ncmdshow = match command {
+            platform::ShowWindowCommand::Hide => SW_HIDE,
+            platform::ShowWindowCommand::Show => SW_SHOW,
+            platform::ShowWindowCommand::Minimize => SW_MINIMIZE,
+            platform::ShowWindowCommand::Restore => SW_RESTORE,
+        };
+        windows_api::show_window(hwnd, ncmdshow).as_bool()
+    }
+
+    fn is_window(&self, hwnd: HWND) -> bool {
+        windows_api::is_window(Some(hwnd)).as_bool()
+    }
+
+    fn is_window_visible(&self, hwnd: HWND) -> bool {
+        windows_api::is_window_visible(hwnd).as_bool()
+    }
+
+    fn is_restored(&self, hwnd: HWND) -> bool {
+        is_restored(hwnd)
+    }
+
+    fn has_sizebox(&self, hwnd: HWND) -> bool {
+        has_sizebox(hwnd)
+    }
+
+    fn is_overlapped_window(&self, hwnd: HWND) -> bool {
+        is_overlapped_window(hwnd)
+    }
+
+    fn get_window_title(&self, hwnd: HWND) -> Option<String> {
+        let len = windows_api::get_window_text_length(hwnd) as usize;
+        let mut buf = vec![0u8; len + 1];
+        windows_api::get_window_text(hwnd, &mut buf);
+        return String::from_utf8(buf).ok();
+    }
+
+    fn get_exe_name(&self, hwnd: HWND) -> Option<String> {
+        let mut id = 0;
+        windows_api::get_window_thread_process_id(hwnd, Some(&mut id));
+        let handle = match windows_api::open_process(PROCESS_QUERY_LIMITED_INFORMATION, false, id) {
+            Ok(h) => h,
+            Err(_) => {
+                return None;
+            }
+        };
+        let mut buf = [0u8; MAX_PATH_LEN];
+        let mut size = MAX_PATH_LEN as u32;
+        let _query = windows_api::query_full_process_image_name(
+            handle,
+            PROCESS_NAME_FORMAT(0),
+            PSTR(&mut buf as *mut u8),
+            &mut size,
+        );
+        let _close_handle = windows_api::close_handle(handle);
+        let path_string = String::from_utf8(Vec::from(&buf[0..size as usize])).ok()?;
+        let path = std::path::Path::new(&path_string);
+        return Some(String::from(path.file_name().unwrap().to_str().unwrap()));
+    }
+
+    fn get_min_size(&self, hwnd: HWND) -> Option<(i32, i32)> {
+        let mut min_max_info = MINMAXINFO::default();
+        let result = windows_api::send_message_timeout(
+            hwnd,
+            WM_GETMINMAXINFO,
+            WPARAM(0),
+            LPARAM(&mut min_max_info as *mut MINMAXINFO as isize),
+            SMTO_ABORTIFHUNG,
+            MIN_SIZE_TIMEOUT_MS,
+            None,
+        );
+        if result.0 == 0 {
+            return None;
+        }
+        return Some((min_max_info.ptMinTrackSize.x, min_max_info.ptMinTrackSize.y));
+    }
+
+    fn set_border_colour(&self, hwnd: HWND, colour: COLORREF) {
+        let _ = windows_api::dwm_set_window_attribute(
+            hwnd,
+            DWMWA_BORDER_COLOR,
+            &colour as *const COLORREF as *const core::ffi::c_void,
+            std::mem::size_of_val(&colour) as u32,
+        );
+    }
+
+    fn set_corner_preference(&self, hwnd: HWND, preference: platform::CornerPreference) {
+        let corner_preference = match preference {
+            platform::CornerPreference::Default => DWMWCP_DEFAULT,
+            platform::CornerPreference::DoNotRound => DWMWCP_DONOTROUND,
+        };
+        let _ = windows_api::dwm_set_window_attribute(
+            hwnd,
+            DWMWA_WINDOW_CORNER_PREFERENCE,
+            &corner_preference as *const DWM_WINDOW_CORNER_PREFERENCE as *const core::ffi::c_void,
+            std::mem::size_of_val(&corner_preference) as u32,
+        );
+    }
+
+    fn spawn(&self, command: &str, args: &[String]) -> std::result::Result<(), platform::Error> {
+        match std::process::Command::new(command).args(args).spawn() {
+            Ok(_) => return Ok(()),
+            Err(_) => return Err(platform::Error::Failed),
+        }
+    }
+
+    fn post_message(&self, msg: u32, wparam: usize) {
+        windows_api::post_message(None, msg, WPARAM(wparam), LPARAM::default()).unwrap();
+    }
+
+    fn uninitialize(&self) {
+        let _unhook_win_event = windows_api::unhook_win_event(self.event_hook);
+        if let Some(hwnd) = self.display_change_window {
+            let _destroy_window = windows_api::destroy_window(hwnd);
+        }
+        windows_api::co_uninitialize();
+    }
+}
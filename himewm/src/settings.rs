@@ -1,14 +1,42 @@
-use serde::{Deserialize, Serialize};
-use windows::Win32::{
-    Foundation::COLORREF,
-    Graphics::Dwm::{DWMWA_COLOR_DEFAULT, DWMWA_COLOR_NONE},
+use crate::{
+    platform::{COLORREF, DWMWA_COLOR_DEFAULT, DWMWA_COLOR_NONE, GUID},
+    window_rules,
 };
+use himewm_layout::padding::{Padding, PaddingOverrides};
+use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize)]
 struct LayoutSettings {
     default_layout: std::path::PathBuf,
     window_padding: i32,
     edge_padding: i32,
+    #[serde(default = "default_resize_step")]
+    resize_step: i32,
+    #[serde(default = "default_weight_step")]
+    weight_step: f64,
+    /// Changes to `window_padding` and `edge_padding` for individual sides.
+    #[serde(default)]
+    padding: PaddingOverrides,
+    #[serde(default = "default_gap_step")]
+    gap_step: i32,
+    #[serde(default = "default_master_ratio_step")]
+    master_ratio_step: f64,
+}
+
+fn default_resize_step() -> i32 {
+    20
+}
+
+fn default_weight_step() -> f64 {
+    0.25
+}
+
+fn default_gap_step() -> i32 {
+    2
+}
+
+fn default_master_ratio_step() -> f64 {
+    0.05
 }
 
 impl Default for LayoutSettings {
@@ -17,11 +45,16 @@ impl Default for LayoutSettings {
             default_layout: std::path::PathBuf::new(),
             window_padding: 0,
             edge_padding: 0,
+            resize_step: default_resize_step(),
+            weight_step: default_weight_step(),
+            padding: PaddingOverrides::default(),
+            gap_step: default_gap_step(),
+            master_ratio_step: default_master_ratio_step(),
         }
     }
 }
 
-#[derive(Deserialize, Serialize)]
+#[derive(Default, Deserialize, Serialize)]
 struct BorderSettings {
     disable_rounding: bool,
     disable_unfocused_border: bool,
@@ -29,17 +62,6 @@ struct BorderSettings {
     unfocused_border_colour: String,
 }
 
-impl Default for BorderSettings {
-    fn default() -> Self {
-        Self {
-            disable_rounding: false,
-            disable_unfocused_border: false,
-            focused_border_colour: String::from(""),
-            unfocused_border_colour: String::from(""),
-        }
-    }
-}
-
 #[derive(Deserialize, Serialize)]
 struct MiscSettings {
     floating_window_default_w_ratio: f64,
@@ -58,33 +80,85 @@ impl Default for MiscSettings {
 #[derive(Deserialize, Serialize)]
 struct AdvancedSettings {
     new_window_retries: i32,
+    #[serde(default)]
+    record_events: bool,
 }
 
 impl Default for AdvancedSettings {
     fn default() -> Self {
         Self {
             new_window_retries: 10000,
+            record_events: false,
         }
     }
 }
 
+/// Settings for the monitors matching a key in the `monitors` section, where
+/// anything that is not given is taken from the other sections.
+#[derive(Deserialize, Serialize)]
+struct UserMonitorSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default_layout: Option<std::path::PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default_variant_idx: Option<Vec<usize>>,
+    #[serde(default, skip_serializing_if = "PaddingOverrides::is_empty")]
+    padding: PaddingOverrides,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    disable_rounding: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    disable_unfocused_border: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    focused_border_colour: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unfocused_border_colour: Option<String>,
+}
+
+/// Settings for the virtual desktops matching a key in the `desktops` section.
+#[derive(Deserialize, Serialize)]
+struct UserDesktopSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default_layout: Option<std::path::PathBuf>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default_variant_idx: Option<Vec<usize>>,
+    /// Whether windows on the desktop start out untiled on every monitor.
+    #[serde(default)]
+    ignored: bool,
+}
+
+/// A window that is shown and hidden with the `toggle_scratchpad` command,
+/// matched the same way as a window rule.
 #[derive(Deserialize, Serialize)]
+struct UserScratchpad {
+    match_type: window_rules::MatchType,
+    regex: String,
+    /// Run when the scratchpad is toggled and no window matches.
+    command: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    args: Vec<String>,
+    /// Relative to the work area of the monitor the window is shown on. The
+    /// window is centered if this is not given.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    position: Option<window_rules::Position>,
+}
+
+#[derive(Default, Deserialize, Serialize)]
 pub struct UserSettings {
     layout_settings: LayoutSettings,
     border_settings: BorderSettings,
     misc_settings: MiscSettings,
     advanced_settings: AdvancedSettings,
-}
-
-impl Default for UserSettings {
-    fn default() -> Self {
-        Self {
-            layout_settings: LayoutSettings::default(),
-            border_settings: BorderSettings::default(),
-            misc_settings: MiscSettings::default(),
-            advanced_settings: AdvancedSettings::default(),
-        }
-    }
+    /// Keyed by the device name of a monitor (like `\\.\DISPLAY1`), its
+    /// index in the order monitors are enumerated, or its resolution (like
+    /// `2560x1440`).
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    monitors: std::collections::BTreeMap<String, UserMonitorSettings>,
+    /// Keyed by the name of a virtual desktop, its index in Task View or its
+    /// id (like `{00000000-0000-0000-0000-000000000000}`).
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    desktops: std::collections::BTreeMap<String, UserDesktopSettings>,
+    /// Keyed by the name of the scratchpad.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    scratchpads: std::collections::BTreeMap<String, UserScratchpad>,
 }
 
 impl UserSettings {
@@ -100,10 +174,76 @@ impl UserSettings {
                 idx = *i;
             }
         }
+        let mut monitors = self
+            .monitors
+            .iter()
+            .map(|(key, monitor)| {
+                let overrides = MonitorOverrides {
+                    default_layout_idx: match &monitor.default_layout {
+                        Some(path) => layout_idx_map.get(path.to_str().unwrap()).copied(),
+                        None => None,
+                    },
+                    default_variant_idx: monitor.default_variant_idx.to_owned(),
+                    padding: monitor.padding.to_owned(),
+                    disable_rounding: monitor.disable_rounding,
+                    disable_unfocused_border: monitor.disable_unfocused_border,
+                    focused_border_colour: monitor
+                        .focused_border_colour
+                        .as_ref()
+                        .map(|s| parse_border_colour(s.as_str())),
+                    unfocused_border_colour: monitor
+                        .unfocused_border_colour
+                        .as_ref()
+                        .map(|s| parse_border_colour(s.as_str())),
+                };
+                (MonitorKey::parse(key), overrides)
+            })
+            .collect::<Vec<(MonitorKey, MonitorOverrides)>>();
+        monitors.sort_by_key(|(key, _)| key.precedence());
+        let mut desktops = self
+            .desktops
+            .iter()
+            .map(|(key, desktop)| {
+                let settings = DesktopSettings {
+                    default_layout_idx: match &desktop.default_layout {
+                        Some(path) => layout_idx_map.get(path.to_str().unwrap()).copied(),
+                        None => None,
+                    },
+                    default_variant_idx: desktop.default_variant_idx.to_owned(),
+                    ignored: desktop.ignored,
+                };
+                (DesktopKey::parse(key), settings)
+            })
+            .collect::<Vec<(DesktopKey, DesktopSettings)>>();
+        desktops.sort_by_key(|(key, _)| key.precedence());
+        let scratchpads = self
+            .scratchpads
+            .iter()
+            .filter_map(|(name, scratchpad)| {
+                Some(Scratchpad {
+                    name: name.to_owned(),
+                    match_type: scratchpad.match_type.to_owned(),
+                    regex: regex::Regex::new(&scratchpad.regex).ok()?,
+                    command: scratchpad.command.to_owned(),
+                    args: scratchpad.args.to_owned(),
+                    position: scratchpad.position.to_owned(),
+                })
+            })
+            .collect();
         return Settings {
             default_layout_idx: idx,
-            window_padding: self.layout_settings.window_padding,
-            edge_padding: self.layout_settings.edge_padding,
+            padding: self.layout_settings.padding.apply(&Padding::uniform(
+                self.layout_settings.window_padding,
+                self.layout_settings.edge_padding,
+            )),
+            monitors,
+            desktops,
+            scratchpads,
+            layout_idx_map: layout_idx_map.to_owned(),
+            resize_step: self.layout_settings.resize_step,
+            weight_step: self.layout_settings.weight_step,
+            gap_step: self.layout_settings.gap_step,
+            master_ratio_step: self.layout_settings.master_ratio_step,
             disable_rounding: self.border_settings.disable_rounding,
             disable_unfocused_border: self.border_settings.disable_unfocused_border,
             focused_border_colour: parse_border_colour(
@@ -115,24 +255,124 @@ impl UserSettings {
             floating_window_default_w_ratio: self.misc_settings.floating_window_default_w_ratio,
             floating_window_default_h_ratio: self.misc_settings.floating_window_default_h_ratio,
             new_window_retries: self.advanced_settings.new_window_retries,
+            record_events: self.advanced_settings.record_events,
         };
     }
 }
 
-pub struct Settings {
+/// How a monitor is matched by a key in the `monitors` section.
+#[derive(Clone, Debug, PartialEq)]
+pub enum MonitorKey {
+    DeviceName(String),
+    Index(usize),
+    Resolution(i32, i32),
+}
+
+impl MonitorKey {
+    pub fn parse(s: &str) -> Self {
+        if let Ok(idx) = s.parse::<usize>() {
+            return MonitorKey::Index(idx);
+        }
+        if let Some((w, h)) = s.split_once('x') {
+            if let (Ok(w), Ok(h)) = (w.parse::<i32>(), h.parse::<i32>()) {
+                return MonitorKey::Resolution(w, h);
+            }
+        }
+        return MonitorKey::DeviceName(s.to_owned());
+    }
+
+    pub fn matches(&self, device_name: &str, idx: usize, resolution: (i32, i32)) -> bool {
+        match self {
+            MonitorKey::DeviceName(name) => return name == device_name,
+            MonitorKey::Index(i) => return *i == idx,
+            MonitorKey::Resolution(w, h) => return (*w, *h) == resolution,
+        }
+    }
+
+    /// Keys with a higher precedence override the ones with a lower one when
+    /// several match the same monitor.
+    fn precedence(&self) -> usize {
+        match self {
+            MonitorKey::Resolution(_, _) => return 0,
+            MonitorKey::Index(_) => return 1,
+            MonitorKey::DeviceName(_) => return 2,
+        }
+    }
+}
+
+/// How a virtual desktop is matched by a key in the `desktops` section.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DesktopKey {
+    Name(String),
+    Index(usize),
+    Id(GUID),
+}
+
+impl DesktopKey {
+    pub fn parse(s: &str) -> Self {
+        if let Ok(idx) = s.parse::<usize>() {
+            return DesktopKey::Index(idx);
+        }
+        if let Some(id) = s.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            if let Ok(guid) = GUID::try_from(id) {
+                return DesktopKey::Id(guid);
+            }
+        }
+        return DesktopKey::Name(s.to_owned());
+    }
+
+    pub fn matches(&self, guid: GUID, name: Option<&str>, idx: Option<usize>) -> bool {
+        match self {
+            DesktopKey::Name(s) => return name == Some(s.as_str()),
+            DesktopKey::Index(i) => return idx == Some(*i),
+            DesktopKey::Id(id) => return *id == guid,
+        }
+    }
+
+    fn precedence(&self) -> usize {
+        match self {
+            DesktopKey::Index(_) => return 0,
+            DesktopKey::Name(_) => return 1,
+            DesktopKey::Id(_) => return 2,
+        }
+    }
+}
+
+/// The settings that can differ between virtual desktops, where the layout
+/// and variant replace the ones of the monitor when they are given.
+#[derive(Clone, Default)]
+pub struct DesktopSettings {
+    pub default_layout_idx: Option<usize>,
+    pub default_variant_idx: Option<Vec<usize>>,
+    pub ignored: bool,
+}
+
+#[derive(Clone)]
+pub struct MonitorOverrides {
+    pub default_layout_idx: Option<usize>,
+    pub default_variant_idx: Option<Vec<usize>>,
+    pub padding: PaddingOverrides,
+    pub disable_rounding: Option<bool>,
+    pub disable_unfocused_border: Option<bool>,
+    pub focused_border_colour: Option<COLORREF>,
+    pub unfocused_border_colour: Option<COLORREF>,
+}
+
+/// The settings that can differ between monitors.
+#[derive(Clone)]
+pub struct MonitorSettings {
     pub default_layout_idx: usize,
-    pub window_padding: i32,
-    pub edge_padding: i32,
+    /// Replaces the default variant of the default layout.
+    pub default_variant_idx: Option<Vec<usize>>,
+    /// Applied on top of the padding of every layout.
+    pub padding: PaddingOverrides,
     pub disable_rounding: bool,
     pub disable_unfocused_border: bool,
     pub focused_border_colour: COLORREF,
     pub unfocused_border_colour: COLORREF,
-    pub floating_window_default_w_ratio: f64,
-    pub floating_window_default_h_ratio: f64,
-    pub new_window_retries: i32,
 }
 
-impl Settings {
+impl MonitorSettings {
     pub fn get_unfocused_border_colour(&self) -> COLORREF {
         if self.disable_unfocused_border {
             return COLORREF(DWMWA_COLOR_NONE);
@@ -142,11 +382,113 @@ impl Settings {
     }
 }
 
+pub struct Scratchpad {
+    pub name: String,
+    pub match_type: window_rules::MatchType,
+    pub regex: regex::Regex,
+    pub command: String,
+    pub args: Vec<String>,
+    pub position: Option<window_rules::Position>,
+}
+
+pub struct Settings {
+    pub default_layout_idx: usize,
+    pub padding: Padding,
+    pub monitors: Vec<(MonitorKey, MonitorOverrides)>,
+    pub desktops: Vec<(DesktopKey, DesktopSettings)>,
+    /// In order of their names.
+    pub scratchpads: Vec<Scratchpad>,
+    /// The index of each layout, keyed by its name.
+    pub layout_idx_map: std::collections::HashMap<String, usize>,
+    pub resize_step: i32,
+    pub weight_step: f64,
+    pub gap_step: i32,
+    pub master_ratio_step: f64,
+    pub disable_rounding: bool,
+    pub disable_unfocused_border: bool,
+    pub focused_border_colour: COLORREF,
+    pub unfocused_border_colour: COLORREF,
+    pub floating_window_default_w_ratio: f64,
+    pub floating_window_default_h_ratio: f64,
+    pub new_window_retries: i32,
+    pub record_events: bool,
+}
+
+impl Settings {
+    /// The settings for monitors that are not in the `monitors` section.
+    pub fn default_monitor_settings(&self) -> MonitorSettings {
+        return MonitorSettings {
+            default_layout_idx: self.default_layout_idx,
+            default_variant_idx: None,
+            padding: PaddingOverrides::default(),
+            disable_rounding: self.disable_rounding,
+            disable_unfocused_border: self.disable_unfocused_border,
+            focused_border_colour: self.focused_border_colour,
+            unfocused_border_colour: self.unfocused_border_colour,
+        };
+    }
+
+    pub fn monitor_settings(
+        &self,
+        device_name: &str,
+        idx: usize,
+        resolution: (i32, i32),
+    ) -> MonitorSettings {
+        let mut ret = self.default_monitor_settings();
+        for (key, overrides) in &self.monitors {
+            if !key.matches(device_name, idx, resolution) {
+                continue;
+            }
+            if let Some(i) = overrides.default_layout_idx {
+                ret.default_layout_idx = i;
+            }
+            if overrides.default_variant_idx.is_some() {
+                ret.default_variant_idx = overrides.default_variant_idx.to_owned();
+            }
+            ret.padding = ret.padding.overridden_by(&overrides.padding);
+            ret.disable_rounding = overrides.disable_rounding.unwrap_or(ret.disable_rounding);
+            ret.disable_unfocused_border = overrides
+                .disable_unfocused_border
+                .unwrap_or(ret.disable_unfocused_border);
+            ret.focused_border_colour = overrides
+                .focused_border_colour
+                .unwrap_or(ret.focused_border_colour);
+            ret.unfocused_border_colour = overrides
+                .unfocused_border_colour
+                .unwrap_or(ret.unfocused_border_colour);
+        }
+        return ret;
+    }
+
+    pub fn desktop_settings(
+        &self,
+        guid: GUID,
+        name: Option<&str>,
+        idx: Option<usize>,
+    ) -> DesktopSettings {
+        let mut ret = DesktopSettings::default();
+        for (key, settings) in &self.desktops {
+            if !key.matches(guid, name, idx) {
+                continue;
+            }
+            if settings.default_layout_idx.is_some() {
+                ret.default_layout_idx = settings.default_layout_idx;
+            }
+            if settings.default_variant_idx.is_some() {
+                ret.default_variant_idx = settings.default_variant_idx.to_owned();
+            }
+            // A desktop is ignored if any of the keys matching it say so
+            ret.ignored |= settings.ignored;
+        }
+        return ret;
+    }
+}
+
 fn hex_to_decimal(c: u8) -> u8 {
-    const ZERO: u8 = '0' as u8;
-    const NINE: u8 = '9' as u8;
-    const A: u8 = 'a' as u8;
-    const F: u8 = 'f' as u8;
+    const ZERO: u8 = b'0';
+    const NINE: u8 = b'9';
+    const A: u8 = b'a';
+    const F: u8 = b'f';
     match c {
         ZERO..=NINE => {
             return c - ZERO;
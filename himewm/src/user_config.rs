@@ -1,4 +1,4 @@
-use crate::{directories, keybinds, layouts, settings, util, window_rules};
+use crate::{commands, directories, keybinds, layouts, settings, util, window_rules};
 use himewm_layout::layout::Layout;
 use serde::{Deserialize, Serialize};
 
@@ -71,11 +71,11 @@ pub fn get_user_config() -> UserConfig {
         }
     };
     let keybinds = match get_from_file("keybinds.json") {
-        Ok(user_keybinds) => keybinds::Keybinds::from(&user_keybinds),
+        Ok(user_keybinds) => keybinds::Keybinds::new(&user_keybinds, &mut warnings),
         Err(e) => {
             util::add_to_message(&mut warnings, &format!("Warning: An error occurred when parsing keybinds.json:\n{}\nProceeding with default settings", e));
-            let default_user_keybinds = keybinds::UserKeybinds::default();
-            keybinds::Keybinds::from(&default_user_keybinds)
+            let default_user_keybinds = commands::UserKeybinds::default();
+            keybinds::Keybinds::new(&default_user_keybinds, &mut warnings)
         }
     };
     let config = Config {
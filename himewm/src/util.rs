@@ -1,4 +1,6 @@
+#[cfg(windows)]
 use crate::windows_api;
+#[cfg(windows)]
 use windows::Win32::{Foundation::*, UI::WindowsAndMessaging::*};
 
 pub enum MessageType {
@@ -14,12 +16,14 @@ pub fn add_to_message(message_buffer: &mut String, message: &str) {
     message_buffer.push_str(message);
 }
 
+#[cfg(windows)]
 pub fn get_console_hwnd() -> HWND {
     let console_hwnd = windows_api::get_console_window();
     let _set_foreground_window = windows_api::set_foreground_window(console_hwnd);
     return windows_api::get_foreground_window();
 }
 
+#[cfg(windows)]
 pub fn display_message(console_hwnd: HWND, message_type: &MessageType, message: &str) {
     if let MessageType::None = message_type {
         return;
@@ -1,8 +1,8 @@
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize)]
-enum MatchType {
+#[derive(Clone, Deserialize, Serialize)]
+pub enum MatchType {
     Title,
     Process,
 }
@@ -41,12 +41,24 @@ impl Default for InvisibleBorder {
     }
 }
 
+/// The smallest size a window can be tiled at.
+#[derive(Clone, Deserialize, Serialize)]
+pub enum MinSize {
+    /// The minimum size the window reports for itself.
+    FromWindow,
+    Size {
+        w: i32,
+        h: i32,
+    },
+}
+
 #[derive(Deserialize, Serialize)]
 enum UserRule {
     Layout(String),
     StartFloating(SetPosition),
     FloatingPosition(Position),
     InvisibleBorder(InvisibleBorder),
+    MinSize(MinSize),
 }
 
 #[derive(Clone)]
@@ -55,6 +67,7 @@ pub enum Rule {
     StartFloating(SetPosition),
     FloatingPosition(Position),
     InvisibleBorder(InvisibleBorder),
+    MinSize(MinSize),
 }
 
 #[derive(PartialEq, Eq, Hash)]
@@ -63,6 +76,7 @@ pub enum FilterRule {
     StartFloating,
     FloatingPosition,
     InvisibleBorder,
+    MinSize,
 }
 
 impl From<&Rule> for FilterRule {
@@ -72,6 +86,7 @@ impl From<&Rule> for FilterRule {
             Rule::StartFloating(_) => return Self::StartFloating,
             Rule::FloatingPosition(_) => return Self::FloatingPosition,
             Rule::InvisibleBorder(_) => return Self::InvisibleBorder,
+            Rule::MinSize(_) => return Self::MinSize,
         }
     }
 }
@@ -88,20 +103,12 @@ pub struct WindowRule {
     pub rule: Rule,
 }
 
+#[derive(Default)]
 pub struct WindowRules {
     pub title_window_rules: Vec<WindowRule>,
     pub process_window_rules: Vec<WindowRule>,
 }
 
-impl Default for WindowRules {
-    fn default() -> Self {
-        Self {
-            title_window_rules: Vec::new(),
-            process_window_rules: Vec::new(),
-        }
-    }
-}
-
 pub fn get_window_rules(
     user_window_rules: &Vec<UserWindowRule>,
     layout_idx_map: &std::collections::HashMap<String, usize>,
@@ -118,6 +125,7 @@ pub fn get_window_rules(
             UserRule::InvisibleBorder(invisible_border) => {
                 Rule::InvisibleBorder(invisible_border.to_owned())
             }
+            UserRule::MinSize(min_size) => Rule::MinSize(min_size.to_owned()),
         };
         let window_rule = WindowRule {
             regex: Regex::new(&user_window_rule.regex).unwrap(),
@@ -3,7 +3,7 @@ use windows::{
     Win32::{
         Foundation::*,
         Graphics::{Dwm::*, Gdi::*},
-        System::{Com::*, Console::*, Threading::*},
+        System::{Com::*, Console::*, LibraryLoader::*, Registry::*, Threading::*},
         UI::{
             Accessibility::*, HiDpi::*, Input::KeyboardAndMouse::*, Shell::*,
             WindowsAndMessaging::*,
@@ -114,6 +114,17 @@ pub fn get_dpi_for_window(hwnd: HWND) -> u32 {
     }
 }
 
+pub fn get_dpi_for_monitor(
+    hmonitor: HMONITOR,
+    dpitype: MONITOR_DPI_TYPE,
+    dpix: *mut u32,
+    dpiy: *mut u32,
+) -> Result<()> {
+    unsafe {
+        return GetDpiForMonitor(hmonitor, dpitype, dpix, dpiy);
+    }
+}
+
 pub fn set_window_pos(
     hwnd: HWND,
     hwndinsertafter: Option<HWND>,
@@ -145,6 +156,20 @@ pub fn dwm_set_window_attribute(
     }
 }
 
+pub fn send_message_timeout(
+    hwnd: HWND,
+    msg: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+    fuflags: SEND_MESSAGE_TIMEOUT_FLAGS,
+    utimeout: u32,
+    lpdwresult: Option<*mut usize>,
+) -> LRESULT {
+    unsafe {
+        return SendMessageTimeoutW(hwnd, msg, wparam, lparam, fuflags, utimeout, lpdwresult);
+    }
+}
+
 pub fn post_message(hwnd: Option<HWND>, msg: u32, wparam: WPARAM, lparam: LPARAM) -> Result<()> {
     unsafe {
         return PostMessageA(hwnd, msg, wparam, lparam);
@@ -187,6 +212,73 @@ pub fn get_monitor_info(hmonitor: HMONITOR, lpmi: *mut MONITORINFO) -> BOOL {
     }
 }
 
+pub fn get_module_handle(lpmodulename: Option<PCSTR>) -> Result<HMODULE> {
+    unsafe {
+        return GetModuleHandleA(lpmodulename.unwrap_or(PCSTR::null()));
+    }
+}
+
+pub fn register_class(lpwndclass: *const WNDCLASSA) -> u16 {
+    unsafe {
+        return RegisterClassA(lpwndclass);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_window_ex(
+    dwexstyle: WINDOW_EX_STYLE,
+    lpclassname: PCSTR,
+    lpwindowname: PCSTR,
+    dwstyle: WINDOW_STYLE,
+    x: i32,
+    y: i32,
+    nwidth: i32,
+    nheight: i32,
+    hinstance: Option<HINSTANCE>,
+) -> Result<HWND> {
+    unsafe {
+        return CreateWindowExA(
+            dwexstyle,
+            lpclassname,
+            lpwindowname,
+            dwstyle,
+            x,
+            y,
+            nwidth,
+            nheight,
+            None,
+            None,
+            hinstance,
+            None,
+        );
+    }
+}
+
+pub fn def_window_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    unsafe {
+        return DefWindowProcA(hwnd, msg, wparam, lparam);
+    }
+}
+
+pub fn destroy_window(hwnd: HWND) -> Result<()> {
+    unsafe {
+        return DestroyWindow(hwnd);
+    }
+}
+
+pub fn reg_get_value(
+    hkey: HKEY,
+    lpsubkey: PCSTR,
+    lpvalue: PCSTR,
+    dwflags: REG_ROUTINE_FLAGS,
+    pvdata: Option<*mut core::ffi::c_void>,
+    pcbdata: Option<*mut u32>,
+) -> WIN32_ERROR {
+    unsafe {
+        return RegGetValueA(hkey, lpsubkey, lpvalue, dwflags, None, pvdata, pcbdata);
+    }
+}
+
 pub fn register_hot_key(
     hwnd: Option<HWND>,
     id: i32,
@@ -1,23 +1,26 @@
-use crate::{settings, window_rules, windows_api};
-use himewm_layout::layout::*;
-use windows::{
-    core::*,
-    Win32::{
-        Foundation::*,
-        Graphics::{Dwm::*, Gdi::*},
-        System::Com::*,
-        UI::{Accessibility::*, Shell::*, WindowsAndMessaging::*},
-    },
+use crate::{
+    commands,
+    platform::{self, COLORREF, DWMWA_COLOR_DEFAULT, GUID, HMONITOR, HWND},
+    settings, window_rules,
 };
+use himewm_layout::{
+    layout::*,
+    neighbours,
+    padding::Padding,
+    position::Position,
+    variant::{Direction, Variant},
+    weights,
+};
+use serde::{Deserialize, Serialize};
 
-mod cb;
 #[macro_use]
 mod macros;
-mod util;
 
 pub mod message_handler;
 pub mod messages;
+pub mod replay;
 
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 pub enum CycleDirection {
     Previous,
     Next,
@@ -29,6 +32,20 @@ struct Workspace {
     variant_idx: Vec<usize>,
     window_handles: std::collections::HashSet<*mut core::ffi::c_void>,
     managed_window_handles: Vec<HWND>,
+    resized_layouts: std::collections::HashMap<usize, Layout>,
+    window_weights: std::collections::HashMap<*mut core::ffi::c_void, f64>,
+    overflowed_windows: std::collections::HashSet<*mut core::ffi::c_void>,
+    monocle: bool,
+    /// The window that was last brought to the top of the monocle stack.
+    monocle_top: Option<HWND>,
+    /// Every member of each tile group, keyed by the member that is shown in
+    /// the group's tile. The other members are minimized.
+    tile_groups: std::collections::HashMap<*mut core::ffi::c_void, Vec<HWND>>,
+    /// Added to the inner padding of the workspace's layout.
+    gap_offset: i32,
+    gaps_disabled: bool,
+    /// Where each managed window was last tiled.
+    positions: Vec<Option<Position>>,
 }
 
 impl Workspace {
@@ -38,6 +55,15 @@ impl Workspace {
             variant_idx,
             window_handles: std::collections::HashSet::from([hwnd.0]),
             managed_window_handles: Vec::new(),
+            resized_layouts: std::collections::HashMap::new(),
+            window_weights: std::collections::HashMap::new(),
+            overflowed_windows: std::collections::HashSet::new(),
+            monocle: false,
+            monocle_top: None,
+            tile_groups: std::collections::HashMap::new(),
+            gap_offset: 0,
+            gaps_disabled: false,
+            positions: Vec::new(),
         }
     }
 
@@ -47,6 +73,93 @@ impl Workspace {
             variant_idx,
             window_handles: std::collections::HashSet::from([hwnd.0]),
             managed_window_handles: vec![hwnd],
+            resized_layouts: std::collections::HashMap::new(),
+            window_weights: std::collections::HashMap::new(),
+            overflowed_windows: std::collections::HashSet::new(),
+            monocle: false,
+            monocle_top: None,
+            tile_groups: std::collections::HashMap::new(),
+            gap_offset: 0,
+            gaps_disabled: false,
+            positions: Vec::new(),
+        }
+    }
+
+    fn get_internal_positions(
+        &mut self,
+        layouts: &mut [Layout],
+        n: usize,
+        settings: &settings::Settings,
+    ) -> Vec<Position> {
+        let weights = self.get_weights(n);
+        let padding = self.get_padding(layouts, settings);
+        let layout = match self.resized_layouts.get_mut(&self.layout_idx) {
+            Some(l) => l,
+            None => &mut layouts[self.layout_idx],
+        };
+        return layout.get_weighted_internal_positions(&self.variant_idx, n, &weights, &padding);
+    }
+
+    fn get_fitted_internal_positions(
+        &mut self,
+        layouts: &mut [Layout],
+        min_sizes: &[(i32, i32)],
+        settings: &settings::Settings,
+    ) -> Vec<Option<Position>> {
+        let n = self.managed_window_handles.len();
+        let weights = self.get_weights(n);
+        let padding = self.get_padding(layouts, settings);
+        let layout = match self.resized_layouts.get_mut(&self.layout_idx) {
+            Some(l) => l,
+            None => &mut layouts[self.layout_idx],
+        };
+        return layout.get_fitted_internal_positions(
+            &self.variant_idx,
+            n,
+            &weights,
+            min_sizes,
+            &padding,
+        );
+    }
+
+    /// The padding from the settings with the changes made by the layout and
+    /// the workspace's gaps.
+    fn get_padding(&self, layouts: &[Layout], settings: &settings::Settings) -> Padding {
+        let layout = match self.resized_layouts.get(&self.layout_idx) {
+            Some(l) => l,
+            None => &layouts[self.layout_idx],
+        };
+        let mut padding = layout.padding().apply(&settings.padding);
+        if self.gaps_disabled {
+            padding.horizontal = 0;
+            padding.vertical = 0;
+        } else {
+            padding.horizontal = std::cmp::max(padding.horizontal + self.gap_offset, 0);
+            padding.vertical = std::cmp::max(padding.vertical + self.gap_offset, 0);
+        }
+        return padding;
+    }
+
+    fn get_weights(&self, n: usize) -> Vec<f64> {
+        return self
+            .managed_window_handles
+            .iter()
+            .take(n)
+            .map(|hwnd| match self.window_weights.get(&hwnd.0) {
+                Some(weight) => *weight,
+                None => weights::DEFAULT_WEIGHT,
+            })
+            .collect();
+    }
+
+    /// Moves the resized layouts onto `monitor_rect`, scaling their resizes
+    /// with it.
+    fn move_resized_layouts(&mut self, monitor_rect: &Position, settings: &settings::Settings) {
+        for layout in self.resized_layouts.values_mut() {
+            if layout.monitor_rect() != monitor_rect {
+                *layout = layout.moved_to(monitor_rect.to_owned());
+                layout.update_all(&layout.padding().apply(&settings.padding));
+            }
         }
     }
 }
@@ -70,23 +183,16 @@ impl WindowInfo {
     }
 }
 
+#[derive(Default)]
 struct DesktopSwitchingState {
     uncloak_count: usize,
     max_uncloak_count: usize,
 }
 
-impl Default for DesktopSwitchingState {
-    fn default() -> Self {
-        Self {
-            uncloak_count: 0,
-            max_uncloak_count: 0,
-        }
-    }
-}
-
 pub struct WindowManager {
-    event_hook: HWINEVENTHOOK,
-    virtual_desktop_manager: IVirtualDesktopManager,
+    platform: Box<dyn platform::Platform>,
+    /// The layouts before they are resolved for each monitor.
+    user_layouts: Vec<Layout>,
     monitor_handles: Vec<HMONITOR>,
     window_info: std::collections::HashMap<*mut core::ffi::c_void, WindowInfo>,
     workspaces: std::collections::HashMap<(GUID, *mut core::ffi::c_void), Workspace>,
@@ -99,6 +205,16 @@ pub struct WindowManager {
     desktop_switching_state: DesktopSwitchingState,
     settings: settings::Settings,
     window_rules: window_rules::WindowRules,
+    min_sizes: std::collections::HashMap<*mut core::ffi::c_void, (i32, i32)>,
+    monitor_settings: std::collections::HashMap<*mut core::ffi::c_void, settings::MonitorSettings>,
+    desktop_settings: std::collections::HashMap<GUID, settings::DesktopSettings>,
+    /// The window of each scratchpad, keyed by its index in the settings.
+    scratchpad_windows: std::collections::HashMap<usize, HWND>,
+    /// The scratchpads whose commands have been run, but whose windows have
+    /// not been created yet.
+    pending_scratchpads: std::collections::HashSet<usize>,
+    /// The command of each hotkey, indexed by its identifier.
+    commands: Vec<commands::Command>,
     restart_requested: bool,
 }
 
@@ -106,32 +222,11 @@ impl WindowManager {
     pub fn new(
         settings: settings::Settings,
         window_rules: window_rules::WindowRules,
-        existing_event_hook: Option<HWINEVENTHOOK>,
-        existing_vd_manager: Option<IVirtualDesktopManager>,
+        platform: Box<dyn platform::Platform>,
     ) -> Self {
-        let event_hook = match existing_event_hook {
-            Some(hook) => hook,
-            None => windows_api::set_win_event_hook(
-                EVENT_MIN,
-                EVENT_MAX,
-                None,
-                Some(cb::event_handler),
-                0,
-                0,
-                WINEVENT_OUTOFCONTEXT,
-            ),
-        };
-        let virtual_desktop_manager = match existing_vd_manager {
-            Some(vd_manager) => vd_manager,
-            None => {
-                let _ = windows_api::co_initialize_ex(None, COINIT_MULTITHREADED);
-                windows_api::co_create_instance(&VirtualDesktopManager, None, CLSCTX_INPROC_SERVER)
-                    .unwrap()
-            }
-        };
         Self {
-            event_hook,
-            virtual_desktop_manager,
+            platform,
+            user_layouts: Vec::new(),
             monitor_handles: Vec::new(),
             window_info: std::collections::HashMap::new(),
             workspaces: std::collections::HashMap::new(),
@@ -144,33 +239,41 @@ impl WindowManager {
             desktop_switching_state: DesktopSwitchingState::default(),
             settings,
             window_rules,
+            min_sizes: std::collections::HashMap::new(),
+            monitor_settings: std::collections::HashMap::new(),
+            desktop_settings: std::collections::HashMap::new(),
+            scratchpad_windows: std::collections::HashMap::new(),
+            pending_scratchpads: std::collections::HashSet::new(),
+            commands: Vec::new(),
             restart_requested: false,
         }
     }
 
     pub fn initialize(&mut self, layouts: Vec<Layout>) {
-        let _ = windows_api::enum_display_monitors(
-            None,
-            None,
-            Some(cb::enum_display_monitors_callback),
-            LPARAM(self as *mut WindowManager as isize),
-        );
-        for layout in layouts {
-            for (hmonitor, wm_layouts) in self.layouts.iter_mut() {
-                let mut layout =
-                    match util::convert_layout_for_monitor(&layout, HMONITOR(*hmonitor)) {
-                        Some(val) => val,
-                        None => layout.clone(),
-                    };
-                layout.update_all(self.settings.window_padding, self.settings.edge_padding);
-                wm_layouts.push(layout);
-            }
-        }
-        let _ = windows_api::enum_windows(
-            Some(cb::enum_windows_callback),
-            LPARAM(self as *mut WindowManager as isize),
-        );
-        let foreground_window = windows_api::get_foreground_window();
+        self.user_layouts = layouts;
+        let monitor_handles = self.platform.enum_display_monitors();
+        self.monitor_handles = monitor_handles.clone();
+        for hmonitor in monitor_handles {
+            self.initialize_monitor(hmonitor);
+        }
+        for guid in self.platform.get_desktop_ids() {
+            self.register_desktop(guid);
+        }
+        for hwnd in self.platform.enum_windows() {
+            let desktop_id = match self.platform.get_window_desktop_id(hwnd) {
+                Some(guid) if guid != GUID::zeroed() => guid,
+                _ => continue,
+            };
+            let monitor_handle = self.platform.monitor_from_window(hwnd);
+            if monitor_handle.is_invalid()
+                || !self.platform.is_window_visible(hwnd)
+                || !self.platform.is_overlapped_window(hwnd)
+            {
+                continue;
+            }
+            self.manage_new_window(desktop_id, monitor_handle, hwnd);
+        }
+        let foreground_window = self.platform.get_foreground_window();
         if self.window_info.contains_key(&foreground_window.0) {
             self.foreground_window = Some(foreground_window);
             self.set_border_to_focused(foreground_window);
@@ -178,12 +281,12 @@ impl WindowManager {
         self.update();
     }
 
-    pub fn event_hook(self) -> HWINEVENTHOOK {
-        self.event_hook
+    pub fn into_platform(self) -> Box<dyn platform::Platform> {
+        self.platform
     }
 
-    pub fn virtual_desktop_manager(&self) -> &IVirtualDesktopManager {
-        &self.virtual_desktop_manager
+    pub fn platform(&self) -> &dyn platform::Platform {
+        self.platform.as_ref()
     }
 
     pub fn monitor_handles(&self) -> &Vec<HMONITOR> {
@@ -226,10 +329,19 @@ impl WindowManager {
         self.restart_requested
     }
 
+    pub fn commands(&self) -> &[commands::Command] {
+        &self.commands
+    }
+
+    pub fn set_commands(&mut self, commands: Vec<commands::Command>) {
+        self.commands = commands;
+    }
+
     pub fn manage_new_window(&mut self, guid: GUID, hmonitor: HMONITOR, hwnd: HWND) {
+        self.register_desktop(guid);
         self.window_info.insert(
             hwnd.0,
-            WindowInfo::new(guid, hmonitor, util::is_restored(hwnd), 0),
+            WindowInfo::new(guid, hmonitor, self.platform.is_restored(hwnd), 0),
         );
         let filter = Some(std::collections::HashSet::from([
             window_rules::FilterRule::Layout,
@@ -248,7 +360,7 @@ impl WindowManager {
                     self.ignored_windows.insert(hwnd.0);
                     match set_position {
                         window_rules::SetPosition::Default => (),
-                        window_rules::SetPosition::Center => self.center_window(hwnd),
+                        window_rules::SetPosition::Center => self.center_window(hwnd, hmonitor),
                         window_rules::SetPosition::Position(window_rules::Position {
                             x,
                             y,
@@ -263,7 +375,9 @@ impl WindowManager {
             },
             None => self.push_hwnd(guid, hmonitor, hwnd),
         }
-        self.initialize_border(hwnd);
+        if !self.ignored_combinations.contains(&(guid, hmonitor.0)) {
+            self.initialize_border(hwnd);
+        }
     }
 
     pub fn manage_window(&mut self, hwnd: HWND) {
@@ -271,7 +385,7 @@ impl WindowManager {
         let monitor_handle;
         match self.window_info.get_mut(&hwnd.0) {
             Some(window_info) if window_info.restored => return,
-            Some(window_info) if util::is_restored(hwnd) => {
+            Some(window_info) if self.platform.is_restored(hwnd) => {
                 let idx = window_info.idx;
                 desktop_id = window_info.desktop_id;
                 monitor_handle = window_info.monitor_handle;
@@ -285,6 +399,10 @@ impl WindowManager {
                 if self.ignored_windows.contains(&hwnd.0) {
                     return;
                 }
+                if self.activate_tile_group_member(desktop_id, monitor_handle, hwnd) {
+                    self.update_workspace(desktop_id, monitor_handle);
+                    return;
+                }
                 self.insert_hwnd(desktop_id, monitor_handle, idx, hwnd);
                 self.update_workspace(desktop_id, monitor_handle);
             }
@@ -292,10 +410,15 @@ impl WindowManager {
                 if self.ignored_windows.contains(&hwnd.0) {
                     return;
                 }
+                if let Some(idx) = self.get_pending_scratchpad(hwnd) {
+                    self.claim_scratchpad_window(idx, hwnd);
+                    self.show_scratchpad(idx, hwnd);
+                    return;
+                }
                 let mut count = 0;
                 loop {
-                    match windows_api::get_window_desktop_id(&self.virtual_desktop_manager, hwnd) {
-                        Ok(guid) if guid != GUID::zeroed() => {
+                    match self.platform.get_window_desktop_id(hwnd) {
+                        Some(guid) if guid != GUID::zeroed() => {
                             desktop_id = guid;
                             break;
                         }
@@ -307,12 +430,12 @@ impl WindowManager {
                         return;
                     }
                 }
-                monitor_handle = windows_api::monitor_from_window(hwnd, MONITOR_DEFAULTTONULL);
+                monitor_handle = self.platform.monitor_from_window(hwnd);
                 if monitor_handle.is_invalid() {
                     return;
                 }
                 self.manage_new_window(desktop_id, monitor_handle, hwnd);
-                if let None = self.foreground_window {
+                if self.foreground_window.is_none() {
                     self.foreground_window_changed(hwnd, false);
                 }
             }
@@ -369,17 +492,23 @@ impl WindowManager {
         let window_info = window_info!(self, hwnd);
         self.desktop_switching_state.uncloak_count = 0;
         self.desktop_switching_state.max_uncloak_count = 0;
+        let old_desktop_id = window_info.desktop_id;
+        let new_desktop_id = match self.platform.get_window_desktop_id(hwnd) {
+            Some(guid) if guid != old_desktop_id => guid,
+            _ => return,
+        };
+        self.move_hwnd_to_desktop(hwnd, new_desktop_id);
+    }
+
+    /// Moves a window to the workspace of another desktop on the same monitor.
+    fn move_hwnd_to_desktop(&mut self, hwnd: HWND, new_desktop_id: GUID) {
         let WindowInfo {
             desktop_id: old_desktop_id,
             monitor_handle,
             restored,
             ..
-        } = window_info.to_owned();
-        let new_desktop_id =
-            match windows_api::get_window_desktop_id(&self.virtual_desktop_manager, hwnd) {
-                Ok(guid) if guid != old_desktop_id => guid,
-                _ => return,
-            };
+        } = self.window_info.get(&hwnd.0).unwrap().to_owned();
+        self.register_desktop(new_desktop_id);
         self.remove_hwnd_from_workspace(hwnd);
         if restored && !self.ignored_windows.contains(&hwnd.0) {
             self.push_hwnd(new_desktop_id, monitor_handle, hwnd);
@@ -430,7 +559,9 @@ impl WindowManager {
                         .get(&(uncloaked_desktop_id, current_monitor_handle.0))
                     {
                         Some(w) if !w.managed_window_handles.is_empty() => {
-                            let _ = windows_api::set_foreground_window(w.managed_window_handles[0]);
+                            let _ = self
+                                .platform
+                                .set_foreground_window(w.managed_window_handles[0]);
                         }
                         _ => (),
                     }
@@ -439,7 +570,8 @@ impl WindowManager {
             };
             let previous_desktop_id = self.window_info.get(&foreground_hwnd.0).unwrap().desktop_id;
             let mut gathered_hwnds_and_indices = Vec::new();
-            for monitor_handle in self.monitor_handles.to_owned() {
+            let monitor_handles = self.monitor_handles.clone();
+            for monitor_handle in monitor_handles.iter().copied() {
                 if let Some(workspace) = self
                     .workspaces
                     .get(&(uncloaked_desktop_id, monitor_handle.0))
@@ -461,15 +593,15 @@ impl WindowManager {
                 self.desktop_switching_state.uncloak_count += 1;
             }
             let mut new_desktop_id = None;
-            gathered_hwnds_and_indices.sort_by(|a, b| a.1.cmp(&b.1));
+            gathered_hwnds_and_indices.sort_by_key(|a| a.1);
             let gathered_hwnds = gathered_hwnds_and_indices
                 .iter()
                 .map(|(h, _idx)| *h)
                 .collect::<Vec<*mut core::ffi::c_void>>();
             for h in gathered_hwnds {
                 let info = self.window_info.get(&h).unwrap().to_owned();
-                match windows_api::get_window_desktop_id(&self.virtual_desktop_manager, HWND(h)) {
-                    Ok(guid) if guid != previous_desktop_id => {
+                match self.platform.get_window_desktop_id(HWND(h)) {
+                    Some(guid) if guid != previous_desktop_id => {
                         self.remove_hwnd_from_workspace(HWND(h));
                         if info.restored && !self.ignored_windows.contains(&h) {
                             self.push_hwnd(guid, info.monitor_handle, HWND(h));
@@ -490,7 +622,7 @@ impl WindowManager {
                 }
             }
             if let Some(guid) = new_desktop_id {
-                for monitor_handle in self.monitor_handles.to_owned() {
+                for monitor_handle in monitor_handles {
                     if let Some(previous_workspace) = self
                         .workspaces
                         .get(&(previous_desktop_id, monitor_handle.0))
@@ -552,12 +684,14 @@ impl WindowManager {
         if ignored_combination {
             return;
         }
-        if !self.ignored_windows.contains(&hwnd.0) && util::is_restored(hwnd) {
+        if !self.ignored_windows.contains(&hwnd.0) && self.platform.is_restored(hwnd) {
             if let Some(workspace) = self.workspaces.get(&(desktop_id, monitor_handle.0)) {
                 for h in &workspace.window_handles {
                     let info = self.window_info.get(h).unwrap();
                     if h != &hwnd.0 && (!info.restored || self.ignored_windows.contains(h)) {
-                        let _ = windows_api::show_window(HWND(*h), SW_MINIMIZE);
+                        let _ = self
+                            .platform
+                            .show_window(HWND(*h), platform::ShowWindowCommand::Minimize);
                     }
                 }
             }
@@ -575,7 +709,7 @@ impl WindowManager {
             restored,
             idx,
         } = window_info.to_owned();
-        let new_monitor_handle = windows_api::monitor_from_window(hwnd, MONITOR_DEFAULTTONULL);
+        let new_monitor_handle = self.platform.monitor_from_window(hwnd);
         if !restored {
             window_info.monitor_handle = new_monitor_handle;
             window_info.idx = match self.workspaces.get(&(desktop_id, new_monitor_handle.0)) {
@@ -585,12 +719,10 @@ impl WindowManager {
             return;
         }
         let changed_monitors = original_monitor_handle != new_monitor_handle;
-        let mut moved_to = RECT::default();
-        windows_api::get_window_rect(hwnd, &mut moved_to).unwrap();
-        let moved_to_area = (moved_to.right - moved_to.left) * (moved_to.bottom - moved_to.top);
-        let workspace;
-        if changed_monitors {
-            workspace = match self.workspaces.get_mut(&(desktop_id, new_monitor_handle.0)) {
+        let moved_to = self.platform.get_window_rect(hwnd).unwrap();
+        let moved_to_area = moved_to.w() * moved_to.h();
+        let workspace = if changed_monitors {
+            match self.workspaces.get_mut(&(desktop_id, new_monitor_handle.0)) {
                 Some(w) => w,
                 None => {
                     self.workspaces
@@ -598,16 +730,11 @@ impl WindowManager {
                         .unwrap()
                         .managed_window_handles
                         .remove(idx);
+                    let (layout_idx, variant_idx) =
+                        default_layout!(self, desktop_id, new_monitor_handle);
                     self.workspaces.insert(
                         (desktop_id, new_monitor_handle.0),
-                        Workspace::insert_into_new(
-                            hwnd,
-                            self.settings.default_layout_idx,
-                            self.layouts.get(&new_monitor_handle.0).unwrap()
-                                [self.settings.default_layout_idx]
-                                .default_variant_idx()
-                                .to_owned(),
-                        ),
+                        Workspace::insert_into_new(hwnd, layout_idx, variant_idx),
                     );
                     window_info.monitor_handle = new_monitor_handle;
                     window_info.idx = 0;
@@ -617,51 +744,40 @@ impl WindowManager {
                 }
             }
         } else {
-            workspace = match self
+            match self
                 .workspaces
                 .get_mut(&(desktop_id, original_monitor_handle.0))
             {
                 Some(w) => w,
                 None => return,
-            };
-        }
+            }
+        };
         let mut max_overlap_at: (usize, i32) = (workspace.managed_window_handles.len(), 0);
         {
-            let layout;
             let positions = if changed_monitors {
-                layout =
-                    &mut self.layouts.get_mut(&new_monitor_handle.0).unwrap()[workspace.layout_idx];
-                layout.get_internal_positions(
-                    &workspace.variant_idx,
+                workspace.get_internal_positions(
+                    self.layouts.get_mut(&new_monitor_handle.0).unwrap(),
                     workspace.managed_window_handles.len() + 1,
-                    self.settings.window_padding,
-                    self.settings.edge_padding,
+                    &self.settings,
                 )
             } else {
-                layout = &mut self.layouts.get_mut(&original_monitor_handle.0).unwrap()
-                    [workspace.layout_idx];
-                layout.get_internal_positions(
-                    &workspace.variant_idx,
+                workspace.get_internal_positions(
+                    self.layouts.get_mut(&original_monitor_handle.0).unwrap(),
                     workspace.managed_window_handles.len(),
-                    self.settings.window_padding,
-                    self.settings.edge_padding,
+                    &self.settings,
                 )
             };
             if !changed_monitors {
                 let position = &positions[idx];
-                if moved_to.left == position.x()
-                    && moved_to.top == position.y()
-                    && moved_to.right - moved_to.left == position.w()
-                    && moved_to.bottom - moved_to.top == position.h()
-                {
+                if &moved_to == position {
                     return;
                 }
             }
             for (i, p) in positions.iter().enumerate() {
-                let left = std::cmp::max(moved_to.left, p.x());
-                let top = std::cmp::max(moved_to.top, p.y());
-                let right = std::cmp::min(moved_to.right, p.x() + p.w());
-                let bottom = std::cmp::min(moved_to.bottom, p.y() + p.h());
+                let left = std::cmp::max(moved_to.x(), p.x());
+                let top = std::cmp::max(moved_to.y(), p.y());
+                let right = std::cmp::min(moved_to.x() + moved_to.w(), p.x() + p.w());
+                let bottom = std::cmp::min(moved_to.y() + moved_to.h(), p.y() + p.h());
                 let area = (right - left) * (bottom - top);
                 if area == moved_to_area {
                     max_overlap_at = (i, area);
@@ -721,7 +837,9 @@ impl WindowManager {
                 }
             }
         };
-        let _ = windows_api::set_foreground_window(workspace.managed_window_handles[to]);
+        let _ = self
+            .platform
+            .set_foreground_window(workspace.managed_window_handles[to]);
     }
 
     pub fn cycle_swap(&mut self, direction: CycleDirection) {
@@ -769,6 +887,97 @@ impl WindowManager {
         self.update_workspace(desktop_id, monitor_handle);
     }
 
+    /// Moves the focused window to `idx` in its workspace, or to the end if
+    /// there are not that many windows.
+    pub fn move_to_index(&mut self, idx: usize) {
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) if !self.ignored_windows.contains(&hwnd.0) => hwnd,
+            _ => return,
+        };
+        let window_info = window_info!(self, foreground_window, true);
+        let WindowInfo {
+            desktop_id,
+            monitor_handle,
+            restored: _,
+            idx: original_idx,
+        } = window_info.to_owned();
+        if self
+            .ignored_combinations
+            .contains(&(desktop_id, monitor_handle.0))
+        {
+            return;
+        }
+        let workspace = match self.workspaces.get(&(desktop_id, monitor_handle.0)) {
+            Some(val) if val.managed_window_handles.len() > 1 => val,
+            _ => return,
+        };
+        let new_idx = std::cmp::min(idx, workspace.managed_window_handles.len() - 1);
+        if new_idx == original_idx {
+            return;
+        }
+        self.unmanage_hwnd(desktop_id, monitor_handle, original_idx, false);
+        self.insert_hwnd(desktop_id, monitor_handle, new_idx, foreground_window);
+        self.update_workspace(desktop_id, monitor_handle);
+    }
+
+    /// Swaps the focused window with the first window in its workspace.
+    pub fn promote_to_master(&mut self) {
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) if !self.ignored_windows.contains(&hwnd.0) => hwnd,
+            _ => return,
+        };
+        let window_info = window_info!(self, foreground_window, true);
+        let WindowInfo {
+            desktop_id,
+            monitor_handle,
+            restored: _,
+            idx,
+        } = window_info.to_owned();
+        if idx == 0
+            || self
+                .ignored_combinations
+                .contains(&(desktop_id, monitor_handle.0))
+        {
+            return;
+        }
+        self.swap_windows(desktop_id, monitor_handle, idx, 0);
+        self.update_workspace(desktop_id, monitor_handle);
+    }
+
+    /// Moves every window in the focused workspace along by one, where the
+    /// window at the end wraps around to the other end.
+    pub fn rotate_windows(&mut self, direction: CycleDirection) {
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) if !self.ignored_windows.contains(&hwnd.0) => hwnd,
+            _ => return,
+        };
+        let window_info = window_info!(self, foreground_window, true);
+        let WindowInfo {
+            desktop_id,
+            monitor_handle,
+            restored: _,
+            idx: _,
+        } = window_info.to_owned();
+        if self
+            .ignored_combinations
+            .contains(&(desktop_id, monitor_handle.0))
+        {
+            return;
+        }
+        let workspace = match self.workspaces.get_mut(&(desktop_id, monitor_handle.0)) {
+            Some(val) if val.managed_window_handles.len() > 1 => val,
+            _ => return,
+        };
+        match direction {
+            CycleDirection::Previous => workspace.managed_window_handles.rotate_left(1),
+            CycleDirection::Next => workspace.managed_window_handles.rotate_right(1),
+        }
+        for (i, hwnd) in workspace.managed_window_handles.iter().enumerate() {
+            self.window_info.get_mut(&hwnd.0).unwrap().idx = i;
+        }
+        self.update_workspace(desktop_id, monitor_handle);
+    }
+
     pub fn cycle_variant(&mut self, direction: CycleDirection, idx: usize) {
         let foreground_window = match self.foreground_window {
             Some(hwnd) => hwnd,
@@ -871,6 +1080,77 @@ impl WindowManager {
         self.update_workspace(desktop_id, monitor_handle);
     }
 
+    /// Switches the focused workspace to the variant at `variant_idx` in its
+    /// layout if there is one.
+    pub fn set_variant(&mut self, variant_idx: &[usize]) {
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) => hwnd,
+            None => return,
+        };
+        let window_info = window_info!(self, foreground_window, true);
+        let WindowInfo {
+            desktop_id,
+            monitor_handle,
+            ..
+        } = window_info.to_owned();
+        if self
+            .ignored_combinations
+            .contains(&(desktop_id, monitor_handle.0))
+        {
+            return;
+        }
+        let workspace = match self.workspaces.get_mut(&(desktop_id, monitor_handle.0)) {
+            Some(val) => val,
+            _ => return,
+        };
+        if workspace.variant_idx == variant_idx
+            || !self.layouts.get(&monitor_handle.0).unwrap()[workspace.layout_idx]
+                .variants()
+                .variant_idxs()
+                .iter()
+                .any(|idx| idx == variant_idx)
+        {
+            return;
+        }
+        workspace.variant_idx = variant_idx.to_vec();
+        self.update_workspace(desktop_id, monitor_handle);
+    }
+
+    /// Switches the focused workspace to the layout with this name.
+    pub fn set_layout(&mut self, name: &str) {
+        let layout_idx = match self.settings.layout_idx_map.get(name) {
+            Some(val) => *val,
+            None => return,
+        };
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) => hwnd,
+            None => return,
+        };
+        let window_info = window_info!(self, foreground_window, true);
+        let WindowInfo {
+            desktop_id,
+            monitor_handle,
+            ..
+        } = window_info.to_owned();
+        if self
+            .ignored_combinations
+            .contains(&(desktop_id, monitor_handle.0))
+        {
+            return;
+        }
+        let workspace = match self.workspaces.get_mut(&(desktop_id, monitor_handle.0)) {
+            Some(val) => val,
+            _ => return,
+        };
+        let layouts = self.layouts.get(&monitor_handle.0).unwrap();
+        if layout_idx >= layouts.len() || workspace.layout_idx == layout_idx {
+            return;
+        }
+        workspace.layout_idx = layout_idx;
+        workspace.variant_idx = layouts[layout_idx].default_variant_idx().to_owned();
+        self.update_workspace(desktop_id, monitor_handle);
+    }
+
     pub fn cycle_focused_monitor(&self, direction: CycleDirection) {
         if self.monitor_handles.len() <= 1 {
             return;
@@ -910,14 +1190,35 @@ impl WindowManager {
                 }
             }
         }
-        let workspace = match self
-            .workspaces
-            .get(&(desktop_id, self.monitor_handles[idx].0))
-        {
+        self.focus_workspace(desktop_id, self.monitor_handles[idx]);
+    }
+
+    /// Focuses the monitor at `idx` in the order monitors are enumerated.
+    pub fn focus_monitor(&self, idx: usize) {
+        let hmonitor = match self.monitor_handles.get(idx) {
+            Some(val) => *val,
+            None => return,
+        };
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) => hwnd,
+            None => return,
+        };
+        let window_info = window_info!(self, foreground_window, true);
+        if window_info.monitor_handle == hmonitor {
+            return;
+        }
+        self.focus_workspace(window_info.desktop_id, hmonitor);
+    }
+
+    /// Focuses the first window of a workspace, if it has any.
+    fn focus_workspace(&self, guid: GUID, hmonitor: HMONITOR) {
+        let workspace = match self.workspaces.get(&(guid, hmonitor.0)) {
             Some(val) if !val.managed_window_handles.is_empty() => val,
             _ => return,
         };
-        let _ = windows_api::set_foreground_window(workspace.managed_window_handles[0]);
+        let _ = self
+            .platform
+            .set_foreground_window(workspace.managed_window_handles[0]);
     }
 
     pub fn cycle_assigned_monitor(&mut self, direction: CycleDirection) {
@@ -928,7 +1229,7 @@ impl WindowManager {
             Some(hwnd) if !self.ignored_windows.contains(&hwnd.0) => hwnd,
             _ => return,
         };
-        let original_dpi = windows_api::get_dpi_for_window(foreground_window);
+        let original_dpi = self.platform.get_dpi_for_window(foreground_window);
         let window_info = window_info!(self, foreground_window, true);
         let WindowInfo {
             desktop_id,
@@ -1005,16 +1306,11 @@ impl WindowManager {
             None => {
                 self.remove_hwnd_from_workspace(foreground_window);
                 // self.unmanage_hwnd(desktop_id, original_monitor_handle, original_window_idx);
+                let (layout_idx, variant_idx) =
+                    default_layout!(self, desktop_id, new_monitor_handle);
                 self.workspaces.insert(
                     (desktop_id, new_monitor_handle.0),
-                    Workspace::insert_into_new(
-                        foreground_window,
-                        self.settings.default_layout_idx,
-                        self.layouts.get(&new_monitor_handle.0).unwrap()
-                            [self.settings.default_layout_idx]
-                            .default_variant_idx()
-                            .to_owned(),
-                    ),
+                    Workspace::insert_into_new(foreground_window, layout_idx, variant_idx),
                 );
                 let window_info_mut = self.window_info.get_mut(&foreground_window.0).unwrap();
                 window_info_mut.monitor_handle = new_monitor_handle;
@@ -1023,20 +1319,16 @@ impl WindowManager {
         };
         self.update_workspace(desktop_id, original_monitor_handle);
         self.update_workspace(desktop_id, new_monitor_handle);
-        if windows_api::get_dpi_for_window(foreground_window) != original_dpi {
+        if self.platform.get_dpi_for_window(foreground_window) != original_dpi {
             let workspace = self
                 .workspaces
-                .get(&(desktop_id, new_monitor_handle.0))
+                .get_mut(&(desktop_id, new_monitor_handle.0))
                 .unwrap();
-            let layout =
-                &mut self.layouts.get_mut(&new_monitor_handle.0).unwrap()[workspace.layout_idx];
-            let position = &layout.get_internal_positions(
-                &workspace.variant_idx,
+            let position = &workspace.get_internal_positions(
+                self.layouts.get_mut(&new_monitor_handle.0).unwrap(),
                 workspace.managed_window_handles.len(),
-                self.settings.window_padding,
-                self.settings.edge_padding,
-            )[workspace.managed_window_handles.len() - 1]
-                .to_owned();
+                &self.settings,
+            )[workspace.managed_window_handles.len() - 1];
             let _ = self.set_visible_window_position(
                 foreground_window,
                 position.x(),
@@ -1047,49 +1339,243 @@ impl WindowManager {
         }
     }
 
-    pub fn grab_window(&mut self) {
-        self.grabbed_window = match self.foreground_window {
-            Some(hwnd) => match self.window_info.get(&hwnd.0) {
-                Some(val) if val.restored => Some(hwnd),
-                _ => None,
-            },
-            None => None,
-        }
-    }
-
-    pub fn release_window(&mut self) {
-        let grabbed_window = match self.grabbed_window {
-            Some(hwnd) => hwnd,
+    pub fn focus_direction(&self, direction: Direction) {
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) if !self.ignored_windows.contains(&hwnd.0) => hwnd,
+            _ => return,
+        };
+        let window_info = window_info!(self, foreground_window, true);
+        let WindowInfo {
+            desktop_id,
+            monitor_handle,
+            restored: _,
+            idx,
+        } = window_info.to_owned();
+        let from = match self.get_tile_position(desktop_id, monitor_handle, idx) {
+            Some(val) => val,
             None => return,
         };
+        let (hmonitor, to) =
+            match self.get_tile_in_direction(desktop_id, monitor_handle, &from, &direction) {
+                Some(val) => val,
+                None => match self.get_monitor_in_direction(desktop_id, monitor_handle, &direction)
+                {
+                    Some(hmonitor) => {
+                        match self.get_tile_in_direction(desktop_id, hmonitor, &from, &direction) {
+                            Some(val) => val,
+                            None => return,
+                        }
+                    }
+                    None => return,
+                },
+            };
+        let workspace = self.workspaces.get(&(desktop_id, hmonitor.0)).unwrap();
+        let _ = self
+            .platform
+            .set_foreground_window(workspace.managed_window_handles[to]);
+    }
+
+    pub fn swap_direction(&mut self, direction: Direction) {
         let foreground_window = match self.foreground_window {
-            Some(hwnd) if hwnd != grabbed_window => hwnd,
+            Some(hwnd) if !self.ignored_windows.contains(&hwnd.0) => hwnd,
             _ => return,
         };
-        let new_window_info = window_info!(self, foreground_window, true);
+        let window_info = window_info!(self, foreground_window, true);
         let WindowInfo {
-            desktop_id: new_desktop_id,
-            monitor_handle: new_monitor_handle,
+            desktop_id,
+            monitor_handle: original_monitor_handle,
             restored: _,
-            idx: new_idx,
-        } = new_window_info.to_owned();
+            idx,
+        } = window_info.to_owned();
         if self
             .ignored_combinations
-            .contains(&(new_desktop_id, new_monitor_handle.0))
+            .contains(&(desktop_id, original_monitor_handle.0))
         {
             return;
         }
-        let WindowInfo {
-            desktop_id: original_desktop_id,
-            monitor_handle: original_monitor_handle,
-            restored: _,
-            idx: original_idx,
-        } = self
-            .window_info
-            .get(&self.grabbed_window.unwrap().0)
-            .unwrap()
-            .to_owned();
-        if original_desktop_id != new_desktop_id {
+        let from = match self.get_tile_position(desktop_id, original_monitor_handle, idx) {
+            Some(val) => val,
+            None => return,
+        };
+        if let Some((_, swap_with)) =
+            self.get_tile_in_direction(desktop_id, original_monitor_handle, &from, &direction)
+        {
+            self.swap_windows(desktop_id, original_monitor_handle, idx, swap_with);
+            self.update_workspace(desktop_id, original_monitor_handle);
+            return;
+        }
+        let new_monitor_handle =
+            match self.get_monitor_in_direction(desktop_id, original_monitor_handle, &direction) {
+                Some(val) => val,
+                None => return,
+            };
+        let original_dpi = self.platform.get_dpi_for_window(foreground_window);
+        match self.get_tile_in_direction(desktop_id, new_monitor_handle, &from, &direction) {
+            Some((_, swap_with)) => {
+                self.move_windows_across_monitors(
+                    desktop_id,
+                    original_monitor_handle,
+                    new_monitor_handle,
+                    idx,
+                    swap_with,
+                );
+                // The window that was swapped with has been pushed along by
+                // the one that took its place
+                self.move_windows_across_monitors(
+                    desktop_id,
+                    new_monitor_handle,
+                    original_monitor_handle,
+                    swap_with + 1,
+                    idx,
+                );
+            }
+            None => {
+                let new_idx = match self.workspaces.get(&(desktop_id, new_monitor_handle.0)) {
+                    Some(w) => w.managed_window_handles.len(),
+                    None => 0,
+                };
+                self.move_windows_across_monitors(
+                    desktop_id,
+                    original_monitor_handle,
+                    new_monitor_handle,
+                    idx,
+                    new_idx,
+                );
+            }
+        }
+        self.update_workspace(desktop_id, original_monitor_handle);
+        self.update_workspace(desktop_id, new_monitor_handle);
+        // Windows are scaled when they first move to a monitor with a
+        // different DPI, so they need to be placed again
+        if self.platform.get_dpi_for_window(foreground_window) != original_dpi {
+            self.update_workspace(desktop_id, original_monitor_handle);
+            self.update_workspace(desktop_id, new_monitor_handle);
+        }
+    }
+
+    /// Moves the focused window to the previous or next virtual desktop,
+    /// wrapping around at the ends, and switches to it if `follow`.
+    pub fn cycle_window_desktop(&mut self, direction: CycleDirection, follow: bool) {
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) => hwnd,
+            None => return,
+        };
+        let desktop_id = window_info!(self, foreground_window).desktop_id;
+        let desktop_ids = self.platform.get_desktop_ids();
+        let idx = match desktop_ids.iter().position(|guid| *guid == desktop_id) {
+            Some(val) if desktop_ids.len() > 1 => val,
+            _ => return,
+        };
+        let new_idx = match direction {
+            CycleDirection::Previous => {
+                if idx == 0 {
+                    desktop_ids.len() - 1
+                } else {
+                    idx - 1
+                }
+            }
+            CycleDirection::Next => {
+                if idx == desktop_ids.len() - 1 {
+                    0
+                } else {
+                    idx + 1
+                }
+            }
+        };
+        self.send_to_desktop(foreground_window, desktop_ids[new_idx], follow);
+    }
+
+    /// Moves the focused window to the virtual desktop at `idx`, and switches
+    /// to it if `follow`.
+    pub fn move_to_desktop(&mut self, idx: usize, follow: bool) {
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) => hwnd,
+            None => return,
+        };
+        let new_desktop_id = match self.platform.get_desktop_ids().get(idx) {
+            Some(val) => *val,
+            None => return,
+        };
+        self.send_to_desktop(foreground_window, new_desktop_id, follow);
+    }
+
+    fn send_to_desktop(&mut self, hwnd: HWND, guid: GUID, follow: bool) {
+        let WindowInfo {
+            desktop_id,
+            monitor_handle,
+            idx,
+            ..
+        } = window_info!(self, hwnd).to_owned();
+        if guid == desktop_id || self.platform.move_window_to_desktop(hwnd, guid).is_err() {
+            return;
+        }
+        // The window is cloaked once it has moved, but the workspaces are
+        // updated here so that they do not depend on the order of the events
+        self.move_hwnd_to_desktop(hwnd, guid);
+        if follow && self.platform.switch_desktop(guid).is_ok() {
+            let _ = self.platform.set_foreground_window(hwnd);
+            return;
+        }
+        if self.foreground_window != Some(hwnd) {
+            return;
+        }
+        // The window can no longer be seen, so the focus moves to the window
+        // that took its place
+        self.foreground_window = None;
+        if let Some(workspace) = self.workspaces.get(&(desktop_id, monitor_handle.0)) {
+            let managed_window_handles = &workspace.managed_window_handles;
+            if let Some(next) = managed_window_handles
+                .get(idx)
+                .or(managed_window_handles.last())
+            {
+                let _ = self.platform.set_foreground_window(*next);
+            }
+        }
+    }
+
+    pub fn grab_window(&mut self) {
+        self.grabbed_window = match self.foreground_window {
+            Some(hwnd) => match self.window_info.get(&hwnd.0) {
+                Some(val) if val.restored => Some(hwnd),
+                _ => None,
+            },
+            None => None,
+        }
+    }
+
+    pub fn release_window(&mut self) {
+        let grabbed_window = match self.grabbed_window {
+            Some(hwnd) => hwnd,
+            None => return,
+        };
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) if hwnd != grabbed_window => hwnd,
+            _ => return,
+        };
+        let new_window_info = window_info!(self, foreground_window, true);
+        let WindowInfo {
+            desktop_id: new_desktop_id,
+            monitor_handle: new_monitor_handle,
+            restored: _,
+            idx: new_idx,
+        } = new_window_info.to_owned();
+        if self
+            .ignored_combinations
+            .contains(&(new_desktop_id, new_monitor_handle.0))
+        {
+            return;
+        }
+        let WindowInfo {
+            desktop_id: original_desktop_id,
+            monitor_handle: original_monitor_handle,
+            restored: _,
+            idx: original_idx,
+        } = self
+            .window_info
+            .get(&self.grabbed_window.unwrap().0)
+            .unwrap()
+            .to_owned();
+        if original_desktop_id != new_desktop_id {
             return;
         }
         let was_ignored = if self.ignored_windows.remove(&grabbed_window.0) {
@@ -1098,7 +1584,9 @@ impl WindowManager {
                 .get_mut(&self.grabbed_window.unwrap().0)
                 .unwrap();
             original_window_info.restored = true;
-            let _ = windows_api::show_window(grabbed_window, SW_RESTORE);
+            let _ = self
+                .platform
+                .show_window(grabbed_window, platform::ShowWindowCommand::Restore);
             true
         } else {
             false
@@ -1136,23 +1624,19 @@ impl WindowManager {
                     new_idx,
                 );
             }
-            let original_dpi = windows_api::get_dpi_for_window(grabbed_window);
+            let original_dpi = self.platform.get_dpi_for_window(grabbed_window);
             self.update_workspace(original_desktop_id, original_monitor_handle);
             self.update_workspace(original_desktop_id, new_monitor_handle);
-            if windows_api::get_dpi_for_window(grabbed_window) != original_dpi {
+            if self.platform.get_dpi_for_window(grabbed_window) != original_dpi {
                 let workspace = self
                     .workspaces
-                    .get(&(original_desktop_id, new_monitor_handle.0))
+                    .get_mut(&(original_desktop_id, new_monitor_handle.0))
                     .unwrap();
-                let layout =
-                    &mut self.layouts.get_mut(&new_monitor_handle.0).unwrap()[workspace.layout_idx];
-                let position = &layout.get_internal_positions(
-                    &workspace.variant_idx,
+                let position = &workspace.get_internal_positions(
+                    self.layouts.get_mut(&new_monitor_handle.0).unwrap(),
                     workspace.managed_window_handles.len(),
-                    self.settings.window_padding,
-                    self.settings.edge_padding,
-                )[new_idx]
-                    .to_owned();
+                    &self.settings,
+                )[new_idx];
                 let _ = self.set_visible_window_position(
                     grabbed_window,
                     position.x(),
@@ -1162,7 +1646,7 @@ impl WindowManager {
                 );
             }
         }
-        let _ = windows_api::set_foreground_window(grabbed_window);
+        let _ = self.platform.set_foreground_window(grabbed_window);
         self.grabbed_window = None;
     }
 
@@ -1185,24 +1669,20 @@ impl WindowManager {
         }
         if self.ignored_windows.remove(&foreground_window.0) {
             if restored {
-                let original_dpi = windows_api::get_dpi_for_window(foreground_window);
+                let original_dpi = self.platform.get_dpi_for_window(foreground_window);
                 self.foreground_window_changed(foreground_window, true);
                 self.insert_hwnd(desktop_id, monitor_handle, idx, foreground_window);
                 self.update_workspace(desktop_id, monitor_handle);
-                if windows_api::get_dpi_for_window(foreground_window) != original_dpi {
+                if self.platform.get_dpi_for_window(foreground_window) != original_dpi {
                     let workspace = self
                         .workspaces
-                        .get(&(desktop_id, monitor_handle.0))
+                        .get_mut(&(desktop_id, monitor_handle.0))
                         .unwrap();
-                    let layout =
-                        &mut self.layouts.get_mut(&monitor_handle.0).unwrap()[workspace.layout_idx];
-                    let position = &layout.get_internal_positions(
-                        &workspace.variant_idx,
+                    let position = &workspace.get_internal_positions(
+                        self.layouts.get_mut(&monitor_handle.0).unwrap(),
                         workspace.managed_window_handles.len(),
-                        self.settings.window_padding,
-                        self.settings.edge_padding,
-                    )[workspace.managed_window_handles.len() - 1]
-                        .to_owned();
+                        &self.settings,
+                    )[workspace.managed_window_handles.len() - 1];
                     let _ = self.set_visible_window_position(
                         foreground_window,
                         position.x(),
@@ -1214,125 +1694,614 @@ impl WindowManager {
             }
         } else {
             self.ignored_windows.insert(foreground_window.0);
-            if let None = self.unmanage_hwnd(desktop_id, monitor_handle, idx, false) {
+            if self
+                .unmanage_hwnd(desktop_id, monitor_handle, idx, false)
+                .is_none()
+            {
                 return;
             }
+            self.leave_tile_group(desktop_id, monitor_handle, foreground_window);
             self.update_workspace(desktop_id, monitor_handle);
             let filter = Some(std::collections::HashSet::from([
                 window_rules::FilterRule::FloatingPosition,
             ]));
             match self.get_window_rule(foreground_window, &filter) {
-                Some(rule) => match rule {
-                    window_rules::Rule::FloatingPosition(window_rules::Position { x, y, w, h }) => {
-                        let _ = self.set_visible_window_position(foreground_window, x, y, w, h);
-                    }
-                    _ => (),
-                },
-                None => self.center_window(foreground_window),
+                Some(window_rules::Rule::FloatingPosition(window_rules::Position {
+                    x,
+                    y,
+                    w,
+                    h,
+                })) => {
+                    let _ = self.set_visible_window_position(foreground_window, x, y, w, h);
+                }
+                Some(_) => (),
+                None => self.center_window(foreground_window, monitor_handle),
             }
         }
     }
 
-    pub fn toggle_workspace(&mut self) {
+    pub fn spawn(&self, program: &str, args: &[String]) {
+        let _ = self.platform.spawn(program, args);
+    }
+
+    /// Shows the window of a scratchpad on the current desktop and monitor, or
+    /// hides it if it is already shown there. The command of the scratchpad is
+    /// run if no window matches it.
+    pub fn toggle_scratchpad(&mut self, name: &str) {
+        let idx = match self
+            .settings
+            .scratchpads
+            .iter()
+            .position(|scratchpad| scratchpad.name == name)
+        {
+            Some(val) => val,
+            None => return,
+        };
+        let hwnd = match self.scratchpad_windows.get(&idx) {
+            Some(hwnd) if self.platform.is_window(*hwnd) => Some(*hwnd),
+            _ => self.find_scratchpad_window(idx),
+        };
+        let hwnd = match hwnd {
+            Some(val) => val,
+            None => {
+                let scratchpad = &self.settings.scratchpads[idx];
+                if self
+                    .platform
+                    .spawn(&scratchpad.command, &scratchpad.args)
+                    .is_ok()
+                {
+                    self.pending_scratchpads.insert(idx);
+                }
+                return;
+            }
+        };
+        self.claim_scratchpad_window(idx, hwnd);
+        let current_desktop_id = self
+            .platform
+            .get_window_desktop_id(self.platform.get_foreground_window());
+        if self.platform.is_window_visible(hwnd)
+            && self.platform.get_window_desktop_id(hwnd) == current_desktop_id
+        {
+            let _ = self
+                .platform
+                .show_window(hwnd, platform::ShowWindowCommand::Hide);
+            return;
+        }
+        self.show_scratchpad(idx, hwnd);
+    }
+
+    pub fn toggle_workspace(&mut self) {
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) => hwnd,
+            None => return,
+        };
+        let WindowInfo {
+            desktop_id,
+            monitor_handle,
+            ..
+        } = window_info!(self, foreground_window);
+        let workspace = match self.workspaces.get(&(*desktop_id, monitor_handle.0)) {
+            Some(w) => w,
+            None => return,
+        };
+        if self
+            .ignored_combinations
+            .remove(&(*desktop_id, monitor_handle.0))
+        {
+            for h in &workspace.window_handles {
+                self.initialize_border(HWND(*h));
+            }
+            self.set_border_to_focused(foreground_window);
+            self.update_workspace(*desktop_id, *monitor_handle);
+        } else {
+            for h in &workspace.window_handles {
+                self.reset_border(HWND(*h));
+            }
+            self.ignored_combinations
+                .insert((*desktop_id, monitor_handle.0));
+        }
+    }
+
+    pub fn refresh_workspace(&mut self) {
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) => hwnd,
+            None => return,
+        };
+        let WindowInfo {
+            desktop_id,
+            monitor_handle,
+            ..
+        } = window_info_owned!(self, foreground_window);
+        if self
+            .ignored_combinations
+            .contains(&(desktop_id, monitor_handle.0))
+        {
+            return;
+        }
+        self.update_workspace(desktop_id, monitor_handle);
+    }
+
+    pub fn resize_focused_window(&mut self, direction: Direction, grow: bool) {
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) if !self.ignored_windows.contains(&hwnd.0) => hwnd,
+            _ => return,
+        };
+        let window_info = window_info!(self, foreground_window, true);
+        let WindowInfo {
+            desktop_id,
+            monitor_handle,
+            restored: _,
+            idx,
+        } = window_info.to_owned();
+        let workspace = match self.workspaces.get_mut(&(desktop_id, monitor_handle.0)) {
+            Some(w) => w,
+            None => return,
+        };
+        let layout = workspace
+            .resized_layouts
+            .entry(workspace.layout_idx)
+            .or_insert_with(|| {
+                self.layouts.get(&monitor_handle.0).unwrap()[workspace.layout_idx].clone()
+            });
+        let delta = if grow {
+            self.settings.resize_step
+        } else {
+            -self.settings.resize_step
+        };
+        if layout
+            .variants_mut()
+            .get_innermost_mut(&workspace.variant_idx)
+            .resize(
+                workspace.managed_window_handles.len(),
+                idx,
+                direction,
+                delta,
+            )
+        {
+            self.update_workspace(desktop_id, monitor_handle);
+        }
+    }
+
+    /// Changes the number of master windows in the focused workspace's
+    /// layout if its variant ends in a master/stack.
+    pub fn change_master_count(&mut self, increase: bool) {
+        self.edit_focused_variant(|variant, _| {
+            if increase {
+                variant.increment_master_count()
+            } else {
+                variant.decrement_master_count()
+            }
+        });
+    }
+
+    /// Grows or shrinks the master area of the focused workspace's layout if
+    /// its variant ends in a master/stack.
+    pub fn resize_master(&mut self, grow: bool) {
+        self.edit_focused_variant(|variant, settings| {
+            if grow {
+                variant.grow_master(settings.master_ratio_step)
+            } else {
+                variant.shrink_master(settings.master_ratio_step)
+            }
+        });
+    }
+
+    /// Runs `edit` on the current variant of the focused workspace's own copy
+    /// of its layout and updates the workspace if it returns `true`.
+    fn edit_focused_variant<F>(&mut self, edit: F)
+    where
+        F: FnOnce(&mut Variant, &settings::Settings) -> bool,
+    {
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) => hwnd,
+            None => return,
+        };
+        let window_info = window_info!(self, foreground_window);
+        let WindowInfo {
+            desktop_id,
+            monitor_handle,
+            restored: _,
+            idx: _,
+        } = window_info.to_owned();
+        let workspace = match self.workspaces.get_mut(&(desktop_id, monitor_handle.0)) {
+            Some(w) => w,
+            None => return,
+        };
+        let layout = workspace
+            .resized_layouts
+            .entry(workspace.layout_idx)
+            .or_insert_with(|| {
+                self.layouts.get(&monitor_handle.0).unwrap()[workspace.layout_idx].clone()
+            });
+        let variant = layout
+            .variants_mut()
+            .get_innermost_mut(&workspace.variant_idx);
+        if edit(variant, &self.settings) {
+            self.update_workspace(desktop_id, monitor_handle);
+        }
+    }
+
+    pub fn change_focused_window_weight(&mut self, increase: bool) {
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) if !self.ignored_windows.contains(&hwnd.0) => hwnd,
+            _ => return,
+        };
+        let window_info = window_info!(self, foreground_window, true);
+        let WindowInfo {
+            desktop_id,
+            monitor_handle,
+            restored: _,
+            idx: _,
+        } = window_info.to_owned();
+        let workspace = match self.workspaces.get_mut(&(desktop_id, monitor_handle.0)) {
+            Some(w) => w,
+            None => return,
+        };
+        let weight = workspace
+            .window_weights
+            .entry(foreground_window.0)
+            .or_insert(weights::DEFAULT_WEIGHT);
+        let new_weight = if increase {
+            *weight + self.settings.weight_step
+        } else {
+            f64::max(*weight - self.settings.weight_step, weights::MIN_WEIGHT)
+        };
+        if new_weight != *weight {
+            *weight = new_weight;
+            self.update_workspace(desktop_id, monitor_handle);
+        }
+    }
+
+    pub fn reset_weights(&mut self) {
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) => hwnd,
+            None => return,
+        };
+        let window_info = window_info!(self, foreground_window);
+        let WindowInfo {
+            desktop_id,
+            monitor_handle,
+            restored: _,
+            idx: _,
+        } = window_info.to_owned();
+        let workspace = match self.workspaces.get_mut(&(desktop_id, monitor_handle.0)) {
+            Some(w) => w,
+            None => return,
+        };
+        if !workspace.window_weights.is_empty() {
+            workspace.window_weights.clear();
+            self.update_workspace(desktop_id, monitor_handle);
+        }
+    }
+
+    pub fn toggle_monocle(&mut self) {
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) => hwnd,
+            None => return,
+        };
+        let window_info = window_info!(self, foreground_window);
+        let WindowInfo {
+            desktop_id,
+            monitor_handle,
+            restored: _,
+            idx: _,
+        } = window_info.to_owned();
+        let workspace = match self.workspaces.get_mut(&(desktop_id, monitor_handle.0)) {
+            Some(w) => w,
+            None => return,
+        };
+        workspace.monocle = !workspace.monocle;
+        workspace.monocle_top = None;
+        self.update_workspace(desktop_id, monitor_handle);
+    }
+
+    /// Moves the focused window into the tile group of the tile before or after
+    /// it, where it becomes the member that is shown.
+    pub fn join_tile_group(&mut self, direction: CycleDirection) {
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) if !self.ignored_windows.contains(&hwnd.0) => hwnd,
+            _ => return,
+        };
+        let window_info = window_info!(self, foreground_window, true);
+        let WindowInfo {
+            desktop_id,
+            monitor_handle,
+            restored: _,
+            idx,
+        } = window_info.to_owned();
+        let workspace = match self.workspaces.get(&(desktop_id, monitor_handle.0)) {
+            Some(val) if val.managed_window_handles.len() > 1 => val,
+            _ => return,
+        };
+        let len = workspace.managed_window_handles.len();
+        let target = match direction {
+            CycleDirection::Previous => workspace.managed_window_handles[(idx + len - 1) % len],
+            CycleDirection::Next => workspace.managed_window_handles[(idx + 1) % len],
+        };
+        self.unmanage_hwnd(desktop_id, monitor_handle, idx, false);
+        let workspace = self
+            .workspaces
+            .get_mut(&(desktop_id, monitor_handle.0))
+            .unwrap();
+        let target_idx = self.window_info.get(&target.0).unwrap().idx;
+        let mut members = match workspace.tile_groups.remove(&target.0) {
+            Some(members) => members,
+            None => vec![target],
+        };
+        match workspace.tile_groups.remove(&foreground_window.0) {
+            Some(mut foreground_members) => members.append(&mut foreground_members),
+            None => members.push(foreground_window),
+        }
+        workspace.tile_groups.insert(foreground_window.0, members);
+        workspace.managed_window_handles[target_idx] = foreground_window;
+        let window_info = self.window_info.get_mut(&foreground_window.0).unwrap();
+        window_info.idx = target_idx;
+        self.hide_tile_group_member(target);
+        self.update_workspace(desktop_id, monitor_handle);
+    }
+
+    /// Moves the focused window out of its tile group into a tile of its own
+    /// after the group's tile.
+    pub fn split_tile_group(&mut self) {
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) if !self.ignored_windows.contains(&hwnd.0) => hwnd,
+            _ => return,
+        };
+        let window_info = window_info!(self, foreground_window, true);
+        let WindowInfo {
+            desktop_id,
+            monitor_handle,
+            restored: _,
+            idx,
+        } = window_info.to_owned();
+        let workspace = match self.workspaces.get_mut(&(desktop_id, monitor_handle.0)) {
+            Some(w) => w,
+            None => return,
+        };
+        let mut members = match workspace.tile_groups.remove(&foreground_window.0) {
+            Some(members) => members,
+            None => return,
+        };
+        let position = members
+            .iter()
+            .position(|h| *h == foreground_window)
+            .unwrap();
+        members.remove(position);
+        let next = members[position % members.len()];
+        if members.len() > 1 {
+            workspace.tile_groups.insert(next.0, members);
+        }
+        workspace.managed_window_handles[idx] = next;
+        self.show_tile_group_member(next, idx);
+        self.insert_hwnd(desktop_id, monitor_handle, idx + 1, foreground_window);
+        self.update_workspace(desktop_id, monitor_handle);
+    }
+
+    /// Shows the previous or next member of the focused window's tile group in
+    /// its place.
+    pub fn cycle_tile_group(&mut self, direction: CycleDirection) {
+        let foreground_window = match self.foreground_window {
+            Some(hwnd) if !self.ignored_windows.contains(&hwnd.0) => hwnd,
+            _ => return,
+        };
+        let window_info = window_info!(self, foreground_window, true);
+        let WindowInfo {
+            desktop_id,
+            monitor_handle,
+            restored: _,
+            idx,
+        } = window_info.to_owned();
+        let workspace = match self.workspaces.get_mut(&(desktop_id, monitor_handle.0)) {
+            Some(w) => w,
+            None => return,
+        };
+        let members = match workspace.tile_groups.remove(&foreground_window.0) {
+            Some(members) => members,
+            None => return,
+        };
+        let position = members
+            .iter()
+            .position(|h| *h == foreground_window)
+            .unwrap();
+        let len = members.len();
+        let next = match direction {
+            CycleDirection::Previous => members[(position + len - 1) % len],
+            CycleDirection::Next => members[(position + 1) % len],
+        };
+        workspace.tile_groups.insert(next.0, members);
+        workspace.managed_window_handles[idx] = next;
+        self.show_tile_group_member(next, idx);
+        self.hide_tile_group_member(foreground_window);
+        let _ = self.platform.set_foreground_window(next);
+        self.update_workspace(desktop_id, monitor_handle);
+    }
+
+    pub fn change_gaps(&mut self, increase: bool) {
         let foreground_window = match self.foreground_window {
             Some(hwnd) => hwnd,
             None => return,
         };
+        let window_info = window_info!(self, foreground_window);
         let WindowInfo {
             desktop_id,
             monitor_handle,
-            ..
-        } = window_info!(self, foreground_window);
-        let workspace = match self.workspaces.get(&(*desktop_id, monitor_handle.0)) {
+            restored: _,
+            idx: _,
+        } = window_info.to_owned();
+        let workspace = match self.workspaces.get_mut(&(desktop_id, monitor_handle.0)) {
             Some(w) => w,
             None => return,
         };
-        if self
-            .ignored_combinations
-            .remove(&(*desktop_id, monitor_handle.0))
-        {
-            for h in &workspace.window_handles {
-                self.initialize_border(HWND(*h));
-            }
-            self.set_border_to_focused(foreground_window);
-            self.update_workspace(*desktop_id, *monitor_handle);
+        let layout = match workspace.resized_layouts.get(&workspace.layout_idx) {
+            Some(l) => l,
+            None => &self.layouts.get(&monitor_handle.0).unwrap()[workspace.layout_idx],
+        };
+        let padding = layout.padding().apply(&self.settings.padding);
+        // Shrinking gaps stops once both of them are gone
+        let min_offset = -std::cmp::max(padding.horizontal, padding.vertical);
+        let gap_offset = if increase {
+            workspace.gap_offset + self.settings.gap_step
         } else {
-            for h in &workspace.window_handles {
-                Self::reset_border(HWND(*h));
-            }
-            self.ignored_combinations
-                .insert((*desktop_id, monitor_handle.0));
+            std::cmp::max(workspace.gap_offset - self.settings.gap_step, min_offset)
+        };
+        if gap_offset != workspace.gap_offset {
+            workspace.gap_offset = gap_offset;
+            self.update_workspace(desktop_id, monitor_handle);
         }
     }
 
-    pub fn refresh_workspace(&mut self) {
+    pub fn toggle_gaps(&mut self) {
         let foreground_window = match self.foreground_window {
             Some(hwnd) => hwnd,
             None => return,
         };
+        let window_info = window_info!(self, foreground_window);
         let WindowInfo {
             desktop_id,
             monitor_handle,
-            ..
-        } = window_info_owned!(self, foreground_window);
-        if self
-            .ignored_combinations
-            .contains(&(desktop_id, monitor_handle.0))
-        {
+            restored: _,
+            idx: _,
+        } = window_info.to_owned();
+        let workspace = match self.workspaces.get_mut(&(desktop_id, monitor_handle.0)) {
+            Some(w) => w,
+            None => return,
+        };
+        workspace.gaps_disabled = !workspace.gaps_disabled;
+        self.update_workspace(desktop_id, monitor_handle);
+    }
+
+    /// Picks up monitors that were added or removed and work areas that
+    /// changed, moving the windows on removed monitors to the first monitor.
+    ///
+    /// Workspaces keep their state, with the resizes made to their layouts
+    /// scaled to the new work area. Workspaces on removed monitors are merged
+    /// into the ones on the first monitor.
+    pub fn display_changed(&mut self) {
+        let monitor_handles = self.platform.enum_display_monitors();
+        if monitor_handles.is_empty() {
             return;
         }
-        self.update_workspace(desktop_id, monitor_handle);
+        let removed: Vec<HMONITOR> = self
+            .monitor_handles
+            .iter()
+            .filter(|h| !monitor_handles.contains(h))
+            .copied()
+            .collect();
+        let added: Vec<HMONITOR> = monitor_handles
+            .iter()
+            .filter(|h| !self.monitor_handles.contains(h))
+            .copied()
+            .collect();
+        self.monitor_handles = monitor_handles.clone();
+        self.layouts.clear();
+        self.monitor_settings.clear();
+        for hmonitor in monitor_handles {
+            self.initialize_monitor(hmonitor);
+        }
+        for (guid, desktop_settings) in &self.desktop_settings {
+            if desktop_settings.ignored {
+                for monitor_handle in &added {
+                    self.ignored_combinations.insert((*guid, monitor_handle.0));
+                }
+            }
+        }
+        for ((_, hmonitor), workspace) in self.workspaces.iter_mut() {
+            if !self.monitor_handles.contains(&HMONITOR(*hmonitor)) {
+                continue;
+            }
+            let work_area = self.platform.get_monitor_work_area(HMONITOR(*hmonitor));
+            workspace.move_resized_layouts(&work_area, &self.settings);
+        }
+        for hmonitor in removed {
+            self.move_workspaces_to_monitor(hmonitor, self.monitor_handles[0]);
+        }
+        self.update();
     }
 
     pub fn restart_himewm(&mut self) {
         self.restart_requested = true;
-        windows_api::post_message(
-            None,
-            messages::messages::RESTART_HIMEWM,
-            WPARAM::default(),
-            LPARAM::default(),
-        )
-        .unwrap();
+        self.platform
+            .post_message(messages::messages::RESTART_HIMEWM, 0);
     }
 
     fn update_workspace(&mut self, guid: GUID, hmonitor: HMONITOR) {
         if self.ignored_combinations.contains(&(guid, hmonitor.0)) {
             return;
         }
-        let workspace = match self.workspaces.get(&(guid, hmonitor.0)) {
+        let workspace = match self.workspaces.get_mut(&(guid, hmonitor.0)) {
             Some(w) => w,
             None => return,
         };
         if workspace.managed_window_handles.is_empty() {
             return;
         }
-        let layout = &mut self.layouts.get_mut(&hmonitor.0).unwrap()[workspace.layout_idx];
+        let managed_window_handles = workspace.managed_window_handles.to_owned();
+        let min_sizes: Vec<(i32, i32)> = managed_window_handles
+            .iter()
+            .map(|hwnd| self.get_min_size(*hwnd))
+            .collect();
         let mut error_hwnds: Option<Vec<HWND>> = None;
-        let positions = layout
-            .get_internal_positions(
-                &workspace.variant_idx,
-                workspace.managed_window_handles.len(),
-                self.settings.window_padding,
-                self.settings.edge_padding,
+        let workspace = self.workspaces.get_mut(&(guid, hmonitor.0)).unwrap();
+        let positions = if workspace.monocle {
+            let layouts = self.layouts.get(&hmonitor.0).unwrap();
+            let monitor_rect = layouts[workspace.layout_idx].monitor_rect();
+            let padding = workspace.get_padding(layouts, &self.settings);
+            vec![
+                Some(Position::new(
+                    monitor_rect.x() + padding.left,
+                    monitor_rect.y() + padding.top,
+                    monitor_rect.w() - padding.left - padding.right,
+                    monitor_rect.h() - padding.top - padding.bottom,
+                ));
+                managed_window_handles.len()
+            ]
+        } else {
+            workspace.get_fitted_internal_positions(
+                self.layouts.get_mut(&hmonitor.0).unwrap(),
+                &min_sizes,
+                &self.settings,
             )
-            .to_owned();
+        };
+        // Windows that do not fit are centered once when they start floating,
+        // and are left where they are after that
+        let overflowed_windows: std::collections::HashSet<*mut core::ffi::c_void> =
+            managed_window_handles
+                .iter()
+                .zip(&positions)
+                .filter(|(_, position)| position.is_none())
+                .map(|(hwnd, _)| hwnd.0)
+                .collect();
+        let newly_overflowed_windows: Vec<HWND> = overflowed_windows
+            .difference(&workspace.overflowed_windows)
+            .map(|h| HWND(*h))
+            .collect();
+        workspace.overflowed_windows = overflowed_windows;
+        workspace.positions = positions.clone();
+        for hwnd in newly_overflowed_windows {
+            self.center_window(hwnd, hmonitor);
+        }
+        let workspace = self.workspaces.get(&(guid, hmonitor.0)).unwrap();
         for (i, hwnd) in workspace.managed_window_handles.iter().enumerate() {
+            let position = match &positions[i] {
+                Some(p) => p,
+                None => continue,
+            };
             match self.set_visible_window_position(
                 *hwnd,
-                positions[i].x(),
-                positions[i].y(),
-                positions[i].w(),
-                positions[i].h(),
+                position.x(),
+                position.y(),
+                position.w(),
+                position.h(),
             ) {
                 Ok(_) => continue,
-                Err(_) => {
+                Err(e) => {
                     match &mut error_hwnds {
                         Some(v) => v.push(*hwnd),
                         None => {
                             error_hwnds = Some(vec![*hwnd]);
                         }
                     }
-                    if windows_api::get_last_error() == ERROR_ACCESS_DENIED {
+                    if e == platform::Error::AccessDenied {
                         self.ignored_windows.insert(hwnd.0);
                     }
                 }
@@ -1343,6 +2312,24 @@ impl WindowManager {
                 self.remove_hwnd(h);
             }
             self.update_workspace(guid, hmonitor);
+            return;
+        }
+        // Every window is in the same place, so the focused one needs to be on
+        // top, which only changes when monocle is turned on or another window
+        // is focused
+        match self.foreground_window {
+            Some(hwnd)
+                if workspace.monocle
+                    && workspace.monocle_top != Some(hwnd)
+                    && workspace.managed_window_handles.contains(&hwnd) =>
+            {
+                let _ = self.platform.set_foreground_window(hwnd);
+                self.workspaces
+                    .get_mut(&(guid, hmonitor.0))
+                    .unwrap()
+                    .monocle_top = Some(hwnd);
+            }
+            _ => (),
         }
     }
 
@@ -1374,6 +2361,63 @@ impl WindowManager {
         managed_window_handles.swap(i, j);
     }
 
+    /// Where the window at `idx` was last tiled, if the workspace has not
+    /// changed since.
+    fn get_tile_position(&self, guid: GUID, hmonitor: HMONITOR, idx: usize) -> Option<Position> {
+        let workspace = self.workspaces.get(&(guid, hmonitor.0))?;
+        if workspace.positions.len() != workspace.managed_window_handles.len() {
+            return None;
+        }
+        return workspace.positions.get(idx)?.to_owned();
+    }
+
+    /// The monitor and index of the tile on a workspace nearest to `from` in
+    /// `direction`.
+    fn get_tile_in_direction(
+        &self,
+        guid: GUID,
+        hmonitor: HMONITOR,
+        from: &Position,
+        direction: &Direction,
+    ) -> Option<(HMONITOR, usize)> {
+        if self.ignored_combinations.contains(&(guid, hmonitor.0)) {
+            return None;
+        }
+        let workspace = self.workspaces.get(&(guid, hmonitor.0))?;
+        if workspace.positions.len() != workspace.managed_window_handles.len() {
+            return None;
+        }
+        let idx = neighbours::nearest_in_direction(from, &workspace.positions, direction)?;
+        return Some((hmonitor, idx));
+    }
+
+    /// The monitor nearest to `hmonitor` in `direction` whose workspace on
+    /// the desktop is not ignored.
+    fn get_monitor_in_direction(
+        &self,
+        guid: GUID,
+        hmonitor: HMONITOR,
+        direction: &Direction,
+    ) -> Option<HMONITOR> {
+        let work_areas: Vec<Option<Position>> = self
+            .monitor_handles
+            .iter()
+            .map(|h| {
+                if *h == hmonitor || self.ignored_combinations.contains(&(guid, h.0)) {
+                    None
+                } else {
+                    Some(self.platform.get_monitor_work_area(*h))
+                }
+            })
+            .collect();
+        let idx = neighbours::nearest_in_direction(
+            &self.platform.get_monitor_work_area(hmonitor),
+            &work_areas,
+            direction,
+        )?;
+        return Some(self.monitor_handles[idx]);
+    }
+
     fn move_windows_across_monitors(
         &mut self,
         guid: GUID,
@@ -1386,56 +2430,162 @@ impl WindowManager {
             Some(val) => val,
             None => return,
         };
+        self.leave_tile_group(guid, first_hmonitor, hwnd);
         self.insert_hwnd(guid, second_hmonitor, second_idx, hwnd);
     }
 
+    /// Resolves the settings and layouts of a monitor in `monitor_handles`.
+    fn initialize_monitor(&mut self, hmonitor: HMONITOR) {
+        let idx = self
+            .monitor_handles
+            .iter()
+            .position(|h| *h == hmonitor)
+            .unwrap();
+        let rect = self.platform.get_monitor_rect(hmonitor);
+        let mut monitor_settings = self.settings.monitor_settings(
+            &self.platform.get_monitor_device_name(hmonitor),
+            idx,
+            (rect.w(), rect.h()),
+        );
+        if monitor_settings.default_layout_idx >= self.user_layouts.len() {
+            monitor_settings.default_layout_idx = self.settings.default_layout_idx;
+        }
+        let monitor_rect = self.platform.get_monitor_work_area(hmonitor);
+        let mut layouts = Vec::new();
+        for layout in &self.user_layouts {
+            let mut layout = layout.resolve(monitor_rect.clone());
+            layout.set_padding(layout.padding().overridden_by(&monitor_settings.padding));
+            layout.update_all(&layout.padding().apply(&self.settings.padding));
+            layouts.push(layout);
+        }
+        if let Some(variant_idx) = &monitor_settings.default_variant_idx {
+            let layout = &mut layouts[monitor_settings.default_layout_idx];
+            if layout.variants().variant_idxs().contains(variant_idx) {
+                layout.set_default_variant_idx(variant_idx);
+            }
+        }
+        self.layouts.insert(hmonitor.0, layouts);
+        self.monitor_settings.insert(hmonitor.0, monitor_settings);
+    }
+
+    /// Moves the workspaces of a monitor that was removed to another monitor,
+    /// placing their windows after the ones already there.
+    fn move_workspaces_to_monitor(&mut self, from: HMONITOR, to: HMONITOR) {
+        let work_area = self.platform.get_monitor_work_area(to);
+        let desktop_ids: Vec<GUID> = self
+            .workspaces
+            .keys()
+            .filter(|(_, hmonitor)| *hmonitor == from.0)
+            .map(|(guid, _)| *guid)
+            .collect();
+        for guid in desktop_ids {
+            let mut workspace = self.workspaces.remove(&(guid, from.0)).unwrap();
+            self.ignored_combinations.remove(&(guid, from.0));
+            workspace.move_resized_layouts(&work_area, &self.settings);
+            let offset = match self.workspaces.get(&(guid, to.0)) {
+                Some(w) => w.managed_window_handles.len(),
+                None => 0,
+            };
+            for h in &workspace.window_handles {
+                if let Some(window_info) = self.window_info.get_mut(h) {
+                    window_info.monitor_handle = to;
+                    window_info.idx += offset;
+                }
+            }
+            match self.workspaces.get_mut(&(guid, to.0)) {
+                Some(target) => {
+                    target
+                        .managed_window_handles
+                        .extend(workspace.managed_window_handles);
+                    target.window_handles.extend(workspace.window_handles);
+                    target.window_weights.extend(workspace.window_weights);
+                    target
+                        .overflowed_windows
+                        .extend(workspace.overflowed_windows);
+                    target.tile_groups.extend(workspace.tile_groups);
+                    // The target keeps its own state where it has any
+                    for (layout_idx, layout) in workspace.resized_layouts {
+                        target.resized_layouts.entry(layout_idx).or_insert(layout);
+                    }
+                    target.monocle |= workspace.monocle;
+                    if target.gap_offset == 0 {
+                        target.gap_offset = workspace.gap_offset;
+                    }
+                    target.gaps_disabled |= workspace.gaps_disabled;
+                }
+                None => {
+                    self.workspaces.insert((guid, to.0), workspace);
+                }
+            }
+        }
+        for window_info in self.window_info.values_mut() {
+            if window_info.monitor_handle == from {
+                window_info.monitor_handle = to;
+            }
+        }
+    }
+
+    /// Resolves the settings of a virtual desktop the first time it is seen,
+    /// ignoring it on every monitor if they say so.
+    fn register_desktop(&mut self, guid: GUID) {
+        if self.desktop_settings.contains_key(&guid) {
+            return;
+        }
+        let idx = self
+            .platform
+            .get_desktop_ids()
+            .iter()
+            .position(|id| *id == guid);
+        let name = self.platform.get_desktop_name(guid);
+        let desktop_settings = self.settings.desktop_settings(guid, name.as_deref(), idx);
+        if desktop_settings.ignored {
+            for monitor_handle in &self.monitor_handles {
+                self.ignored_combinations.insert((guid, monitor_handle.0));
+            }
+        }
+        self.desktop_settings.insert(guid, desktop_settings);
+    }
+
+    /// The settings of the monitor the window is on.
+    fn get_monitor_settings(&self, hwnd: HWND) -> settings::MonitorSettings {
+        let monitor_handle = match self.window_info.get(&hwnd.0) {
+            Some(window_info) => window_info.monitor_handle,
+            None => self.platform.monitor_from_window(hwnd),
+        };
+        match self.monitor_settings.get(&monitor_handle.0) {
+            Some(monitor_settings) => return monitor_settings.to_owned(),
+            None => return self.settings.default_monitor_settings(),
+        }
+    }
+
     fn set_border_to_unfocused(&self, hwnd: HWND) {
-        let _ = windows_api::dwm_set_window_attribute(
+        self.platform.set_border_colour(
             hwnd,
-            DWMWA_BORDER_COLOR,
-            &self.settings.get_unfocused_border_colour() as *const COLORREF
-                as *const core::ffi::c_void,
-            std::mem::size_of_val(&self.settings.get_unfocused_border_colour()) as u32,
+            self.get_monitor_settings(hwnd)
+                .get_unfocused_border_colour(),
         );
     }
 
     fn set_border_to_focused(&self, hwnd: HWND) {
-        let _ = windows_api::dwm_set_window_attribute(
-            hwnd,
-            DWMWA_BORDER_COLOR,
-            &self.settings.focused_border_colour as *const COLORREF as *const core::ffi::c_void,
-            std::mem::size_of_val(&self.settings.focused_border_colour) as u32,
-        );
+        self.platform
+            .set_border_colour(hwnd, self.get_monitor_settings(hwnd).focused_border_colour);
     }
 
     fn initialize_border(&self, hwnd: HWND) {
-        let corner_preference = if self.settings.disable_rounding {
-            DWMWCP_DONOTROUND
+        let corner_preference = if self.get_monitor_settings(hwnd).disable_rounding {
+            platform::CornerPreference::DoNotRound
         } else {
-            DWMWCP_DEFAULT
+            platform::CornerPreference::Default
         };
-        let _ = windows_api::dwm_set_window_attribute(
-            hwnd,
-            DWMWA_WINDOW_CORNER_PREFERENCE,
-            &corner_preference as *const DWM_WINDOW_CORNER_PREFERENCE as *const core::ffi::c_void,
-            std::mem::size_of_val(&corner_preference) as u32,
-        );
+        self.platform.set_corner_preference(hwnd, corner_preference);
         self.set_border_to_unfocused(hwnd);
     }
 
-    fn reset_border(hwnd: HWND) {
-        let _corner = windows_api::dwm_set_window_attribute(
-            hwnd,
-            DWMWA_WINDOW_CORNER_PREFERENCE,
-            &DWMWCP_DEFAULT as *const DWM_WINDOW_CORNER_PREFERENCE as *const core::ffi::c_void,
-            std::mem::size_of_val(&DWMWCP_DEFAULT) as u32,
-        );
-        let _border_colour = windows_api::dwm_set_window_attribute(
-            hwnd,
-            DWMWA_BORDER_COLOR,
-            &COLORREF(DWMWA_COLOR_DEFAULT) as *const COLORREF as *const core::ffi::c_void,
-            std::mem::size_of_val(&COLORREF(DWMWA_COLOR_DEFAULT)) as u32,
-        );
+    fn reset_border(&self, hwnd: HWND) {
+        self.platform
+            .set_corner_preference(hwnd, platform::CornerPreference::Default);
+        self.platform
+            .set_border_colour(hwnd, COLORREF(DWMWA_COLOR_DEFAULT));
     }
 
     fn unfocus_border_with_combination_check(&self, hwnd: HWND) {
@@ -1457,25 +2607,22 @@ impl WindowManager {
         hwnd: HWND,
         filter: &Option<std::collections::HashSet<window_rules::FilterRule>>,
     ) -> Option<window_rules::Rule> {
-        match util::get_window_title(hwnd) {
-            Some(title) => {
-                for window_rule in &self.window_rules.title_window_rules {
-                    if window_rule.regex.is_match(&title) {
-                        match &filter {
-                            Some(f) => {
-                                let filter_for = window_rules::FilterRule::from(&window_rule.rule);
-                                if f.contains(&filter_for) {
-                                    return Some(window_rule.rule.to_owned());
-                                }
+        if let Some(title) = self.platform.get_window_title(hwnd) {
+            for window_rule in &self.window_rules.title_window_rules {
+                if window_rule.regex.is_match(&title) {
+                    match &filter {
+                        Some(f) => {
+                            let filter_for = window_rules::FilterRule::from(&window_rule.rule);
+                            if f.contains(&filter_for) {
+                                return Some(window_rule.rule.to_owned());
                             }
-                            None => return Some(window_rule.rule.to_owned()),
                         }
+                        None => return Some(window_rule.rule.to_owned()),
                     }
                 }
             }
-            None => (),
         }
-        match util::get_exe_name(hwnd) {
+        match self.platform.get_exe_name(hwnd) {
             Some(name) => {
                 for window_rule in &self.window_rules.process_window_rules {
                     if window_rule.regex.is_match(&name) {
@@ -1498,6 +2645,49 @@ impl WindowManager {
         }
     }
 
+    fn get_invisible_border(&self, hwnd: HWND) -> window_rules::InvisibleBorder {
+        let filter = Some(std::collections::HashSet::from([
+            window_rules::FilterRule::InvisibleBorder,
+        ]));
+        match self.get_window_rule(hwnd, &filter) {
+            Some(window_rules::Rule::InvisibleBorder(val)) => return val,
+            _ => return window_rules::InvisibleBorder::default(),
+        }
+    }
+
+    /// The minimum size of a window's tile, excluding its invisible border.
+    /// Windows without a `MinSize` rule can be tiled at any size.
+    fn get_min_size(&mut self, hwnd: HWND) -> (i32, i32) {
+        if let Some(min_size) = self.min_sizes.get(&hwnd.0) {
+            return *min_size;
+        }
+        let filter = Some(std::collections::HashSet::from([
+            window_rules::FilterRule::MinSize,
+        ]));
+        let min_size = match self.get_window_rule(hwnd, &filter) {
+            Some(rule) => match rule {
+                window_rules::Rule::MinSize(window_rules::MinSize::Size { w, h }) => (w, h),
+                window_rules::Rule::MinSize(window_rules::MinSize::FromWindow) => {
+                    match self.platform.get_min_size(hwnd) {
+                        Some((w, h)) => {
+                            let invisible_border = self.get_invisible_border(hwnd);
+                            (
+                                w - invisible_border.left - invisible_border.right,
+                                h - invisible_border.top - invisible_border.bottom,
+                            )
+                        }
+                        // Not remembered, so that it is asked for again
+                        None => return (0, 0),
+                    }
+                }
+                _ => (0, 0),
+            },
+            None => (0, 0),
+        };
+        self.min_sizes.insert(hwnd.0, min_size);
+        return min_size;
+    }
+
     fn set_visible_window_position(
         &self,
         hwnd: HWND,
@@ -1505,41 +2695,110 @@ impl WindowManager {
         y: i32,
         w: i32,
         h: i32,
-    ) -> Result<()> {
-        let filter = Some(std::collections::HashSet::from([
-            window_rules::FilterRule::InvisibleBorder,
-        ]));
-        let invisible_border = match self.get_window_rule(hwnd, &filter) {
-            Some(rule) => match rule {
-                window_rules::Rule::InvisibleBorder(val) => val,
-                _ => window_rules::InvisibleBorder::default(),
-            },
-            None => window_rules::InvisibleBorder::default(),
-        };
-        return windows_api::set_window_pos(
+    ) -> Result<(), platform::Error> {
+        let invisible_border = self.get_invisible_border(hwnd);
+        return self.platform.set_window_pos(
             hwnd,
-            None,
             x - invisible_border.left,
             y - invisible_border.top,
             w + invisible_border.left + invisible_border.right,
             h + invisible_border.top + invisible_border.bottom,
-            SWP_NOZORDER,
         );
     }
 
-    fn center_window(&self, hwnd: HWND) {
-        let window_info = window_info!(self, hwnd);
+    fn matches_scratchpad(&self, hwnd: HWND, idx: usize) -> bool {
+        let scratchpad = &self.settings.scratchpads[idx];
+        let text = match scratchpad.match_type {
+            window_rules::MatchType::Title => self.platform.get_window_title(hwnd),
+            window_rules::MatchType::Process => self.platform.get_exe_name(hwnd),
+        };
+        match text {
+            Some(val) => return scratchpad.regex.is_match(&val),
+            None => return false,
+        }
+    }
+
+    /// An open window that matches a scratchpad and is not the window of
+    /// another scratchpad.
+    fn find_scratchpad_window(&self, idx: usize) -> Option<HWND> {
+        return self.platform.enum_windows().into_iter().find(|hwnd| {
+            self.platform.is_overlapped_window(*hwnd)
+                && !self.scratchpad_windows.values().any(|h| h == hwnd)
+                && self.matches_scratchpad(*hwnd, idx)
+        });
+    }
+
+    fn get_pending_scratchpad(&self, hwnd: HWND) -> Option<usize> {
+        return self
+            .pending_scratchpads
+            .iter()
+            .copied()
+            .find(|idx| self.matches_scratchpad(hwnd, *idx));
+    }
+
+    /// Makes a window the window of a scratchpad, which floats from then on.
+    fn claim_scratchpad_window(&mut self, idx: usize, hwnd: HWND) {
+        self.scratchpad_windows.insert(idx, hwnd);
+        self.pending_scratchpads.remove(&idx);
+        if !self.ignored_windows.insert(hwnd.0) {
+            return;
+        }
         let WindowInfo {
             desktop_id,
             monitor_handle,
-            ..
-        } = window_info.to_owned();
-        let workspace = self
-            .workspaces
-            .get(&(desktop_id, monitor_handle.0))
-            .unwrap();
-        let monitor_rect =
-            self.layouts.get(&monitor_handle.0).unwrap()[workspace.layout_idx].monitor_rect();
+            restored,
+            idx: window_idx,
+        } = window_info_owned!(self, hwnd);
+        if restored
+            && self
+                .unmanage_hwnd(desktop_id, monitor_handle, window_idx, false)
+                .is_some()
+        {
+            self.leave_tile_group(desktop_id, monitor_handle, hwnd);
+            self.update_workspace(desktop_id, monitor_handle);
+        }
+    }
+
+    /// Shows the window of a scratchpad on the desktop and monitor of the
+    /// foreground window, and focuses it.
+    fn show_scratchpad(&mut self, idx: usize, hwnd: HWND) {
+        let foreground_window = self.platform.get_foreground_window();
+        if let Some(guid) = self.platform.get_window_desktop_id(foreground_window) {
+            if self.platform.get_window_desktop_id(hwnd) != Some(guid)
+                && self.platform.move_window_to_desktop(hwnd, guid).is_ok()
+                && self.window_info.contains_key(&hwnd.0)
+            {
+                self.move_hwnd_to_desktop(hwnd, guid);
+            }
+        }
+        let hmonitor = match self.platform.monitor_from_window(foreground_window) {
+            h if !h.is_invalid() => h,
+            _ => match self.monitor_handles.first() {
+                Some(h) => *h,
+                None => return,
+            },
+        };
+        let _ = self
+            .platform
+            .show_window(hwnd, platform::ShowWindowCommand::Show);
+        match &self.settings.scratchpads[idx].position {
+            Some(window_rules::Position { x, y, w, h }) => {
+                let work_area = self.platform.get_monitor_work_area(hmonitor);
+                let _ = self.set_visible_window_position(
+                    hwnd,
+                    work_area.x() + x,
+                    work_area.y() + y,
+                    *w,
+                    *h,
+                );
+            }
+            None => self.center_window(hwnd, hmonitor),
+        }
+        let _ = self.platform.set_foreground_window(hwnd);
+    }
+
+    fn center_window(&self, hwnd: HWND, hmonitor: HMONITOR) {
+        let monitor_rect = self.platform.get_monitor_work_area(hmonitor);
         let w = ((monitor_rect.w() as f64) * self.settings.floating_window_default_w_ratio).round()
             as i32;
         let h = ((monitor_rect.h() as f64) * self.settings.floating_window_default_h_ratio).round()
@@ -1559,22 +2818,116 @@ impl WindowManager {
                 window_info.idx = workspace.managed_window_handles.len();
             }
             None => {
+                let (layout_idx, variant_idx) = default_layout!(self, guid, hmonitor);
                 self.workspaces.insert(
                     (guid, hmonitor.0),
-                    Workspace::new(
-                        hwnd,
-                        self.settings.default_layout_idx,
-                        self.layouts.get(&window_info.monitor_handle.0).unwrap()
-                            [self.settings.default_layout_idx]
-                            .default_variant_idx()
-                            .to_owned(),
-                    ),
+                    Workspace::new(hwnd, layout_idx, variant_idx),
                 );
                 window_info.idx = 0;
             }
         }
     }
 
+    /// Marks a tile group member as no longer tiled before minimizing it, so
+    /// that minimizing it does not unmanage its tile.
+    fn hide_tile_group_member(&mut self, hwnd: HWND) {
+        match self.window_info.get_mut(&hwnd.0) {
+            Some(window_info) => window_info.restored = false,
+            None => return,
+        }
+        let _ = self
+            .platform
+            .show_window(hwnd, platform::ShowWindowCommand::Minimize);
+    }
+
+    /// Marks a tile group member as tiled at `idx` before restoring it, so that
+    /// restoring it does not give it a tile of its own.
+    fn show_tile_group_member(&mut self, hwnd: HWND, idx: usize) {
+        match self.window_info.get_mut(&hwnd.0) {
+            Some(window_info) => {
+                window_info.restored = true;
+                window_info.idx = idx;
+            }
+            None => return,
+        }
+        let _ = self
+            .platform
+            .show_window(hwnd, platform::ShowWindowCommand::Restore);
+    }
+
+    /// Shows a hidden tile group member that has been restored (for example
+    /// from the taskbar) in its group's tile instead of a tile of its own.
+    /// Returns whether the window is in such a group.
+    fn activate_tile_group_member(&mut self, guid: GUID, hmonitor: HMONITOR, hwnd: HWND) -> bool {
+        let workspace = match self.workspaces.get_mut(&(guid, hmonitor.0)) {
+            Some(w) => w,
+            None => return false,
+        };
+        let shown = match workspace
+            .tile_groups
+            .iter()
+            .find(|(shown, members)| **shown != hwnd.0 && members.contains(&hwnd))
+        {
+            Some((shown, _)) => HWND(*shown),
+            None => return false,
+        };
+        let members = workspace.tile_groups.remove(&shown.0).unwrap();
+        workspace.tile_groups.insert(hwnd.0, members);
+        // If the shown member is not tiled (because it was minimized), the
+        // window takes over the group and is tiled as usual
+        let idx = match workspace
+            .managed_window_handles
+            .iter()
+            .position(|h| *h == shown)
+        {
+            Some(idx) => idx,
+            None => return false,
+        };
+        workspace.managed_window_handles[idx] = hwnd;
+        self.window_info.get_mut(&hwnd.0).unwrap().idx = idx;
+        self.hide_tile_group_member(shown);
+        return true;
+    }
+
+    /// Removes a window from its tile group. If it was the member shown in the
+    /// group's tile, the next member is restored, which tiles it in the same
+    /// place.
+    fn leave_tile_group(&mut self, guid: GUID, hmonitor: HMONITOR, hwnd: HWND) {
+        let workspace = match self.workspaces.get_mut(&(guid, hmonitor.0)) {
+            Some(w) => w,
+            None => return,
+        };
+        let shown = match workspace
+            .tile_groups
+            .iter()
+            .find(|(_, members)| members.contains(&hwnd))
+        {
+            Some((shown, _)) => HWND(*shown),
+            None => return,
+        };
+        let mut members = workspace.tile_groups.remove(&shown.0).unwrap();
+        let position = members.iter().position(|h| *h == hwnd).unwrap();
+        members.remove(position);
+        if shown != hwnd {
+            if members.len() > 1 {
+                workspace.tile_groups.insert(shown.0, members);
+            }
+            return;
+        }
+        let next = members[position % members.len()];
+        if members.len() > 1 {
+            workspace.tile_groups.insert(next.0, members);
+        }
+        let idx = self.window_info.get(&hwnd.0).unwrap().idx;
+        match self.window_info.get_mut(&next.0) {
+            Some(window_info) => window_info.idx = idx,
+            None => return,
+        }
+        let _ = self
+            .platform
+            .show_window(next, platform::ShowWindowCommand::Restore);
+    }
+
     fn remove_hwnd_from_workspace(&mut self, hwnd: HWND) {
         let window_info = window_info!(self, hwnd);
         let WindowInfo {
@@ -1583,8 +2936,10 @@ impl WindowManager {
             restored,
             idx,
         } = window_info.to_owned();
+        self.leave_tile_group(desktop_id, monitor_handle, hwnd);
         if let Some(workspace) = self.workspaces.get_mut(&(desktop_id, monitor_handle.0)) {
             workspace.window_handles.remove(&hwnd.0);
+            workspace.window_weights.remove(&hwnd.0);
             if restored {
                 self.unmanage_hwnd(desktop_id, monitor_handle, idx, false);
             }
@@ -1601,8 +2956,9 @@ impl WindowManager {
                 window_info.idx = idx;
                 if window_info.restored {
                     workspace.managed_window_handles.insert(idx, hwnd);
-                    for h in workspace.window_handles.to_owned() {
-                        if windows_api::is_window(Some(HWND(h))).as_bool() {
+                    let window_handles = workspace.window_handles.clone();
+                    for h in window_handles {
+                        if self.platform.is_window(HWND(h)) {
                             let info = self.window_info.get_mut(&h).unwrap();
                             if h != hwnd.0 && info.idx >= idx {
                                 info.idx += 1;
@@ -1615,16 +2971,10 @@ impl WindowManager {
             }
             None => {
                 if window_info.restored {
+                    let (layout_idx, variant_idx) = default_layout!(self, guid, hmonitor);
                     self.workspaces.insert(
                         (guid, hmonitor.0),
-                        Workspace::insert_into_new(
-                            hwnd,
-                            self.settings.default_layout_idx,
-                            self.layouts.get(&window_info.monitor_handle.0).unwrap()
-                                [self.settings.default_layout_idx]
-                                .default_variant_idx()
-                                .to_owned(),
-                        ),
+                        Workspace::insert_into_new(hwnd, layout_idx, variant_idx),
                     );
                 }
                 window_info.idx = 0;
@@ -1648,16 +2998,15 @@ impl WindowManager {
         idx: usize,
         remove_from_workspace: bool,
     ) -> Option<HWND> {
-        let workspace = match self.workspaces.get_mut(&(guid, hmonitor.0)) {
-            Some(val) => val,
-            None => return None,
-        };
+        let workspace = self.workspaces.get_mut(&(guid, hmonitor.0))?;
         let hwnd = workspace.managed_window_handles.remove(idx);
         if remove_from_workspace {
             workspace.window_handles.remove(&hwnd.0);
+            workspace.window_weights.remove(&hwnd.0);
         }
-        for h in workspace.window_handles.to_owned() {
-            if windows_api::is_window(Some(HWND(h))).as_bool() {
+        let window_handles = workspace.window_handles.clone();
+        for h in window_handles {
+            if self.platform.is_window(HWND(h)) {
                 let info = self.window_info.get_mut(&h).unwrap();
                 if info.idx > idx {
                     info.idx -= 1;
@@ -1672,6 +3021,7 @@ impl WindowManager {
     fn remove_hwnd(&mut self, hwnd: HWND) {
         self.remove_hwnd_from_workspace(hwnd);
         self.window_info.remove(&hwnd.0);
+        self.min_sizes.remove(&hwnd.0);
         if self.foreground_window == Some(hwnd) {
             self.foreground_window = None;
         }
@@ -1685,9 +3035,8 @@ impl WindowManager {
 
     pub fn exit(self) {
         for h in self.window_info.keys() {
-            Self::reset_border(HWND(*h));
+            self.reset_border(HWND(*h));
         }
-        let _unhook_win_event = windows_api::unhook_win_event(self.event_hook);
-        windows_api::co_uninitialize();
+        self.platform.uninitialize();
     }
 }
@@ -33,3 +33,29 @@ macro_rules! window_info_owned {
         }
     };
 }
+
+/// The layout and variant that a new workspace on a desktop and monitor starts
+/// with. This only reads the settings and layouts, so it can be used while a
+/// window or workspace is borrowed.
+macro_rules! default_layout {
+    ($wm:expr, $guid:expr, $hmonitor:expr) => {{
+        let layouts = $wm.layouts.get(&$hmonitor.0).unwrap();
+        let desktop_settings = $wm.desktop_settings.get(&$guid);
+        let layout_idx = match desktop_settings.and_then(|s| s.default_layout_idx) {
+            Some(idx) if idx < layouts.len() => idx,
+            _ => {
+                $wm.monitor_settings
+                    .get(&$hmonitor.0)
+                    .unwrap()
+                    .default_layout_idx
+            }
+        };
+        let variant_idx = match desktop_settings.and_then(|s| s.default_variant_idx.as_ref()) {
+            Some(idx) if layouts[layout_idx].variants().variant_idxs().contains(idx) => {
+                idx.to_owned()
+            }
+            _ => layouts[layout_idx].default_variant_idx().to_owned(),
+        };
+        (layout_idx, variant_idx)
+    }};
+}
@@ -1,105 +1,182 @@
-use crate::wm;
-use windows::Win32::{Foundation::*, UI::WindowsAndMessaging::*};
+use crate::{
+    commands::Command,
+    platform::{HWND, WM_HOTKEY},
+    wm,
+};
+#[cfg(windows)]
+use windows::Win32::UI::WindowsAndMessaging::MSG;
 
+#[cfg(windows)]
 pub fn handle_message(msg: MSG, wm: &mut wm::WindowManager) {
-    match msg.message {
+    dispatch(msg.message, msg.wParam.0, wm);
+}
+
+pub fn dispatch(message: u32, wparam: usize, wm: &mut wm::WindowManager) {
+    match message {
         wm::messages::messages::WINDOW_CREATED => {
-            wm.manage_window(HWND(msg.wParam.0 as *mut core::ffi::c_void));
+            wm.manage_window(HWND(wparam as *mut core::ffi::c_void));
         }
         wm::messages::messages::WINDOW_RESTORED
             if wm
                 .window_info()
-                .contains_key(&(msg.wParam.0 as *mut core::ffi::c_void)) =>
+                .contains_key(&(wparam as *mut core::ffi::c_void)) =>
         {
-            wm.manage_window(HWND(msg.wParam.0 as *mut core::ffi::c_void));
+            wm.manage_window(HWND(wparam as *mut core::ffi::c_void));
         }
         wm::messages::messages::WINDOW_DESTROYED => {
-            wm.window_destroyed(HWND(msg.wParam.0 as *mut core::ffi::c_void));
+            wm.window_destroyed(HWND(wparam as *mut core::ffi::c_void));
         }
         wm::messages::messages::STOP_MANAGING_WINDOW => {
-            wm.stop_managing_window(HWND(msg.wParam.0 as *mut core::ffi::c_void));
+            wm.stop_managing_window(HWND(wparam as *mut core::ffi::c_void));
         }
         wm::messages::messages::WINDOW_CLOAKED => {
-            wm.window_cloaked(HWND(msg.wParam.0 as *mut core::ffi::c_void));
+            wm.window_cloaked(HWND(wparam as *mut core::ffi::c_void));
         }
         wm::messages::messages::WINDOW_UNCLOAKED => {
-            wm.window_uncloaked(HWND(msg.wParam.0 as *mut core::ffi::c_void));
+            wm.window_uncloaked(HWND(wparam as *mut core::ffi::c_void));
         }
         wm::messages::messages::FOREGROUND_WINDOW_CHANGED => {
-            wm.foreground_window_changed(HWND(msg.wParam.0 as *mut core::ffi::c_void), false);
+            wm.foreground_window_changed(HWND(wparam as *mut core::ffi::c_void), false);
         }
         wm::messages::messages::WINDOW_MOVE_FINISHED => {
-            wm.window_move_finished(HWND(msg.wParam.0 as *mut core::ffi::c_void));
+            wm.window_move_finished(HWND(wparam as *mut core::ffi::c_void));
         }
         wm::messages::messages::REQUEST_RESTART => {
             wm.restart_himewm();
         }
-        WM_HOTKEY => match msg.wParam.0 {
-            wm::messages::hotkey_identifiers::FOCUS_PREVIOUS => {
-                wm.cycle_focus(wm::CycleDirection::Previous);
-            }
-            wm::messages::hotkey_identifiers::FOCUS_NEXT => {
-                wm.cycle_focus(wm::CycleDirection::Next);
-            }
-            wm::messages::hotkey_identifiers::SWAP_PREVIOUS => {
-                wm.cycle_swap(wm::CycleDirection::Previous);
-            }
-            wm::messages::hotkey_identifiers::SWAP_NEXT => {
-                wm.cycle_swap(wm::CycleDirection::Next);
-            }
-            wm::messages::hotkey_identifiers::LAYOUT_PREVIOUS => {
-                wm.cycle_layout(wm::CycleDirection::Previous);
-            }
-            wm::messages::hotkey_identifiers::LAYOUT_NEXT => {
-                wm.cycle_layout(wm::CycleDirection::Next);
-            }
-            wm::messages::hotkey_identifiers::FOCUS_PREVIOUS_MONITOR => {
-                wm.cycle_focused_monitor(wm::CycleDirection::Previous);
-            }
-            wm::messages::hotkey_identifiers::FOCUS_NEXT_MONITOR => {
-                wm.cycle_focused_monitor(wm::CycleDirection::Next);
-            }
-            wm::messages::hotkey_identifiers::MOVE_TO_PREVIOUS_MONITOR => {
-                wm.cycle_assigned_monitor(wm::CycleDirection::Previous);
-            }
-            wm::messages::hotkey_identifiers::MOVE_TO_NEXT_MONITOR => {
-                wm.cycle_assigned_monitor(wm::CycleDirection::Next);
-            }
-            wm::messages::hotkey_identifiers::GRAB_WINDOW => {
-                wm.grab_window();
-            }
-            wm::messages::hotkey_identifiers::RELEASE_WINDOW => {
-                wm.release_window();
-            }
-            wm::messages::hotkey_identifiers::TOGGLE_WINDOW => {
-                wm.toggle_window();
-            }
-            wm::messages::hotkey_identifiers::TOGGLE_WORKSPACE => {
-                wm.toggle_workspace();
-            }
-            wm::messages::hotkey_identifiers::REFRESH_WORKSPACE => {
-                wm.refresh_workspace();
-            }
-            wm::messages::hotkey_identifiers::REQUEST_RESTART => {
-                wm.restart_himewm();
-            }
-            _ => {
-                let direction = if msg.wParam.0 % 2 == 0 {
-                    wm::CycleDirection::Previous
-                } else {
-                    wm::CycleDirection::Next
-                };
-                let idx = match direction {
-                    wm::CycleDirection::Previous => {
-                        (msg.wParam.0 - wm::messages::hotkey_identifiers::VARIANT_START) / 2
-                    }
-                    wm::CycleDirection::Next => {
-                        (msg.wParam.0 - wm::messages::hotkey_identifiers::VARIANT_START - 1) / 2
-                    }
-                };
-                wm.cycle_variant(direction, idx);
+        wm::messages::messages::DISPLAY_CHANGED => {
+            wm.display_changed();
+        }
+        WM_HOTKEY => {
+            if let Some(command) = wm.commands().get(wparam).cloned() {
+                run_command(&command, wm);
             }
-        },
+        }
         _ => (),
     }
 }
+
+pub fn run_command(command: &Command, wm: &mut wm::WindowManager) {
+    match command {
+        Command::Focus { direction } => {
+            wm.cycle_focus(*direction);
+        }
+        Command::Swap { direction } => {
+            wm.cycle_swap(*direction);
+        }
+        Command::FocusDirection { direction } => {
+            wm.focus_direction(direction.to_owned());
+        }
+        Command::SwapDirection { direction } => {
+            wm.swap_direction(direction.to_owned());
+        }
+        Command::CycleLayout { direction } => {
+            wm.cycle_layout(*direction);
+        }
+        Command::SetLayout { name } => {
+            wm.set_layout(name);
+        }
+        Command::CycleVariant { index, direction } => {
+            wm.cycle_variant(*direction, *index);
+        }
+        Command::SetVariant { index } => {
+            wm.set_variant(index);
+        }
+        Command::CycleFocusedMonitor { direction } => {
+            wm.cycle_focused_monitor(*direction);
+        }
+        Command::FocusMonitor { index } => {
+            wm.focus_monitor(*index);
+        }
+        Command::MoveToMonitor { direction } => {
+            wm.cycle_assigned_monitor(*direction);
+        }
+        Command::GrabWindow => {
+            wm.grab_window();
+        }
+        Command::ReleaseWindow => {
+            wm.release_window();
+        }
+        Command::ToggleWindow => {
+            wm.toggle_window();
+        }
+        Command::ToggleWorkspace => {
+            wm.toggle_workspace();
+        }
+        Command::RefreshWorkspace => {
+            wm.refresh_workspace();
+        }
+        Command::RestartHimewm => {
+            wm.restart_himewm();
+        }
+        Command::Grow { direction } => {
+            wm.resize_focused_window(direction.to_owned(), true);
+        }
+        Command::Shrink { direction } => {
+            wm.resize_focused_window(direction.to_owned(), false);
+        }
+        Command::IncreaseWeight => {
+            wm.change_focused_window_weight(true);
+        }
+        Command::DecreaseWeight => {
+            wm.change_focused_window_weight(false);
+        }
+        Command::ResetWeights => {
+            wm.reset_weights();
+        }
+        Command::ToggleMonocle => {
+            wm.toggle_monocle();
+        }
+        Command::JoinGroup { direction } => {
+            wm.join_tile_group(*direction);
+        }
+        Command::SplitGroup => {
+            wm.split_tile_group();
+        }
+        Command::CycleGroup { direction } => {
+            wm.cycle_tile_group(*direction);
+        }
+        Command::IncreaseGaps => {
+            wm.change_gaps(true);
+        }
+        Command::DecreaseGaps => {
+            wm.change_gaps(false);
+        }
+        Command::ToggleGaps => {
+            wm.toggle_gaps();
+        }
+        Command::PromoteToMaster => {
+            wm.promote_to_master();
+        }
+        Command::IncreaseMasterCount => {
+            wm.change_master_count(true);
+        }
+        Command::DecreaseMasterCount => {
+            wm.change_master_count(false);
+        }
+        Command::GrowMaster => {
+            wm.resize_master(true);
+        }
+        Command::ShrinkMaster => {
+            wm.resize_master(false);
+        }
+        Command::Rotate { direction } => {
+            wm.rotate_windows(*direction);
+        }
+        Command::MoveToIndex { index } => {
+            wm.move_to_index(*index);
+        }
+        Command::MoveToDesktop { direction, follow } => {
+            wm.cycle_window_desktop(*direction, *follow);
+        }
+        Command::MoveToDesktopIndex { index, follow } => {
+            wm.move_to_desktop(*index, *follow);
+        }
+        Command::ToggleScratchpad { name } => {
+            wm.toggle_scratchpad(name);
+        }
+        Command::Spawn { program, args } => {
+            wm.spawn(program, args);
+        }
+    }
+}
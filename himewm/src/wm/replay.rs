@@ -0,0 +1,367 @@
+use crate::{
+    commands, directories,
+    platform::{
+        self,
+        fake::{FakeMonitor, FakePlatform, FakeWindow},
+        GUID, HMONITOR, HWND, WM_HOTKEY,
+    },
+    settings, window_rules,
+    wm::{self, message_handler, messages},
+};
+use himewm_layout::{layout::Layout, position::Position};
+use serde::{Deserialize, Serialize};
+
+mod guid_string {
+    use crate::platform::GUID;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(guid: &GUID, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("{:?}", guid))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<GUID, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        return GUID::try_from(s.as_str()).map_err(serde::de::Error::custom);
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct MonitorState {
+    pub hmonitor: usize,
+    pub work_area: Position,
+    /// Missing from older recordings, where it is taken to be the work area.
+    #[serde(default)]
+    pub rect: Option<Position>,
+    pub dpi: u32,
+    #[serde(default)]
+    pub device_name: String,
+}
+
+/// The state of a window as seen by the platform when a message was handled.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct WindowState {
+    pub hwnd: usize,
+    pub exists: bool,
+    #[serde(with = "guid_string")]
+    pub desktop_id: GUID,
+    pub rect: Position,
+    pub visible: bool,
+    pub restored: bool,
+    pub has_sizebox: bool,
+    pub overlapped: bool,
+    pub title: String,
+    pub exe_name: String,
+}
+
+impl WindowState {
+    fn capture(platform: &dyn platform::Platform, hwnd: HWND) -> Self {
+        if !platform.is_window(hwnd) {
+            return Self {
+                hwnd: hwnd.0 as usize,
+                exists: false,
+                desktop_id: GUID::zeroed(),
+                rect: Position::new(0, 0, 0, 0),
+                visible: false,
+                restored: false,
+                has_sizebox: false,
+                overlapped: false,
+                title: String::new(),
+                exe_name: String::new(),
+            };
+        }
+        return Self {
+            hwnd: hwnd.0 as usize,
+            exists: true,
+            desktop_id: platform
+                .get_window_desktop_id(hwnd)
+                .unwrap_or(GUID::zeroed()),
+            rect: platform
+                .get_window_rect(hwnd)
+                .unwrap_or(Position::new(0, 0, 0, 0)),
+            visible: platform.is_window_visible(hwnd),
+            restored: platform.is_restored(hwnd),
+            has_sizebox: platform.has_sizebox(hwnd),
+            overlapped: platform.is_overlapped_window(hwnd),
+            title: platform.get_window_title(hwnd).unwrap_or_default(),
+            exe_name: platform.get_exe_name(hwnd).unwrap_or_default(),
+        };
+    }
+
+    fn apply(&self, platform: &FakePlatform) {
+        let hwnd = HWND(self.hwnd as *mut core::ffi::c_void);
+        if !self.exists {
+            platform.remove_window(hwnd);
+            return;
+        }
+        let mut window = FakeWindow::new(self.desktop_id, self.rect.to_owned())
+            .with_title(&self.title)
+            .with_exe_name(&self.exe_name);
+        window.visible = self.visible;
+        window.has_sizebox = self.has_sizebox;
+        window.overlapped = self.overlapped;
+        window.minimized = !self.restored && self.visible && self.has_sizebox;
+        if let Some(previous) = platform.window(hwnd) {
+            window.border_colour = previous.border_colour;
+            window.corner_preference = previous.corner_preference;
+        }
+        platform.insert_window(hwnd, window);
+    }
+}
+
+/// A message handled by the window manager, along with the state of the
+/// windows it is likely to query while handling it.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Event {
+    pub message: u32,
+    pub wparam: usize,
+    pub foreground_window: usize,
+    pub windows: Vec<WindowState>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct WindowInfoSnapshot {
+    pub hwnd: usize,
+    #[serde(with = "guid_string")]
+    pub desktop_id: GUID,
+    pub monitor_handle: usize,
+    pub restored: bool,
+    pub idx: usize,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct WorkspaceSnapshot {
+    #[serde(with = "guid_string")]
+    pub desktop_id: GUID,
+    pub monitor_handle: usize,
+    pub layout_idx: usize,
+    pub variant_idx: Vec<usize>,
+    pub window_handles: Vec<usize>,
+    pub managed_window_handles: Vec<usize>,
+}
+
+/// The parts of the window manager's state that replayed recordings are
+/// checked against. Everything is sorted so that snapshots can be compared.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Snapshot {
+    pub foreground_window: Option<usize>,
+    pub window_info: Vec<WindowInfoSnapshot>,
+    pub workspaces: Vec<WorkspaceSnapshot>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Recording {
+    pub monitors: Vec<MonitorState>,
+    pub windows: Vec<WindowState>,
+    pub foreground_window: usize,
+    pub events: Vec<Event>,
+    pub snapshot: Option<Snapshot>,
+    /// The command of each hotkey, so that recorded hotkeys run the same
+    /// commands when they are replayed.
+    #[serde(default)]
+    pub commands: Vec<commands::Command>,
+}
+
+impl Recording {
+    pub fn from_file(path: &std::path::Path) -> Result<Self, String> {
+        let byte_vector = std::fs::read(path).map_err(|e| e.to_string())?;
+        return serde_json::from_slice(byte_vector.as_slice()).map_err(|e| e.to_string());
+    }
+}
+
+fn is_recorded(message: u32) -> bool {
+    return message == WM_HOTKEY
+        || (messages::messages::WINDOW_CREATED..=messages::messages::REQUEST_RESTART)
+            .contains(&message);
+}
+
+/// Records the messages handled by a window manager so that they can be
+/// replayed over a `FakePlatform`.
+pub struct Recorder {
+    path: std::path::PathBuf,
+    recording: Recording,
+}
+
+impl Recorder {
+    /// Captures the monitors and windows that the window manager will see
+    /// when it is initialized, so this should be created before calling
+    /// `WindowManager::initialize`.
+    pub fn new(path: std::path::PathBuf, platform: &dyn platform::Platform) -> Self {
+        let monitors = platform
+            .enum_display_monitors()
+            .into_iter()
+            .map(|hmonitor| MonitorState {
+                hmonitor: hmonitor.0 as usize,
+                work_area: platform.get_monitor_work_area(hmonitor),
+                rect: Some(platform.get_monitor_rect(hmonitor)),
+                dpi: platform.get_dpi_for_monitor(hmonitor),
+                device_name: platform.get_monitor_device_name(hmonitor),
+            })
+            .collect();
+        let windows = platform
+            .enum_windows()
+            .into_iter()
+            .map(|hwnd| WindowState::capture(platform, hwnd))
+            .collect();
+        return Self {
+            path,
+            recording: Recording {
+                monitors,
+                windows,
+                foreground_window: platform.get_foreground_window().0 as usize,
+                events: Vec::new(),
+                snapshot: None,
+                commands: Vec::new(),
+            },
+        };
+    }
+
+    /// Creates a recorder that saves to the recordings directory, named after
+    /// the time that recording started.
+    pub fn in_recordings_dir(platform: &dyn platform::Platform) -> Self {
+        let secs = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let path = directories::Directories::new()
+            .recordings_dir
+            .join(format!("{secs}.json"));
+        return Self::new(path, platform);
+    }
+
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    pub fn recording(&self) -> &Recording {
+        &self.recording
+    }
+
+    /// Records a message if it is one that `message_handler::dispatch`
+    /// handles. This should be called before the message is handled.
+    pub fn record(&mut self, message: u32, wparam: usize, platform: &dyn platform::Platform) {
+        if !is_recorded(message) {
+            return;
+        }
+        let foreground_window = platform.get_foreground_window();
+        let mut windows = Vec::new();
+        if message != WM_HOTKEY && message != messages::messages::REQUEST_RESTART {
+            windows.push(WindowState::capture(
+                platform,
+                HWND(wparam as *mut core::ffi::c_void),
+            ));
+        }
+        if !foreground_window.is_invalid() && foreground_window.0 as usize != wparam {
+            windows.push(WindowState::capture(platform, foreground_window));
+        }
+        self.recording.events.push(Event {
+            message,
+            wparam,
+            foreground_window: foreground_window.0 as usize,
+            windows,
+        });
+    }
+
+    /// Writes the recording along with a snapshot of the window manager's
+    /// current state.
+    pub fn save(mut self, wm: &wm::WindowManager) -> std::io::Result<()> {
+        self.recording.snapshot = Some(wm.snapshot());
+        self.recording.commands = wm.commands().to_owned();
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(&self.recording)?;
+        return std::fs::write(&self.path, contents);
+    }
+}
+
+/// Replays a recording over a `FakePlatform`, returning the window manager
+/// after every event has been handled.
+///
+/// Messages posted by the fake platform while replaying are discarded, since
+/// the messages that Windows posted in response are part of the recording.
+pub fn replay(
+    recording: &Recording,
+    settings: settings::Settings,
+    window_rules: window_rules::WindowRules,
+    layouts: Vec<Layout>,
+) -> wm::WindowManager {
+    let platform = FakePlatform::new();
+    for monitor in &recording.monitors {
+        platform.insert_monitor(
+            HMONITOR(monitor.hmonitor as *mut core::ffi::c_void),
+            FakeMonitor {
+                work_area: monitor.work_area.to_owned(),
+                rect: match &monitor.rect {
+                    Some(rect) => rect.to_owned(),
+                    None => monitor.work_area.to_owned(),
+                },
+                dpi: monitor.dpi,
+                device_name: monitor.device_name.to_owned(),
+            },
+        );
+    }
+    for window in &recording.windows {
+        window.apply(&platform);
+    }
+    platform.set_foreground(HWND(recording.foreground_window as *mut core::ffi::c_void));
+    let mut wm = wm::WindowManager::new(settings, window_rules, Box::new(platform.clone()));
+    wm.set_commands(recording.commands.to_owned());
+    wm.initialize(layouts);
+    platform.take_messages();
+    for event in &recording.events {
+        for window in &event.windows {
+            window.apply(&platform);
+        }
+        platform.set_foreground(HWND(event.foreground_window as *mut core::ffi::c_void));
+        message_handler::dispatch(event.message, event.wparam, &mut wm);
+        platform.take_messages();
+    }
+    return wm;
+}
+
+impl wm::WindowManager {
+    pub fn snapshot(&self) -> Snapshot {
+        let mut window_info: Vec<WindowInfoSnapshot> = self
+            .window_info
+            .iter()
+            .map(|(hwnd, info)| WindowInfoSnapshot {
+                hwnd: *hwnd as usize,
+                desktop_id: info.desktop_id,
+                monitor_handle: info.monitor_handle.0 as usize,
+                restored: info.restored,
+                idx: info.idx,
+            })
+            .collect();
+        window_info.sort_by_key(|w| w.hwnd);
+        let mut workspaces: Vec<WorkspaceSnapshot> = self
+            .workspaces
+            .iter()
+            .map(|((desktop_id, hmonitor), workspace)| {
+                let mut window_handles: Vec<usize> = workspace
+                    .window_handles
+                    .iter()
+                    .map(|h| *h as usize)
+                    .collect();
+                window_handles.sort();
+                WorkspaceSnapshot {
+                    desktop_id: *desktop_id,
+                    monitor_handle: *hmonitor as usize,
+                    layout_idx: workspace.layout_idx,
+                    variant_idx: workspace.variant_idx.to_owned(),
+                    window_handles,
+                    managed_window_handles: workspace
+                        .managed_window_handles
+                        .iter()
+                        .map(|h| h.0 as usize)
+                        .collect(),
+                }
+            })
+            .collect();
+        workspaces.sort_by_key(|w| (w.desktop_id.to_u128(), w.monitor_handle));
+        return Snapshot {
+            foreground_window: self.foreground_window.map(|h| h.0 as usize),
+            window_info,
+            workspaces,
+        };
+    }
+}
@@ -0,0 +1,142 @@
+use himewm::{
+    commands::{Command, UserKeybind, UserKeybinds},
+    wm::CycleDirection,
+};
+
+#[test]
+fn keybinds_are_parsed_into_commands() {
+    let user_keybinds: UserKeybinds = serde_json::from_value(serde_json::json!([
+        {"keys": "alt shift j", "command": "swap", "args": {"direction": "Previous"}},
+        {"keys": "alt p", "command": "grab_window"},
+        {"keys": "alt ctrl t", "command": "set_layout", "args": {"name": "tall"}},
+        {"keys": "alt 1", "command": "focus_monitor", "args": {"index": 0}},
+        {"keys": "alt shift 2", "command": "move_to_desktop_index", "args": {"index": 1}},
+        {"keys": "alt enter", "command": "spawn", "args": {"program": "wt.exe", "args": ["-w", "0"]}}
+    ]))
+    .unwrap();
+    assert_eq!(
+        user_keybinds
+            .keybinds
+            .iter()
+            .map(|keybind| keybind.command.to_owned())
+            .collect::<Vec<Command>>(),
+        vec![
+            Command::Swap {
+                direction: CycleDirection::Previous
+            },
+            Command::GrabWindow,
+            Command::SetLayout {
+                name: String::from("tall")
+            },
+            Command::FocusMonitor { index: 0 },
+            Command::MoveToDesktopIndex {
+                index: 1,
+                follow: false
+            },
+            Command::Spawn {
+                program: String::from("wt.exe"),
+                args: vec![String::from("-w"), String::from("0")]
+            },
+        ]
+    );
+    assert_eq!(user_keybinds.keybinds[0].keys, "alt shift j");
+}
+
+#[test]
+fn set_variant_takes_a_variant_index() {
+    let user_keybind: UserKeybind = serde_json::from_value(serde_json::json!(
+        {"keys": "alt ctrl 1", "command": "set_variant", "args": {"index": [1, 0]}}
+    ))
+    .unwrap();
+    assert_eq!(
+        user_keybind.command,
+        Command::SetVariant { index: vec![1, 0] }
+    );
+}
+
+#[test]
+fn unknown_commands_are_errors() {
+    let result = serde_json::from_value::<UserKeybinds>(serde_json::json!([
+        {"keys": "alt p", "command": "grab_everything"}
+    ]));
+    assert!(result.is_err());
+}
+
+#[test]
+fn default_keybinds_round_trip() {
+    let serialized = serde_json::to_string(&UserKeybinds::default()).unwrap();
+    let deserialized: UserKeybinds = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, UserKeybinds::default());
+}
+
+#[test]
+fn default_keybinds_are_unique_and_avoid_reserved_chords() {
+    let keybinds = UserKeybinds::default().keybinds;
+    let keys: std::collections::HashSet<&str> = keybinds
+        .iter()
+        .map(|keybind| keybind.keys.as_str())
+        .collect();
+    assert_eq!(keys.len(), keybinds.len());
+    assert!(keys
+        .iter()
+        .all(|keys| !keys.split(' ').any(|key| key == "win")));
+    for arrow in ["left", "down", "up", "right"] {
+        assert!(!keys.contains(format!("alt ctrl {arrow}").as_str()));
+    }
+}
+
+#[test]
+fn legacy_keybinds_are_migrated() {
+    let mut legacy = serde_json::json!({
+        "variant_keybinds": [
+            {"index": 1, "keybinds": {"previous": "alt shift h", "next": "alt shift l"}}
+        ]
+    });
+    for field in [
+        "focus_previous",
+        "focus_next",
+        "swap_previous",
+        "swap_next",
+        "layout_previous",
+        "layout_next",
+        "focus_previous_monitor",
+        "focus_next_monitor",
+        "move_to_previous_monitor",
+        "move_to_next_monitor",
+        "grab_window",
+        "release_window",
+        "toggle_window",
+        "toggle_workspace",
+        "refresh_workspace",
+        "restart_himewm",
+    ] {
+        legacy[field] = serde_json::Value::from("");
+    }
+    legacy["focus_next"] = serde_json::Value::from("alt k");
+    let user_keybinds: UserKeybinds = serde_json::from_value(legacy).unwrap();
+    assert_eq!(
+        user_keybinds.keybinds,
+        vec![
+            UserKeybind {
+                keys: String::from("alt k"),
+                command: Command::Focus {
+                    direction: CycleDirection::Next
+                },
+            },
+            UserKeybind {
+                keys: String::from("alt shift h"),
+                command: Command::CycleVariant {
+                    index: 1,
+                    direction: CycleDirection::Previous
+                },
+            },
+            UserKeybind {
+                keys: String::from("alt shift l"),
+                command: Command::CycleVariant {
+                    index: 1,
+                    direction: CycleDirection::Next
+                },
+            },
+        ]
+    );
+}
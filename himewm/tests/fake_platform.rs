@@ -0,0 +1,759 @@
+use himewm::{
+    commands,
+    platform::{
+        self,
+        fake::{FakeMonitor, FakePlatform, FakeWindow},
+        Platform, HWND,
+    },
+    settings, window_rules, wm,
+};
+use himewm_layout::{
+    layout::Layout, overflow::OverflowPolicy, position::Position, user_layout::UserLayout,
+    variant::Direction,
+};
+
+const LAYOUT: &str = r#"{
+    "w": 1920,
+    "h": 1080,
+    "default_variant_idx": [0],
+    "variants": [
+        {
+            "positions": [[[0, 0, 1920, 1080]]],
+            "end_behaviour": {
+                "from": null,
+                "position_idx": 0,
+                "behaviour": {"Directional": {"direction": "Right"}}
+            }
+        }
+    ]
+}"#;
+
+fn layout() -> Layout {
+    let user_layout: UserLayout = serde_json::from_str(LAYOUT).unwrap();
+    return Layout::from(user_layout);
+}
+
+fn window_manager(platform: &FakePlatform) -> wm::WindowManager {
+    return window_manager_with(platform, layout(), window_rules::WindowRules::default());
+}
+
+fn window_manager_with(
+    platform: &FakePlatform,
+    layout: Layout,
+    window_rules: window_rules::WindowRules,
+) -> wm::WindowManager {
+    let settings = settings::UserSettings::default().to_settings(&std::collections::HashMap::new());
+    let mut wm = wm::WindowManager::new(settings, window_rules, Box::new(platform.clone()));
+    wm.initialize(vec![layout]);
+    return wm;
+}
+
+/// Creates a window manager with the layouts "wide" (the default) and "tall",
+/// where the windows are stacked vertically, and a section of the settings.
+fn window_manager_with_section(
+    platform: &FakePlatform,
+    key: &str,
+    section: serde_json::Value,
+) -> wm::WindowManager {
+    let mut user_settings = serde_json::to_value(settings::UserSettings::default()).unwrap();
+    user_settings[key] = section;
+    let user_settings: settings::UserSettings = serde_json::from_value(user_settings).unwrap();
+    let settings = user_settings.to_settings(&std::collections::HashMap::from([
+        (String::from("wide"), 0),
+        (String::from("tall"), 1),
+    ]));
+    let tall: UserLayout = serde_json::from_str(&LAYOUT.replace("Right", "Down")).unwrap();
+    let mut wm = wm::WindowManager::new(
+        settings,
+        window_rules::WindowRules::default(),
+        Box::new(platform.clone()),
+    );
+    wm.initialize(vec![layout(), Layout::from(tall)]);
+    return wm;
+}
+
+/// Handles the events the fake platform has posted, like the message loop
+/// would.
+fn pump(platform: &FakePlatform, wm: &mut wm::WindowManager) {
+    while let Some((message, wparam)) = platform.pop_message() {
+        wm::message_handler::dispatch(message, wparam, wm);
+    }
+}
+
+fn rect(platform: &FakePlatform, hwnd: HWND) -> Position {
+    return platform.window(hwnd).unwrap().rect;
+}
+
+fn setup() -> (FakePlatform, HWND, HWND) {
+    let platform = FakePlatform::new();
+    platform.add_monitor(Position::new(0, 0, 1920, 1080), 96);
+    let desktop = platform.current_desktop();
+    let first = platform.add_window(FakeWindow::new(desktop, Position::new(100, 100, 400, 300)));
+    let second = platform.add_window(FakeWindow::new(desktop, Position::new(200, 200, 400, 300)));
+    return (platform, first, second);
+}
+
+// Tiled positions are extended by the default invisible border of 7 pixels on
+// the left, right and bottom.
+fn left_half() -> Position {
+    return Position::new(-7, 0, 974, 1087);
+}
+
+fn right_half() -> Position {
+    return Position::new(953, 0, 974, 1087);
+}
+
+fn full() -> Position {
+    return Position::new(-7, 0, 1934, 1087);
+}
+
+#[test]
+fn initialize_tiles_existing_windows() {
+    let (platform, first, second) = setup();
+    let wm = window_manager(&platform);
+    assert_eq!(wm.window_info().len(), 2);
+    assert_eq!(rect(&platform, first), left_half());
+    assert_eq!(rect(&platform, second), right_half());
+}
+
+#[test]
+fn manage_window_tiles_new_window() {
+    let platform = FakePlatform::new();
+    platform.add_monitor(Position::new(0, 0, 1920, 1080), 96);
+    let desktop = platform.current_desktop();
+    let mut wm = window_manager(&platform);
+    let first = platform.create_window(FakeWindow::new(desktop, Position::new(10, 10, 300, 300)));
+    wm.manage_window(first);
+    assert_eq!(rect(&platform, first), full());
+    let second = platform.create_window(FakeWindow::new(desktop, Position::new(10, 10, 300, 300)));
+    wm.manage_window(second);
+    assert_eq!(rect(&platform, first), left_half());
+    assert_eq!(rect(&platform, second), right_half());
+}
+
+#[test]
+fn window_cloaked_moves_window_to_other_desktop() {
+    let (platform, first, second) = setup();
+    let mut wm = window_manager(&platform);
+    let other_desktop = platform.add_desktop();
+    platform.move_window_to_desktop(second, other_desktop);
+    assert_eq!(
+        platform.take_messages(),
+        vec![(wm::messages::messages::WINDOW_CLOAKED, second.0 as usize)]
+    );
+    wm.window_cloaked(second);
+    assert_eq!(rect(&platform, first), full());
+    assert_eq!(rect(&platform, second), full());
+}
+
+#[test]
+fn cycle_swap_swaps_with_next_window() {
+    let (platform, first, second) = setup();
+    let mut wm = window_manager(&platform);
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    wm.cycle_swap(wm::CycleDirection::Next);
+    assert_eq!(rect(&platform, first), right_half());
+    assert_eq!(rect(&platform, second), left_half());
+}
+
+#[test]
+fn window_move_finished_swaps_windows() {
+    let (platform, first, second) = setup();
+    let mut wm = window_manager(&platform);
+    platform.move_window(first, Position::new(1200, 100, 400, 300));
+    wm.window_move_finished(first);
+    assert_eq!(rect(&platform, first), right_half());
+    assert_eq!(rect(&platform, second), left_half());
+}
+
+#[test]
+fn resize_focused_window_persists_for_workspace() {
+    let (platform, first, second) = setup();
+    let mut wm = window_manager(&platform);
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    wm.resize_focused_window(Direction::Right, true);
+    assert_eq!(rect(&platform, first), Position::new(-7, 0, 994, 1087));
+    assert_eq!(rect(&platform, second), Position::new(973, 0, 954, 1087));
+    wm.refresh_workspace();
+    assert_eq!(rect(&platform, first), Position::new(-7, 0, 994, 1087));
+    wm.resize_focused_window(Direction::Right, false);
+    assert_eq!(rect(&platform, first), left_half());
+    assert_eq!(rect(&platform, second), right_half());
+}
+
+#[test]
+fn window_weights_follow_windows_until_reset() {
+    let (platform, first, second) = setup();
+    let mut wm = window_manager(&platform);
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    wm.change_focused_window_weight(true);
+    assert_eq!(rect(&platform, first), Position::new(-7, 0, 1081, 1087));
+    assert_eq!(rect(&platform, second), Position::new(1060, 0, 867, 1087));
+    wm.cycle_swap(wm::CycleDirection::Next);
+    assert_eq!(rect(&platform, first), Position::new(846, 0, 1081, 1087));
+    assert_eq!(rect(&platform, second), Position::new(-7, 0, 867, 1087));
+    wm.reset_weights();
+    assert_eq!(rect(&platform, first), right_half());
+    assert_eq!(rect(&platform, second), left_half());
+}
+
+#[test]
+fn windows_smaller_than_their_min_size_float() {
+    let platform = FakePlatform::new();
+    platform.add_monitor(Position::new(0, 0, 1920, 1080), 96);
+    let desktop = platform.current_desktop();
+    let first = platform.add_window(FakeWindow::new(desktop, Position::new(100, 100, 400, 300)));
+    let second = platform.add_window(
+        FakeWindow::new(desktop, Position::new(200, 200, 400, 300))
+            .with_exe_name("big.exe")
+            .with_min_size(1200, 600),
+    );
+    let mut layout = layout();
+    layout.set_overflow(OverflowPolicy::Float);
+    let window_rules = window_rules::WindowRules {
+        title_window_rules: Vec::new(),
+        process_window_rules: vec![window_rules::WindowRule {
+            regex: regex::Regex::new("big.exe").unwrap(),
+            rule: window_rules::Rule::MinSize(window_rules::MinSize::FromWindow),
+        }],
+    };
+    let _wm = window_manager_with(&platform, layout, window_rules);
+    assert_eq!(rect(&platform, first), full());
+    assert_eq!(rect(&platform, second), Position::new(473, 270, 974, 547));
+}
+
+#[test]
+fn monocle_stacks_every_window_until_toggled_off() {
+    let (platform, first, second) = setup();
+    let mut wm = window_manager(&platform);
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    wm.toggle_monocle();
+    assert_eq!(rect(&platform, first), full());
+    assert_eq!(rect(&platform, second), full());
+    wm.cycle_focus(wm::CycleDirection::Next);
+    assert_eq!(platform.get_foreground_window(), second);
+    wm.toggle_monocle();
+    assert_eq!(rect(&platform, first), left_half());
+    assert_eq!(rect(&platform, second), right_half());
+}
+
+#[test]
+fn set_variant_switches_to_existing_variants() {
+    let (platform, first, second) = setup();
+    let user_layout: UserLayout = serde_json::from_str(&LAYOUT.replace(
+        "\"variants\": [",
+        r#""variants": [
+            {
+                "positions": [[[0, 0, 1920, 1080]]],
+                "end_behaviour": {
+                    "from": null,
+                    "position_idx": 0,
+                    "behaviour": {"Directional": {"direction": "Down"}}
+                }
+            },"#,
+    ))
+    .unwrap();
+    let mut wm = window_manager_with(
+        &platform,
+        Layout::from(user_layout),
+        window_rules::WindowRules::default(),
+    );
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    assert_eq!(rect(&platform, second), Position::new(-7, 540, 1934, 547));
+    wm::message_handler::run_command(&commands::Command::SetVariant { index: vec![1] }, &mut wm);
+    assert_eq!(rect(&platform, first), left_half());
+    assert_eq!(rect(&platform, second), right_half());
+    wm.set_variant(&[2]);
+    wm.set_variant(&[1, 0]);
+    assert_eq!(rect(&platform, second), right_half());
+}
+
+#[test]
+fn monocle_only_takes_focus_when_it_is_turned_on() {
+    let (platform, first, second) = setup();
+    let mut wm = window_manager(&platform);
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    wm.toggle_monocle();
+    platform.set_foreground(second);
+    wm.refresh_workspace();
+    assert_eq!(platform.get_foreground_window(), second);
+    wm.toggle_monocle();
+    wm.toggle_monocle();
+    assert_eq!(platform.get_foreground_window(), first);
+}
+
+#[test]
+fn tile_groups_show_one_member_at_a_time() {
+    let (platform, first, second) = setup();
+    let mut wm = window_manager(&platform);
+    platform.set_foreground_window(second);
+    pump(&platform, &mut wm);
+    wm.join_tile_group(wm::CycleDirection::Previous);
+    pump(&platform, &mut wm);
+    assert_eq!(rect(&platform, second), full());
+    assert!(platform.window(first).unwrap().minimized);
+    wm.cycle_tile_group(wm::CycleDirection::Next);
+    pump(&platform, &mut wm);
+    assert_eq!(rect(&platform, first), full());
+    assert!(platform.window(second).unwrap().minimized);
+    wm.split_tile_group();
+    pump(&platform, &mut wm);
+    assert!(!platform.window(second).unwrap().minimized);
+    assert_eq!(rect(&platform, second), left_half());
+    assert_eq!(rect(&platform, first), right_half());
+}
+
+#[test]
+fn destroying_the_shown_member_restores_the_next() {
+    let (platform, first, second) = setup();
+    let mut wm = window_manager(&platform);
+    platform.set_foreground_window(second);
+    pump(&platform, &mut wm);
+    wm.join_tile_group(wm::CycleDirection::Previous);
+    pump(&platform, &mut wm);
+    platform.destroy_window(second);
+    pump(&platform, &mut wm);
+    assert!(!platform.window(first).unwrap().minimized);
+    assert_eq!(rect(&platform, first), full());
+}
+
+#[test]
+fn gap_commands_change_the_gaps_of_the_workspace() {
+    let (platform, first, second) = setup();
+    let mut wm = window_manager(&platform);
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    wm.change_gaps(true);
+    assert_eq!(rect(&platform, first), Position::new(-7, 0, 972, 1087));
+    assert_eq!(rect(&platform, second), Position::new(955, 0, 972, 1087));
+    wm.toggle_gaps();
+    assert_eq!(rect(&platform, first), left_half());
+    wm.toggle_gaps();
+    assert_eq!(rect(&platform, second), Position::new(955, 0, 972, 1087));
+    wm.change_gaps(false);
+    wm.change_gaps(false);
+    assert_eq!(rect(&platform, first), left_half());
+    wm.change_gaps(true);
+    assert_eq!(rect(&platform, first), Position::new(-7, 0, 972, 1087));
+}
+
+#[test]
+fn monitors_section_applies_to_matching_monitors() {
+    let platform = FakePlatform::new();
+    platform.add_monitor(Position::new(0, 0, 1920, 1080), 96);
+    let portrait = platform.add_monitor(Position::new(1920, 0, 1080, 1920), 96);
+    let desktop = platform.current_desktop();
+    let wide = platform.add_window(FakeWindow::new(desktop, Position::new(100, 100, 400, 300)));
+    let first = platform.add_window(FakeWindow::new(desktop, Position::new(2000, 100, 400, 300)));
+    let second = platform.add_window(FakeWindow::new(desktop, Position::new(2000, 200, 400, 300)));
+    let section = serde_json::json!({
+        platform.monitor(portrait).unwrap().device_name: {
+            "default_layout": "tall",
+            "padding": {"left": 10},
+            "disable_rounding": true,
+        },
+    });
+    let _wm = window_manager_with_section(&platform, "monitors", section);
+    assert_eq!(rect(&platform, wide), full());
+    assert_eq!(rect(&platform, first), Position::new(1923, 0, 1084, 967));
+    assert_eq!(rect(&platform, second), Position::new(1923, 960, 1084, 967));
+    assert_eq!(
+        platform.window(wide).unwrap().corner_preference,
+        Some(platform::CornerPreference::Default)
+    );
+    assert_eq!(
+        platform.window(first).unwrap().corner_preference,
+        Some(platform::CornerPreference::DoNotRound)
+    );
+}
+
+#[test]
+fn desktops_section_applies_to_matching_desktops() {
+    let (platform, first, second) = setup();
+    let desktop = platform.current_desktop();
+    platform.set_desktop_name(desktop, "Notes");
+    let untiled_desktop = platform.add_desktop();
+    let untiled = platform.add_window(FakeWindow::new(
+        untiled_desktop,
+        Position::new(100, 100, 400, 300),
+    ));
+    let section = serde_json::json!({
+        "Notes": {"default_layout": "tall"},
+        "1": {"ignored": true},
+    });
+    let _wm = window_manager_with_section(&platform, "desktops", section);
+    assert_eq!(rect(&platform, first), Position::new(-7, 0, 1934, 547));
+    assert_eq!(rect(&platform, second), Position::new(-7, 540, 1934, 547));
+    assert_eq!(rect(&platform, untiled), Position::new(100, 100, 400, 300));
+    assert_eq!(platform.window(untiled).unwrap().corner_preference, None);
+}
+
+#[test]
+fn removed_monitors_move_their_windows_to_the_first_monitor() {
+    let (platform, first, second) = setup();
+    let other = platform.add_monitor(Position::new(1920, 0, 1920, 1080), 96);
+    let desktop = platform.current_desktop();
+    let third = platform.add_window(FakeWindow::new(desktop, Position::new(2000, 100, 400, 300)));
+    let mut wm = window_manager(&platform);
+    assert_eq!(rect(&platform, third), Position::new(1913, 0, 1934, 1087));
+    platform.remove_monitor(other);
+    wm.display_changed();
+    assert_eq!(wm.monitor_handles().len(), 1);
+    assert_eq!(rect(&platform, first), Position::new(-7, 0, 654, 1087));
+    assert_eq!(rect(&platform, second), Position::new(633, 0, 654, 1087));
+    assert_eq!(rect(&platform, third), Position::new(1273, 0, 654, 1087));
+}
+
+#[test]
+fn monitors_can_be_removed_without_any_layouts() {
+    let platform = FakePlatform::new();
+    platform.add_monitor(Position::new(0, 0, 1920, 1080), 96);
+    let other = platform.add_monitor(Position::new(1920, 0, 1920, 1080), 96);
+    let settings = settings::UserSettings::default().to_settings(&std::collections::HashMap::new());
+    let mut wm = wm::WindowManager::new(
+        settings,
+        window_rules::WindowRules::default(),
+        Box::new(platform.clone()),
+    );
+    wm.initialize(Vec::new());
+    platform.remove_monitor(other);
+    wm.display_changed();
+    assert_eq!(wm.monitor_handles().len(), 1);
+}
+
+#[test]
+fn work_area_changes_retile_windows() {
+    let (platform, first, second) = setup();
+    let mut wm = window_manager(&platform);
+    let hmonitor = wm.monitor_handles()[0];
+    platform.insert_monitor(
+        hmonitor,
+        FakeMonitor::new(Position::new(0, 0, 1920, 1040), 96)
+            .with_rect(Position::new(0, 0, 1920, 1080)),
+    );
+    wm.display_changed();
+    assert_eq!(rect(&platform, first), Position::new(-7, 0, 974, 1047));
+    assert_eq!(rect(&platform, second), Position::new(953, 0, 974, 1047));
+    let added = platform.add_monitor(Position::new(1920, 0, 1920, 1080), 96);
+    wm.display_changed();
+    assert!(wm.layouts().contains_key(&added.0));
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    wm.cycle_assigned_monitor(wm::CycleDirection::Next);
+    assert_eq!(rect(&platform, first), Position::new(1913, 0, 1934, 1087));
+    assert_eq!(rect(&platform, second), Position::new(-7, 0, 1934, 1047));
+}
+
+#[test]
+fn resizes_are_kept_when_the_work_area_moves() {
+    let (platform, first, second) = setup();
+    let mut wm = window_manager(&platform);
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    wm.resize_focused_window(Direction::Right, true);
+    let hmonitor = wm.monitor_handles()[0];
+    platform.insert_monitor(
+        hmonitor,
+        FakeMonitor::new(Position::new(0, 40, 1920, 1080), 96)
+            .with_rect(Position::new(0, 0, 1920, 1120)),
+    );
+    wm.display_changed();
+    assert_eq!(rect(&platform, first), Position::new(-7, 40, 994, 1087));
+    assert_eq!(rect(&platform, second), Position::new(973, 40, 954, 1087));
+}
+
+#[test]
+fn resizes_are_scaled_when_the_work_area_is_resized() {
+    let (platform, first, second) = setup();
+    let mut wm = window_manager(&platform);
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    wm.resize_focused_window(Direction::Right, true);
+    let hmonitor = wm.monitor_handles()[0];
+    platform.insert_monitor(
+        hmonitor,
+        FakeMonitor::new(Position::new(0, 0, 1920, 1040), 96)
+            .with_rect(Position::new(0, 0, 1920, 1080)),
+    );
+    wm.display_changed();
+    assert_eq!(rect(&platform, first), Position::new(-7, 0, 994, 1047));
+    assert_eq!(rect(&platform, second), Position::new(973, 0, 954, 1047));
+}
+
+#[test]
+fn workspace_state_is_kept_when_monitors_are_removed() {
+    let platform = FakePlatform::new();
+    platform.add_monitor(Position::new(0, 0, 1920, 1080), 96);
+    let other = platform.add_monitor(Position::new(1920, 0, 1920, 1080), 96);
+    let desktop = platform.current_desktop();
+    let first = platform.add_window(FakeWindow::new(desktop, Position::new(2000, 100, 400, 300)));
+    let second = platform.add_window(FakeWindow::new(desktop, Position::new(2100, 200, 400, 300)));
+    let mut wm = window_manager(&platform);
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    wm.resize_focused_window(Direction::Right, true);
+    platform.remove_monitor(other);
+    wm.display_changed();
+    assert_eq!(rect(&platform, first), Position::new(-7, 0, 994, 1087));
+    assert_eq!(rect(&platform, second), Position::new(973, 0, 954, 1087));
+    let third = platform.add_window(FakeWindow::new(desktop, Position::new(2000, 100, 400, 300)));
+    let other = platform.add_monitor(Position::new(1920, 0, 1920, 1080), 96);
+    wm.display_changed();
+    wm.manage_window(third);
+    platform.set_foreground_window(third);
+    wm.foreground_window_changed(third, false);
+    wm.toggle_monocle();
+    platform.remove_monitor(other);
+    wm.display_changed();
+    assert_eq!(rect(&platform, first), full());
+    assert_eq!(rect(&platform, second), full());
+    assert_eq!(rect(&platform, third), full());
+}
+
+#[test]
+fn focus_direction_moves_to_the_nearest_tile_then_the_next_monitor() {
+    let (platform, first, second) = setup();
+    platform.add_monitor(Position::new(1920, 0, 1920, 1080), 96);
+    let desktop = platform.current_desktop();
+    let third = platform.add_window(FakeWindow::new(desktop, Position::new(2000, 100, 400, 300)));
+    let mut wm = window_manager(&platform);
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    wm.focus_direction(Direction::Up);
+    assert_eq!(platform.get_foreground_window(), first);
+    wm.focus_direction(Direction::Right);
+    assert_eq!(platform.get_foreground_window(), second);
+    wm.foreground_window_changed(second, false);
+    wm.focus_direction(Direction::Right);
+    assert_eq!(platform.get_foreground_window(), third);
+    wm.foreground_window_changed(third, false);
+    wm.focus_direction(Direction::Left);
+    assert_eq!(platform.get_foreground_window(), second);
+}
+
+#[test]
+fn swap_direction_swaps_with_the_nearest_tile_then_the_next_monitor() {
+    let (platform, first, second) = setup();
+    platform.add_monitor(Position::new(1920, 0, 1920, 1080), 96);
+    let desktop = platform.current_desktop();
+    let third = platform.add_window(FakeWindow::new(desktop, Position::new(2000, 100, 400, 300)));
+    let mut wm = window_manager(&platform);
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    wm.swap_direction(Direction::Right);
+    assert_eq!(rect(&platform, first), right_half());
+    assert_eq!(rect(&platform, second), left_half());
+    wm.swap_direction(Direction::Right);
+    assert_eq!(rect(&platform, first), Position::new(1913, 0, 1934, 1087));
+    assert_eq!(rect(&platform, second), left_half());
+    assert_eq!(rect(&platform, third), right_half());
+    wm.swap_direction(Direction::Right);
+    assert_eq!(rect(&platform, first), Position::new(1913, 0, 1934, 1087));
+}
+
+#[test]
+fn windows_can_be_moved_to_an_index_promoted_and_rotated() {
+    let (platform, first, second) = setup();
+    let desktop = platform.current_desktop();
+    let third = platform.add_window(FakeWindow::new(desktop, Position::new(300, 300, 400, 300)));
+    let mut wm = window_manager(&platform);
+    let thirds = [
+        Position::new(-7, 0, 654, 1087),
+        Position::new(633, 0, 654, 1087),
+        Position::new(1273, 0, 654, 1087),
+    ];
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    wm.move_to_index(8);
+    assert_eq!(rect(&platform, second), thirds[0]);
+    assert_eq!(rect(&platform, third), thirds[1]);
+    assert_eq!(rect(&platform, first), thirds[2]);
+    wm.promote_to_master();
+    assert_eq!(rect(&platform, first), thirds[0]);
+    assert_eq!(rect(&platform, third), thirds[1]);
+    assert_eq!(rect(&platform, second), thirds[2]);
+    wm.rotate_windows(wm::CycleDirection::Next);
+    assert_eq!(rect(&platform, second), thirds[0]);
+    assert_eq!(rect(&platform, first), thirds[1]);
+    assert_eq!(rect(&platform, third), thirds[2]);
+    wm.rotate_windows(wm::CycleDirection::Previous);
+    assert_eq!(rect(&platform, first), thirds[0]);
+    wm.move_to_index(1);
+    assert_eq!(rect(&platform, third), thirds[0]);
+    assert_eq!(rect(&platform, first), thirds[1]);
+    assert_eq!(rect(&platform, second), thirds[2]);
+}
+
+#[test]
+fn windows_can_be_sent_to_other_desktops() {
+    let (platform, first, second) = setup();
+    let mut wm = window_manager(&platform);
+    let first_desktop = platform.current_desktop();
+    let other_desktop = platform.add_desktop();
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    wm.cycle_window_desktop(wm::CycleDirection::Next, false);
+    assert_eq!(platform.window(first).unwrap().desktop_id, other_desktop);
+    assert_eq!(platform.current_desktop(), first_desktop);
+    assert_eq!(rect(&platform, second), full());
+    // The cloak that follows the move has nothing left to do
+    pump(&platform, &mut wm);
+    assert_eq!(rect(&platform, first), full());
+    assert_eq!(rect(&platform, second), full());
+    platform.set_foreground_window(second);
+    wm.foreground_window_changed(second, false);
+    wm.move_to_desktop(1, true);
+    pump(&platform, &mut wm);
+    assert_eq!(platform.current_desktop(), other_desktop);
+    assert_eq!(platform.get_foreground_window(), second);
+    assert_eq!(rect(&platform, first), left_half());
+    assert_eq!(rect(&platform, second), right_half());
+}
+
+#[test]
+fn sending_a_window_away_focuses_the_window_that_took_its_place() {
+    let (platform, first, second) = setup();
+    let desktop = platform.current_desktop();
+    let third = platform.add_window(FakeWindow::new(desktop, Position::new(300, 300, 400, 300)));
+    let mut wm = window_manager(&platform);
+    platform.add_desktop();
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    wm.cycle_window_desktop(wm::CycleDirection::Next, false);
+    pump(&platform, &mut wm);
+    assert_eq!(platform.get_foreground_window(), second);
+    wm.cycle_swap(wm::CycleDirection::Next);
+    assert_eq!(rect(&platform, second), right_half());
+    assert_eq!(rect(&platform, third), left_half());
+}
+
+#[test]
+fn scratchpads_are_launched_shown_and_hidden() {
+    let (platform, first, second) = setup();
+    let desktop = platform.current_desktop();
+    let mut wm = window_manager_with_section(
+        &platform,
+        "scratchpads",
+        serde_json::json!({
+            "terminal": {
+                "match_type": "Process",
+                "regex": "^wt\\.exe$",
+                "command": "wt.exe",
+                "position": {"x": 100, "y": 50, "w": 800, "h": 600}
+            }
+        }),
+    );
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    wm.toggle_scratchpad("terminal");
+    assert_eq!(
+        platform.spawned(),
+        vec![(String::from("wt.exe"), Vec::new())]
+    );
+    let terminal = platform.create_window(
+        FakeWindow::new(desktop, Position::new(10, 10, 300, 300)).with_exe_name("wt.exe"),
+    );
+    pump(&platform, &mut wm);
+    assert_eq!(rect(&platform, terminal), Position::new(93, 50, 814, 607));
+    assert_eq!(platform.get_foreground_window(), terminal);
+    assert_eq!(rect(&platform, first), left_half());
+    assert_eq!(rect(&platform, second), right_half());
+    wm.toggle_scratchpad("terminal");
+    pump(&platform, &mut wm);
+    assert!(!platform.window(terminal).unwrap().visible);
+    let other_desktop = platform.add_desktop();
+    platform.switch_desktop(other_desktop);
+    let other = platform.create_window(FakeWindow::new(
+        other_desktop,
+        Position::new(10, 10, 300, 300),
+    ));
+    platform.set_foreground_window(other);
+    pump(&platform, &mut wm);
+    wm.toggle_scratchpad("terminal");
+    pump(&platform, &mut wm);
+    let window = platform.window(terminal).unwrap();
+    assert!(window.visible);
+    assert_eq!(window.desktop_id, other_desktop);
+    assert_eq!(window.rect, Position::new(93, 50, 814, 607));
+    assert_eq!(rect(&platform, other), full());
+    assert_eq!(platform.spawned().len(), 1);
+}
+
+#[test]
+fn hotkeys_run_the_command_at_their_identifier() {
+    let (platform, first, second) = setup();
+    platform.add_monitor(Position::new(1920, 0, 1920, 1080), 96);
+    let desktop = platform.current_desktop();
+    let third = platform.add_window(FakeWindow::new(desktop, Position::new(2000, 100, 400, 300)));
+    let mut wm = window_manager_with_section(&platform, "desktops", serde_json::json!({}));
+    wm.set_commands(vec![
+        commands::Command::SetLayout {
+            name: String::from("tall"),
+        },
+        commands::Command::FocusMonitor { index: 1 },
+        commands::Command::Spawn {
+            program: String::from("notepad.exe"),
+            args: Vec::new(),
+        },
+    ]);
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    wm::message_handler::dispatch(platform::WM_HOTKEY, 0, &mut wm);
+    assert_eq!(rect(&platform, first), Position::new(-7, 0, 1934, 547));
+    assert_eq!(rect(&platform, second), Position::new(-7, 540, 1934, 547));
+    wm::message_handler::dispatch(platform::WM_HOTKEY, 1, &mut wm);
+    assert_eq!(platform.get_foreground_window(), third);
+    wm::message_handler::dispatch(platform::WM_HOTKEY, 2, &mut wm);
+    wm::message_handler::dispatch(platform::WM_HOTKEY, 3, &mut wm);
+    assert_eq!(
+        platform.spawned(),
+        vec![(String::from("notepad.exe"), Vec::new())]
+    );
+}
+
+#[test]
+fn master_commands_change_the_workspace_layout() {
+    let (platform, first, second) = setup();
+    let desktop = platform.current_desktop();
+    let third = platform.add_window(FakeWindow::new(desktop, Position::new(300, 300, 400, 300)));
+    let user_layout: UserLayout = serde_json::from_str(&LAYOUT.replace(
+        r#"{"Directional": {"direction": "Right"}}"#,
+        r#"{"MasterStack": {"master_count": 1, "master_ratio": 0.5, "stack_direction": "Right"}}"#,
+    ))
+    .unwrap();
+    let mut wm = window_manager_with(
+        &platform,
+        Layout::from(user_layout),
+        window_rules::WindowRules::default(),
+    );
+    wm.set_commands(vec![
+        commands::Command::GrowMaster,
+        commands::Command::IncreaseMasterCount,
+        commands::Command::DecreaseMasterCount,
+        commands::Command::ShrinkMaster,
+    ]);
+    platform.set_foreground_window(first);
+    wm.foreground_window_changed(first, false);
+    assert_eq!(rect(&platform, first), left_half());
+    assert_eq!(rect(&platform, third), Position::new(953, 540, 974, 547));
+    wm::message_handler::dispatch(platform::WM_HOTKEY, 0, &mut wm);
+    assert_eq!(rect(&platform, first), Position::new(-7, 0, 1070, 1087));
+    assert_eq!(rect(&platform, second), Position::new(1049, 0, 878, 547));
+    wm.refresh_workspace();
+    assert_eq!(rect(&platform, first), Position::new(-7, 0, 1070, 1087));
+    wm::message_handler::dispatch(platform::WM_HOTKEY, 1, &mut wm);
+    assert_eq!(rect(&platform, first), Position::new(-7, 0, 1070, 547));
+    assert_eq!(rect(&platform, second), Position::new(-7, 540, 1070, 547));
+    assert_eq!(rect(&platform, third), Position::new(1049, 0, 878, 1087));
+    wm::message_handler::dispatch(platform::WM_HOTKEY, 2, &mut wm);
+    wm::message_handler::dispatch(platform::WM_HOTKEY, 3, &mut wm);
+    assert_eq!(rect(&platform, first), left_half());
+    assert_eq!(rect(&platform, second), Position::new(953, 0, 974, 547));
+    assert_eq!(rect(&platform, third), Position::new(953, 540, 974, 547));
+}
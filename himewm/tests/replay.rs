@@ -0,0 +1,58 @@
+use himewm::{settings, window_rules, wm::replay};
+use himewm_layout::{layout::Layout, user_layout::UserLayout};
+
+const FIXTURES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+
+fn layouts() -> Vec<Layout> {
+    let mut paths: Vec<std::path::PathBuf> =
+        std::fs::read_dir(std::path::Path::new(FIXTURES_DIR).join("layouts"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+    paths.sort();
+    return paths
+        .iter()
+        .map(|path| {
+            let byte_vector = std::fs::read(path).unwrap();
+            let user_layout: UserLayout = serde_json::from_slice(byte_vector.as_slice()).unwrap();
+            Layout::from(user_layout)
+        })
+        .collect();
+}
+
+#[test]
+fn recordings_replay_to_their_snapshots() {
+    let mut paths: Vec<std::path::PathBuf> =
+        std::fs::read_dir(std::path::Path::new(FIXTURES_DIR).join("recordings"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+    paths.sort();
+    assert!(!paths.is_empty());
+    for path in paths {
+        let recording = replay::Recording::from_file(&path).unwrap();
+        let settings =
+            settings::UserSettings::default().to_settings(&std::collections::HashMap::new());
+        let wm = replay::replay(
+            &recording,
+            settings,
+            window_rules::WindowRules::default(),
+            layouts(),
+        );
+        assert_eq!(
+            Some(wm.snapshot()),
+            recording.snapshot,
+            "{} replayed to a different snapshot",
+            path.display()
+        );
+    }
+}
+
+#[test]
+fn recording_round_trips() {
+    let path = std::path::Path::new(FIXTURES_DIR).join("recordings/desktop_switching.json");
+    let recording = replay::Recording::from_file(&path).unwrap();
+    let serialized = serde_json::to_string(&recording).unwrap();
+    let deserialized: replay::Recording = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(recording, deserialized);
+}
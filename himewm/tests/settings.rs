@@ -0,0 +1,113 @@
+use himewm::{
+    platform::GUID,
+    settings::{DesktopKey, MonitorKey, UserSettings},
+};
+
+fn settings(monitors: serde_json::Value) -> himewm::settings::Settings {
+    return settings_with_section("monitors", monitors);
+}
+
+fn settings_with_section(key: &str, section: serde_json::Value) -> himewm::settings::Settings {
+    let mut user_settings = serde_json::to_value(UserSettings::default()).unwrap();
+    user_settings[key] = section;
+    let user_settings: UserSettings = serde_json::from_value(user_settings).unwrap();
+    let layout_idx_map =
+        std::collections::HashMap::from([(String::from("wide"), 0), (String::from("tall"), 1)]);
+    return user_settings.to_settings(&layout_idx_map);
+}
+
+#[test]
+fn monitor_keys_are_parsed_by_their_form() {
+    assert_eq!(MonitorKey::parse("1"), MonitorKey::Index(1));
+    assert_eq!(
+        MonitorKey::parse("2560x1440"),
+        MonitorKey::Resolution(2560, 1440)
+    );
+    assert_eq!(
+        MonitorKey::parse("\\\\.\\DISPLAY1"),
+        MonitorKey::DeviceName(String::from("\\\\.\\DISPLAY1"))
+    );
+}
+
+#[test]
+fn monitors_without_a_match_use_the_other_sections() {
+    let settings = settings(serde_json::json!({"0": {"default_layout": "tall"}}));
+    let monitor_settings = settings.monitor_settings("\\\\.\\DISPLAY2", 1, (1920, 1080));
+    assert_eq!(monitor_settings.default_layout_idx, 0);
+    assert!(monitor_settings.padding.is_empty());
+}
+
+#[test]
+fn more_specific_keys_take_precedence() {
+    let settings = settings(serde_json::json!({
+        "\\\\.\\DISPLAY2": {"default_layout": "tall"},
+        "1": {"default_layout": "wide", "disable_rounding": true},
+        "1080x1920": {"padding": {"left": 10}, "default_variant_idx": [1]},
+    }));
+    let monitor_settings = settings.monitor_settings("\\\\.\\DISPLAY2", 1, (1080, 1920));
+    assert_eq!(monitor_settings.default_layout_idx, 1);
+    assert!(monitor_settings.disable_rounding);
+    assert_eq!(monitor_settings.padding.left, Some(10));
+    assert_eq!(monitor_settings.default_variant_idx, Some(vec![1]));
+}
+
+#[test]
+fn unknown_default_layouts_are_ignored() {
+    let settings = settings(serde_json::json!({"0": {"default_layout": "missing"}}));
+    assert_eq!(
+        settings
+            .monitor_settings("", 0, (1920, 1080))
+            .default_layout_idx,
+        0
+    );
+}
+
+#[test]
+fn desktop_keys_are_parsed_by_their_form() {
+    assert_eq!(DesktopKey::parse("2"), DesktopKey::Index(2));
+    assert_eq!(
+        DesktopKey::parse("{00000000-0000-0000-0000-000000000001}"),
+        DesktopKey::Id(GUID::from_u128(1))
+    );
+    assert_eq!(
+        DesktopKey::parse("Games"),
+        DesktopKey::Name(String::from("Games"))
+    );
+}
+
+#[test]
+fn desktops_are_ignored_if_any_matching_key_says_so() {
+    let settings = settings_with_section(
+        "desktops",
+        serde_json::json!({
+            "2": {"ignored": true, "default_layout": "wide"},
+            "Games": {"default_layout": "tall"},
+        }),
+    );
+    let desktop_settings = settings.desktop_settings(GUID::from_u128(3), Some("Games"), Some(2));
+    assert!(desktop_settings.ignored);
+    assert_eq!(desktop_settings.default_layout_idx, Some(1));
+    let desktop_settings = settings.desktop_settings(GUID::from_u128(1), None, Some(0));
+    assert!(!desktop_settings.ignored);
+    assert_eq!(desktop_settings.default_layout_idx, None);
+}
+
+#[test]
+fn scratchpads_are_ordered_by_name_and_skip_invalid_regexes() {
+    let settings = settings_with_section(
+        "scratchpads",
+        serde_json::json!({
+            "notes": {"match_type": "Title", "regex": "Notes", "command": "notes.exe"},
+            "broken": {"match_type": "Title", "regex": "(", "command": "broken.exe"},
+            "terminal": {"match_type": "Process", "regex": "wt", "command": "wt.exe", "args": ["-w", "0"]}
+        }),
+    );
+    let names: Vec<&str> = settings
+        .scratchpads
+        .iter()
+        .map(|scratchpad| scratchpad.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["notes", "terminal"]);
+    assert_eq!(settings.scratchpads[1].args, vec!["-w", "0"]);
+    assert!(settings.scratchpads[0].position.is_none());
+}
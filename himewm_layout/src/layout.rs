@@ -1,18 +1,32 @@
-use crate::{position, user_layout, variant, variants_container};
+use crate::{overflow, padding, position, user_layout, validation, variant, variants_container};
 
 #[derive(Clone, Debug)]
 pub struct Layout {
     monitor_rect: position::Position,
+    w: f64,
+    h: f64,
+    user_variants: variants_container::VariantsContainer<user_layout::UserVariant>,
     variants: variants_container::VariantsContainer<variant::Variant>,
     default_variant_idx: Vec<usize>,
+    min_w: f64,
+    min_h: f64,
+    overflow: overflow::OverflowPolicy,
+    padding: padding::PaddingOverrides,
 }
 
 impl Layout {
     pub fn new(w: i32, h: i32) -> Self {
         Self {
             monitor_rect: position::Position::new(0, 0, w, h),
+            w: w as f64,
+            h: h as f64,
+            user_variants: variants_container::VariantsContainer::Variants(Vec::new()),
             variants: variants_container::VariantsContainer::Variants(Vec::new()),
             default_variant_idx: vec![0],
+            min_w: 0.0,
+            min_h: 0.0,
+            overflow: overflow::OverflowPolicy::default(),
+            padding: padding::PaddingOverrides::default(),
         }
     }
 
@@ -24,6 +38,11 @@ impl Layout {
         self.monitor_rect = position;
     }
 
+    /// The size of the layout in the units its positions were written in.
+    pub fn units(&self) -> (f64, f64) {
+        (self.w, self.h)
+    }
+
     pub fn variants(&self) -> &variants_container::VariantsContainer<variant::Variant> {
         &self.variants
     }
@@ -40,9 +59,131 @@ impl Layout {
         self.default_variant_idx = Vec::from(idx);
     }
 
-    pub fn update_all(&mut self, window_padding: i32, edge_padding: i32) {
+    /// The minimum size of a tile in pixels on the current monitor rect.
+    pub fn min_size(&self) -> (i32, i32) {
+        return (
+            (self.min_w * self.monitor_rect.w() as f64 / self.w).round() as i32,
+            (self.min_h * self.monitor_rect.h() as f64 / self.h).round() as i32,
+        );
+    }
+
+    pub fn overflow(&self) -> &overflow::OverflowPolicy {
+        &self.overflow
+    }
+
+    pub fn set_overflow(&mut self, overflow: overflow::OverflowPolicy) {
+        self.overflow = overflow;
+    }
+
+    /// Changes to the padding from the settings made by the layout.
+    pub fn padding(&self) -> &padding::PaddingOverrides {
+        &self.padding
+    }
+
+    pub fn set_padding(&mut self, padding: padding::PaddingOverrides) {
+        self.padding = padding;
+    }
+
+    /// Returns a copy of the layout placed on `monitor_rect`.
+    ///
+    /// Positions are resolved from the units the layout was written in rather
+    /// than rescaled from the current monitor rect, so resolving repeatedly
+    /// never accumulates rounding errors. Changes made to the variants since
+    /// the layout was created are discarded.
+    pub fn resolve(&self, monitor_rect: position::Position) -> Self {
+        let variants = self
+            .user_variants
+            .map(|user_variant| user_variant.resolve(self.w, self.h, &monitor_rect));
+        return Self {
+            monitor_rect,
+            w: self.w,
+            h: self.h,
+            user_variants: self.user_variants.clone(),
+            variants,
+            default_variant_idx: self.default_variant_idx.clone(),
+            min_w: self.min_w,
+            min_h: self.min_h,
+            overflow: self.overflow.clone(),
+            padding: self.padding.clone(),
+        };
+    }
+
+    /// Like `resolve`, but keeps the changes made to the variants, which are
+    /// mapped from the current monitor rect onto `monitor_rect`.
+    pub fn moved_to(&self, monitor_rect: position::Position) -> Self {
+        let mut layout = Self::from(user_layout::UserLayout::from(self)).resolve(monitor_rect);
+        layout.user_variants = self.user_variants.clone();
+        return layout;
+    }
+
+    /// Checks every variant of the layout for problems that would otherwise
+    /// cause a panic when windows are tiled with it.
+    pub fn validate(&self) -> Result<(), Vec<validation::ValidationError>> {
+        let mut errors = Vec::new();
+        if !(self.w > 0.0 && self.h > 0.0) {
+            errors.push(validation::ValidationError {
+                variant_idx: Vec::new(),
+                window_count: None,
+                position_idx: None,
+                kind: validation::ValidationErrorKind::InvalidSize,
+            });
+        }
+        if let overflow::OverflowPolicy::Variant(variant_idx) = &self.overflow {
+            if !self.user_variants.variant_idxs().contains(variant_idx) {
+                errors.push(validation::ValidationError {
+                    variant_idx: Vec::new(),
+                    window_count: None,
+                    position_idx: None,
+                    kind: validation::ValidationErrorKind::MissingOverflowVariant {
+                        variant_idx: variant_idx.to_owned(),
+                    },
+                });
+            }
+        }
+        let mut stack = vec![(Vec::new(), &self.user_variants)];
+        while let Some((current_idx, container)) = stack.pop() {
+            if container.is_empty() {
+                errors.push(validation::ValidationError {
+                    variant_idx: current_idx.clone(),
+                    window_count: None,
+                    position_idx: None,
+                    kind: validation::ValidationErrorKind::EmptyContainer,
+                });
+            }
+            match container {
+                variants_container::VariantsContainer::Container(inner) => {
+                    for (i, inner_container) in inner.iter().enumerate().rev() {
+                        stack.push(([current_idx.as_slice(), &[i]].concat(), inner_container));
+                    }
+                }
+                variants_container::VariantsContainer::Variants(inner) => {
+                    for (i, user_variant) in inner.iter().enumerate() {
+                        errors.append(&mut validation::validate_variant(
+                            user_variant,
+                            self.w,
+                            self.h,
+                            &[current_idx.as_slice(), &[i]].concat(),
+                        ));
+                    }
+                }
+            }
+        }
+        if errors.is_empty() {
+            return Ok(());
+        } else {
+            return Err(errors);
+        }
+    }
+
+    /// Writes the layout, including any changes made to its variants, as the
+    /// JSON of a layout file.
+    pub fn to_json(&self) -> String {
+        return serde_json::to_string_pretty(&user_layout::UserLayout::from(self)).unwrap();
+    }
+
+    pub fn update_all(&mut self, padding: &padding::Padding) {
         self.variants.callback_all(|variant| {
-            variant.update(window_padding, edge_padding, &self.monitor_rect);
+            variant.update(padding, &self.monitor_rect);
         });
     }
 
@@ -57,20 +198,155 @@ impl Layout {
             .get_innermost_mut(variant_idx)
             .get_internal_positions(n, window_padding, edge_padding, &self.monitor_rect)
     }
+
+    pub fn get_weighted_internal_positions(
+        &mut self,
+        variant_idx: &[usize],
+        n: usize,
+        weights: &[f64],
+        padding: &padding::Padding,
+    ) -> Vec<position::Position> {
+        self.variants
+            .get_innermost_mut(variant_idx)
+            .get_weighted_internal_positions(n, weights, padding, &self.monitor_rect)
+    }
+
+    /// Like `get_weighted_internal_positions`, but with the windows that would
+    /// be smaller than the layout's minimum size or their own size in
+    /// `min_sizes` handled by the layout's overflow policy. Windows that are
+    /// left untiled have no position.
+    pub fn get_fitted_internal_positions(
+        &mut self,
+        variant_idx: &[usize],
+        n: usize,
+        weights: &[f64],
+        min_sizes: &[(i32, i32)],
+        padding: &padding::Padding,
+    ) -> Vec<Option<position::Position>> {
+        let positions = self.get_weighted_internal_positions(variant_idx, n, weights, padding);
+        if n < 2 || overflow::fits(&positions, min_sizes, self.min_size(), n) {
+            return positions.into_iter().map(Some).collect();
+        }
+        match self.overflow.to_owned() {
+            overflow::OverflowPolicy::Ignore => {
+                return positions.into_iter().map(Some).collect();
+            }
+            overflow::OverflowPolicy::Monocle => {
+                return self.get_stacked_internal_positions(
+                    variant_idx,
+                    n,
+                    weights,
+                    min_sizes,
+                    padding,
+                );
+            }
+            overflow::OverflowPolicy::Variant(other_idx) => {
+                let positions =
+                    self.get_weighted_internal_positions(&other_idx, n, weights, padding);
+                if overflow::fits(&positions, min_sizes, self.min_size(), n) {
+                    return positions.into_iter().map(Some).collect();
+                }
+                return self
+                    .get_stacked_internal_positions(&other_idx, n, weights, min_sizes, padding);
+            }
+            overflow::OverflowPolicy::Float => {
+                let mut k = n - 1;
+                let mut positions = Vec::new();
+                while k > 0 {
+                    positions =
+                        self.get_weighted_internal_positions(variant_idx, k, weights, padding);
+                    if k == 1 || overflow::fits(&positions, min_sizes, self.min_size(), k) {
+                        break;
+                    }
+                    k -= 1;
+                }
+                let mut ret: Vec<Option<position::Position>> =
+                    positions.into_iter().map(Some).collect();
+                ret.resize(n, None);
+                return ret;
+            }
+        }
+    }
+
+    /// Tiles the first windows with as many tiles as fit, with the rest of the
+    /// windows stacked in the last tile.
+    fn get_stacked_internal_positions(
+        &mut self,
+        variant_idx: &[usize],
+        n: usize,
+        weights: &[f64],
+        min_sizes: &[(i32, i32)],
+        padding: &padding::Padding,
+    ) -> Vec<Option<position::Position>> {
+        let mut k = n - 1;
+        let mut positions = Vec::new();
+        while k > 0 {
+            positions = self.get_weighted_internal_positions(
+                variant_idx,
+                k,
+                &weights[..std::cmp::min(k, weights.len())],
+                padding,
+            );
+            if k == 1 || overflow::fits(&positions, min_sizes, self.min_size(), n) {
+                break;
+            }
+            k -= 1;
+        }
+        return (0..n)
+            .map(|i| Some(positions[std::cmp::min(i, k - 1)].to_owned()))
+            .collect();
+    }
 }
 
-impl TryFrom<user_layout::UserLayout<'_>> for Layout {
-    type Error = serde_json::Error;
-
-    fn try_from(value: user_layout::UserLayout) -> Result<Self, Self::Error> {
-        let user_variants =
-            variants_container::VariantsContainer::<user_layout::UserVariant>::from_raw_value(
-                &value.variants,
-            )?;
-        Ok(Self {
-            monitor_rect: position::Position::new(0, 0, value.w, value.h),
-            variants: user_variants.map(variant::Variant::from),
+impl From<user_layout::UserLayout> for Layout {
+    fn from(value: user_layout::UserLayout) -> Self {
+        let layout = Self {
+            monitor_rect: position::Position::new(0, 0, 0, 0),
+            w: value.w,
+            h: value.h,
+            user_variants: value.variants,
+            variants: variants_container::VariantsContainer::Variants(Vec::new()),
             default_variant_idx: value.default_variant_idx,
-        })
+            min_w: value.min_w,
+            min_h: value.min_h,
+            overflow: value.overflow,
+            padding: value.padding,
+        };
+        return layout.resolve(position::Position::new(
+            0,
+            0,
+            value.w.round() as i32,
+            value.h.round() as i32,
+        ));
+    }
+}
+
+impl From<&Layout> for user_layout::UserLayout {
+    fn from(value: &Layout) -> Self {
+        let variants = value.variants.map_with_idx(|idx, variant| {
+            let original = match value.user_variants.get(idx) {
+                variants_container::VariantsContainerReturn::Variant(user_variant) => {
+                    Some(user_variant)
+                }
+                variants_container::VariantsContainerReturn::Container(_) => None,
+            };
+            user_layout::UserVariant::from_variant(
+                &variant,
+                value.w,
+                value.h,
+                &value.monitor_rect,
+                original,
+            )
+        });
+        return Self {
+            w: value.w,
+            h: value.h,
+            default_variant_idx: value.default_variant_idx.clone(),
+            min_w: value.min_w,
+            min_h: value.min_h,
+            overflow: value.overflow.clone(),
+            padding: value.padding.clone(),
+            variants,
+        };
     }
 }
@@ -1,5 +1,11 @@
 pub mod layout;
+pub mod neighbours;
+pub mod overflow;
+pub mod padding;
 pub mod position;
+pub mod render;
 pub mod user_layout;
+pub mod validation;
 pub mod variant;
 pub mod variants_container;
+pub mod weights;
@@ -0,0 +1,133 @@
+use himewm_layout::{layout, position, render, user_layout};
+
+const USAGE: &str = "Usage: himewm-layout <layout file> [options]
+
+Renders every variant of a layout for 1 to N windows.
+
+Options:
+    --windows <N>            Render up to N windows (default: 5)
+    --size <W>x<H>           Size of the monitor (default: 1920x1080)
+    --window-padding <PX>    Padding between windows (default: 0)
+    --edge-padding <PX>      Padding at the edges of the monitor (default: 0)
+    --svg <DIR>              Write SVG files to DIR instead of printing ASCII";
+
+struct Args {
+    path: std::path::PathBuf,
+    windows: usize,
+    w: i32,
+    h: i32,
+    svg_dir: Option<std::path::PathBuf>,
+    options: render::RenderOptions,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut path = None;
+    let mut windows = 5;
+    let mut w = 1920;
+    let mut h = 1080;
+    let mut svg_dir = None;
+    let mut options = render::RenderOptions::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--windows" => {
+                windows = value()?.parse().map_err(|_| "Invalid window count")?;
+            }
+            "--size" => {
+                let size = value()?;
+                let (w_str, h_str) = size.split_once('x').ok_or("Invalid size")?;
+                w = w_str.parse().map_err(|_| "Invalid size")?;
+                h = h_str.parse().map_err(|_| "Invalid size")?;
+            }
+            "--window-padding" => {
+                options.window_padding = value()?.parse().map_err(|_| "Invalid padding")?;
+            }
+            "--edge-padding" => {
+                options.edge_padding = value()?.parse().map_err(|_| "Invalid padding")?;
+            }
+            "--svg" => {
+                svg_dir = Some(std::path::PathBuf::from(value()?));
+            }
+            "-h" | "--help" => {
+                return Err(String::new());
+            }
+            _ if path.is_none() && !arg.starts_with("--") => {
+                path = Some(std::path::PathBuf::from(arg));
+            }
+            _ => {
+                return Err(format!("Unexpected argument {}", arg));
+            }
+        }
+    }
+    if w <= 0 || h <= 0 {
+        return Err(String::from("Invalid size"));
+    }
+    return Ok(Args {
+        path: path.ok_or("Missing layout file")?,
+        windows,
+        w,
+        h,
+        svg_dir,
+        options,
+    });
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let byte_vector = std::fs::read(&args.path).map_err(|e| e.to_string())?;
+    let user_layout: user_layout::UserLayout =
+        serde_json::from_slice(byte_vector.as_slice()).map_err(|e| e.to_string())?;
+    let mut layout =
+        layout::Layout::from(user_layout).resolve(position::Position::new(0, 0, args.w, args.h));
+    match layout.validate() {
+        Ok(_) => (),
+        Err(errors) => {
+            let messages: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+            return Err(messages.join("\n"));
+        }
+    }
+    if let Some(dir) = &args.svg_dir {
+        std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    }
+    for variant_idx in layout.variants().variant_idxs() {
+        for n in 1..=args.windows {
+            match &args.svg_dir {
+                Some(dir) => {
+                    let idx_strings: Vec<String> =
+                        variant_idx.iter().map(|i| i.to_string()).collect();
+                    let file_name = format!("variant_{}_{}.svg", idx_strings.join("_"), n);
+                    let svg = render::svg(&mut layout, &variant_idx, n, &args.options);
+                    std::fs::write(dir.join(file_name), svg).map_err(|e| e.to_string())?;
+                }
+                None => {
+                    println!("variant {:?}, {} window(s)", variant_idx, n);
+                    println!(
+                        "{}\n",
+                        render::ascii(&mut layout, &variant_idx, n, &args.options)
+                    );
+                }
+            }
+        }
+    }
+    return Ok(());
+}
+
+fn main() {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(e) => {
+            if !e.is_empty() {
+                eprintln!("{}\n", e);
+            }
+            eprintln!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
+    match run(args) {
+        Ok(_) => (),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
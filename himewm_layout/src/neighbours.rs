@@ -0,0 +1,53 @@
+use crate::{position, variant::Direction};
+
+/// The start and end of a position across `direction`.
+fn cross_span(position: &position::Position, direction: &Direction) -> (i32, i32) {
+    match direction {
+        Direction::Left | Direction::Right => {
+            return (position.y(), position.y() + position.h());
+        }
+        Direction::Up | Direction::Down => {
+            return (position.x(), position.x() + position.w());
+        }
+    }
+}
+
+/// How far `to` is past `from` in `direction`, or `None` if it is not
+/// entirely past it.
+fn gap(from: &position::Position, to: &position::Position, direction: &Direction) -> Option<i32> {
+    let gap = match direction {
+        Direction::Left => from.x() - (to.x() + to.w()),
+        Direction::Right => to.x() - (from.x() + from.w()),
+        Direction::Up => from.y() - (to.y() + to.h()),
+        Direction::Down => to.y() - (from.y() + from.h()),
+    };
+    if gap < 0 {
+        return None;
+    }
+    return Some(gap);
+}
+
+/// The index of the position in `candidates` that is nearest to `from` in
+/// `direction`. Positions that line up with `from` across the direction are
+/// preferred, then the closest ones, then the ones whose centres are closest
+/// across the direction.
+pub fn nearest_in_direction(
+    from: &position::Position,
+    candidates: &[Option<position::Position>],
+    direction: &Direction,
+) -> Option<usize> {
+    let (from_start, from_end) = cross_span(from, direction);
+    return candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, candidate)| {
+            let candidate = candidate.as_ref()?;
+            let gap = gap(from, candidate, direction)?;
+            let (start, end) = cross_span(candidate, direction);
+            let overlaps = std::cmp::min(end, from_end) > std::cmp::max(start, from_start);
+            let centre_distance = ((start + end) - (from_start + from_end)).abs();
+            Some((!overlaps, gap, centre_distance, i))
+        })
+        .min()
+        .map(|(_, _, _, i)| i);
+}
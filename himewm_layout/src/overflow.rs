@@ -0,0 +1,48 @@
+use crate::position;
+use serde::{Deserialize, Serialize};
+
+/// What happens to the windows that would make tiles smaller than their
+/// minimum sizes.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub enum OverflowPolicy {
+    /// Tile every window regardless.
+    #[default]
+    Ignore,
+    /// Stack the windows that do not fit in the last tile that does.
+    Monocle,
+    /// Tile the windows with another variant of the layout, stacking them if
+    /// they still do not fit.
+    Variant(Vec<usize>),
+    /// Leave the windows that do not fit untiled.
+    Float,
+}
+
+impl OverflowPolicy {
+    pub fn is_ignore(&self) -> bool {
+        return self == &OverflowPolicy::Ignore;
+    }
+}
+
+/// Whether the first `n` windows fit in `positions`, where the windows past
+/// the last position are stacked in it. Each window needs at least its own
+/// minimum size in `min_sizes` (or none if it is missing) and `layout_min`.
+pub fn fits(
+    positions: &[position::Position],
+    min_sizes: &[(i32, i32)],
+    layout_min: (i32, i32),
+    n: usize,
+) -> bool {
+    if positions.is_empty() {
+        return n == 0;
+    }
+    for i in 0..n {
+        let tile = &positions[std::cmp::min(i, positions.len() - 1)];
+        let (min_w, min_h) = min_sizes.get(i).copied().unwrap_or((0, 0));
+        if tile.w() < std::cmp::max(min_w, layout_min.0)
+            || tile.h() < std::cmp::max(min_h, layout_min.1)
+        {
+            return false;
+        }
+    }
+    return true;
+}
@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+/// The space left around tiles, in pixels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Padding {
+    /// The padding on the left and right of tiles, on sides that are next to
+    /// another tile.
+    pub horizontal: i32,
+    /// The padding on the top and bottom of tiles, on sides that are next to
+    /// another tile.
+    pub vertical: i32,
+    /// The padding at each edge of the monitor.
+    pub top: i32,
+    pub bottom: i32,
+    pub left: i32,
+    pub right: i32,
+}
+
+impl Default for Padding {
+    fn default() -> Self {
+        return Self::uniform(0, 0);
+    }
+}
+
+impl Padding {
+    pub fn uniform(window_padding: i32, edge_padding: i32) -> Self {
+        return Self {
+            horizontal: window_padding,
+            vertical: window_padding,
+            top: edge_padding,
+            bottom: edge_padding,
+            left: edge_padding,
+            right: edge_padding,
+        };
+    }
+}
+
+/// Changes to some sides of a `Padding`, where sides that are not given are
+/// left as they are.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct PaddingOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub horizontal: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vertical: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bottom: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub left: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub right: Option<i32>,
+}
+
+impl PaddingOverrides {
+    pub fn is_empty(&self) -> bool {
+        return self == &PaddingOverrides::default();
+    }
+
+    /// Combines two sets of changes, where the sides given by `other` take
+    /// precedence.
+    pub fn overridden_by(&self, other: &PaddingOverrides) -> PaddingOverrides {
+        return PaddingOverrides {
+            horizontal: other.horizontal.or(self.horizontal),
+            vertical: other.vertical.or(self.vertical),
+            top: other.top.or(self.top),
+            bottom: other.bottom.or(self.bottom),
+            left: other.left.or(self.left),
+            right: other.right.or(self.right),
+        };
+    }
+
+    pub fn apply(&self, padding: &Padding) -> Padding {
+        return Padding {
+            horizontal: self.horizontal.unwrap_or(padding.horizontal),
+            vertical: self.vertical.unwrap_or(padding.vertical),
+            top: self.top.unwrap_or(padding.top),
+            bottom: self.bottom.unwrap_or(padding.bottom),
+            left: self.left.unwrap_or(padding.left),
+            right: self.right.unwrap_or(padding.right),
+        };
+    }
+}
@@ -1,9 +1,82 @@
 use serde::{Deserialize, Serialize};
+#[cfg(windows)]
 use windows::Win32::Foundation::*;
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Position(i32, i32, i32, i32);
 
+/// A position in the units of a layout, which are relative to the `w` and `h`
+/// of the layout. These can be pixels, fractions of the monitor or any other
+/// grid.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct UnitPosition(f64, f64, f64, f64);
+
+impl From<(f64, f64, f64, f64)> for UnitPosition {
+    fn from(value: (f64, f64, f64, f64)) -> Self {
+        Self(value.0, value.1, value.2, value.3)
+    }
+}
+
+impl From<&Position> for UnitPosition {
+    fn from(value: &Position) -> Self {
+        Self(
+            value.x() as f64,
+            value.y() as f64,
+            value.w() as f64,
+            value.h() as f64,
+        )
+    }
+}
+
+impl UnitPosition {
+    pub fn new(x: f64, y: f64, w: f64, h: f64) -> Self {
+        Self(x, y, w, h)
+    }
+
+    pub fn x(&self) -> f64 {
+        self.0
+    }
+
+    pub fn y(&self) -> f64 {
+        self.1
+    }
+
+    pub fn w(&self) -> f64 {
+        self.2
+    }
+
+    pub fn h(&self) -> f64 {
+        self.3
+    }
+
+    /// Maps the position from a layout of size `w` by `h` onto `rect`.
+    ///
+    /// Each edge is rounded on its own rather than rounding the size, so tiles
+    /// that share an edge in layout units always share it in pixels.
+    pub fn resolve(&self, w: f64, h: f64, rect: &Position) -> Position {
+        let resolve_x = |x: f64| rect.x() + (x * rect.w() as f64 / w).round() as i32;
+        let resolve_y = |y: f64| rect.y() + (y * rect.h() as f64 / h).round() as i32;
+        let left = resolve_x(self.0);
+        let top = resolve_y(self.1);
+        let right = resolve_x(self.0 + self.2);
+        let bottom = resolve_y(self.1 + self.3);
+        return Position(left, top, right - left, bottom - top);
+    }
+
+    /// The inverse of `resolve`, mapping `position` on `rect` back into the
+    /// units of a layout of size `w` by `h`.
+    pub fn unresolve(position: &Position, w: f64, h: f64, rect: &Position) -> Self {
+        let unresolve_x = |x: i32| (x - rect.x()) as f64 * w / rect.w() as f64;
+        let unresolve_y = |y: i32| (y - rect.y()) as f64 * h / rect.h() as f64;
+        let left = unresolve_x(position.x());
+        let top = unresolve_y(position.y());
+        let right = unresolve_x(position.x() + position.w());
+        let bottom = unresolve_y(position.y() + position.h());
+        return Self(left, top, right - left, bottom - top);
+    }
+}
+
+#[cfg(windows)]
 impl From<RECT> for Position {
     fn from(value: RECT) -> Self {
         Self(
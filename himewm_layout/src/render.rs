@@ -0,0 +1,141 @@
+use crate::{layout, position};
+
+const COLOURS: [&str; 8] = [
+    "#e06c75", "#98c379", "#e5c07b", "#61afef", "#c678dd", "#56b6c2", "#d19a66", "#abb2bf",
+];
+
+#[derive(Clone, Debug)]
+pub struct RenderOptions {
+    pub window_padding: i32,
+    pub edge_padding: i32,
+    /// The width of ASCII renders in characters.
+    pub columns: usize,
+    /// The height of ASCII renders in lines.
+    pub rows: usize,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            window_padding: 0,
+            edge_padding: 0,
+            columns: 64,
+            rows: 18,
+        }
+    }
+}
+
+fn positions(
+    layout: &mut layout::Layout,
+    variant_idx: &[usize],
+    n: usize,
+    options: &RenderOptions,
+) -> Vec<position::Position> {
+    if n == 0 {
+        return Vec::new();
+    }
+    return layout
+        .get_internal_positions(variant_idx, n, options.window_padding, options.edge_padding)
+        .to_owned();
+}
+
+/// Draws the positions of `n` windows in a variant of `layout` as a grid of
+/// characters, with each window labelled by its index.
+pub fn ascii(
+    layout: &mut layout::Layout,
+    variant_idx: &[usize],
+    n: usize,
+    options: &RenderOptions,
+) -> String {
+    let positions = positions(layout, variant_idx, n, options);
+    let monitor_rect = layout.monitor_rect();
+    let columns = options.columns as i64;
+    let rows = options.rows as i64;
+    let column = |x: i32| {
+        ((x - monitor_rect.x()) as f64 * columns as f64 / monitor_rect.w() as f64).round() as i64
+    };
+    let row = |y: i32| {
+        ((y - monitor_rect.y()) as f64 * rows as f64 / monitor_rect.h() as f64).round() as i64
+    };
+    let mut canvas = vec![vec![' '; options.columns]; options.rows];
+    for (i, position) in positions.iter().enumerate() {
+        let left = column(position.x()).clamp(0, columns - 1);
+        let right = (column(position.x() + position.w()) - 1).clamp(0, columns - 1);
+        let top = row(position.y()).clamp(0, rows - 1);
+        let bottom = (row(position.y() + position.h()) - 1).clamp(0, rows - 1);
+        if right <= left || bottom <= top {
+            continue;
+        }
+        for x in left..=right {
+            canvas[top as usize][x as usize] = '-';
+            canvas[bottom as usize][x as usize] = '-';
+        }
+        for y in top..=bottom {
+            canvas[y as usize][left as usize] = '|';
+            canvas[y as usize][right as usize] = '|';
+        }
+        for (x, y) in [(left, top), (right, top), (left, bottom), (right, bottom)] {
+            canvas[y as usize][x as usize] = '+';
+        }
+        let label = i.to_string();
+        let label_len = label.len() as i64;
+        if right - left > label_len && bottom - top > 1 {
+            let label_x = left + 1 + (right - left - 1 - label_len) / 2;
+            let label_y = (top + bottom) / 2;
+            for (j, c) in label.chars().enumerate() {
+                canvas[label_y as usize][label_x as usize + j] = c;
+            }
+        }
+    }
+    return canvas
+        .iter()
+        .map(|line| line.iter().collect::<String>().trim_end().to_owned())
+        .collect::<Vec<String>>()
+        .join("\n");
+}
+
+/// Draws the positions of `n` windows in a variant of `layout` as an SVG
+/// image the size of the layout's monitor rect.
+pub fn svg(
+    layout: &mut layout::Layout,
+    variant_idx: &[usize],
+    n: usize,
+    options: &RenderOptions,
+) -> String {
+    let positions = positions(layout, variant_idx, n, options);
+    let monitor_rect = layout.monitor_rect();
+    let font_size = std::cmp::max(std::cmp::min(monitor_rect.w(), monitor_rect.h()) / 10, 1);
+    let mut ret = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"{x} {y} {w} {h}\">\n",
+        x = monitor_rect.x(),
+        y = monitor_rect.y(),
+        w = monitor_rect.w(),
+        h = monitor_rect.h(),
+    );
+    ret += &format!(
+        "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#282c34\"/>\n",
+        monitor_rect.x(),
+        monitor_rect.y(),
+        monitor_rect.w(),
+        monitor_rect.h(),
+    );
+    for (i, position) in positions.iter().enumerate() {
+        ret += &format!(
+            "  <rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\" stroke=\"#000000\"/>\n",
+            position.x(),
+            position.y(),
+            std::cmp::max(position.w(), 0),
+            std::cmp::max(position.h(), 0),
+            COLOURS[i % COLOURS.len()],
+        );
+        ret += &format!(
+            "  <text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"middle\">{}</text>\n",
+            position.x() + position.w() / 2,
+            position.y() + position.h() / 2,
+            font_size,
+            i,
+        );
+    }
+    ret += "</svg>\n";
+    return ret;
+}
@@ -1,18 +1,120 @@
-use crate::{position, variant};
+use crate::{overflow, padding, position, variant, variants_container};
 use serde::{Deserialize, Serialize};
-use serde_json::value::RawValue;
 
 #[derive(Deserialize, Serialize)]
-pub struct UserLayout<'a> {
-    pub w: i32,
-    pub h: i32,
+pub struct UserLayout {
+    pub w: f64,
+    pub h: f64,
     pub default_variant_idx: Vec<usize>,
-    #[serde(borrow)]
-    pub variants: &'a RawValue,
+    pub variants: variants_container::VariantsContainer<UserVariant>,
+    /// The minimum width of a tile, in the same units as `w`.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub min_w: f64,
+    /// The minimum height of a tile, in the same units as `h`.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub min_h: f64,
+    #[serde(default, skip_serializing_if = "overflow::OverflowPolicy::is_ignore")]
+    pub overflow: overflow::OverflowPolicy,
+    #[serde(default, skip_serializing_if = "padding::PaddingOverrides::is_empty")]
+    pub padding: padding::PaddingOverrides,
+}
+
+fn is_zero(value: &f64) -> bool {
+    return *value == 0.0;
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct UserEndBehaviour {
+    pub from: Option<Vec<position::UnitPosition>>,
+    pub position_idx: usize,
+    pub behaviour: variant::EndBehaviourType,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct UserVariant {
-    pub positions: Vec<Vec<position::Position>>,
-    pub end_behaviour: variant::EndBehaviour,
+    pub positions: Vec<Vec<position::UnitPosition>>,
+    pub end_behaviour: UserEndBehaviour,
+}
+
+impl UserVariant {
+    /// Creates a variant for a layout of size `w` by `h` that has been placed
+    /// on `monitor_rect`.
+    pub fn resolve(&self, w: f64, h: f64, monitor_rect: &position::Position) -> variant::Variant {
+        let resolve_all = |positions: &Vec<position::UnitPosition>| {
+            positions
+                .iter()
+                .map(|p| p.resolve(w, h, monitor_rect))
+                .collect::<Vec<position::Position>>()
+        };
+        let positions = self.positions.iter().map(resolve_all).collect();
+        let end_behaviour = variant::EndBehaviour::new(
+            self.end_behaviour.from.as_ref().map(resolve_all),
+            self.end_behaviour.position_idx,
+            self.end_behaviour.behaviour.to_owned(),
+        );
+        return variant::Variant::new(positions, end_behaviour);
+    }
+
+    /// Creates a variant for a layout of size `w` by `h` from `variant`, which
+    /// has been placed on `monitor_rect`.
+    ///
+    /// Positions that are unchanged from `original` are kept as they were
+    /// written. Positions generated by the end behaviour are written out as
+    /// well, with the positions they were generated from kept in `from` so
+    /// that the variant keeps extending in the same way, except for `Repeating`
+    /// end behaviours which depend on how many positions were written.
+    pub fn from_variant(
+        variant: &variant::Variant,
+        w: f64,
+        h: f64,
+        monitor_rect: &position::Position,
+        original: Option<&UserVariant>,
+    ) -> Self {
+        let unit_position =
+            |p: &position::Position, original: Option<&position::UnitPosition>| match original {
+                Some(unit_position) if &unit_position.resolve(w, h, monitor_rect) == p => {
+                    return unit_position.clone();
+                }
+                _ => return position::UnitPosition::unresolve(p, w, h, monitor_rect),
+            };
+        let unit_positions =
+            |positions: &Vec<position::Position>,
+             original: Option<&Vec<position::UnitPosition>>| {
+                positions
+                    .iter()
+                    .enumerate()
+                    .map(|(j, p)| unit_position(p, original.and_then(|o| o.get(j))))
+                    .collect::<Vec<position::UnitPosition>>()
+            };
+        let end_behaviour = variant.end_behaviour();
+        let manual_positions_until = variant.manual_positions_until();
+        let positions_until = match end_behaviour.behaviour() {
+            variant::EndBehaviourType::Repeating { .. } => manual_positions_until,
+            _ => variant.positions().len(),
+        };
+        let positions = variant.positions()[..positions_until]
+            .iter()
+            .enumerate()
+            .map(|(i, positions)| {
+                unit_positions(positions, original.and_then(|o| o.positions.get(i)))
+            })
+            .collect();
+        let original_from = original.and_then(|o| o.end_behaviour.from.as_ref());
+        let from = match end_behaviour.from() {
+            Some(from) => Some(unit_positions(from, original_from)),
+            None if positions_until > manual_positions_until => Some(unit_positions(
+                &variant.positions()[manual_positions_until - 1],
+                original.and_then(|o| o.positions.get(manual_positions_until - 1)),
+            )),
+            None => None,
+        };
+        return Self {
+            positions,
+            end_behaviour: UserEndBehaviour {
+                from,
+                position_idx: end_behaviour.position_idx(),
+                behaviour: end_behaviour.behaviour().to_owned(),
+            },
+        };
+    }
 }
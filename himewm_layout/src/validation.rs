@@ -0,0 +1,232 @@
+use crate::{position, user_layout, variant};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationErrorKind {
+    EmptyContainer,
+    NoPositions,
+    WrongPositionCount { found: usize },
+    EmptyPosition,
+    OutOfBounds,
+    Overlap { other_idx: usize },
+    PositionIdxOutOfRange { len: usize },
+    TooManyFromPositions { max: usize },
+    EmptySplits,
+    InvalidSplitRatio { split_idx: usize },
+    InvalidSplitOffset { split_idx: usize },
+    InvalidRatio,
+    InvalidSize,
+    MissingOverflowVariant { variant_idx: Vec<usize> },
+}
+
+/// A problem with a layout that would make it fail when it is used for tiling.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationError {
+    /// The path of the variant (or container) in the layout's variants, which
+    /// is empty for problems with the layout as a whole.
+    pub variant_idx: Vec<usize>,
+    /// The window count of the offending positions, or `None` if they are the
+    /// `from` positions of the end behaviour.
+    pub window_count: Option<usize>,
+    pub position_idx: Option<usize>,
+    pub kind: ValidationErrorKind,
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.variant_idx.is_empty() {
+            write!(f, "layout")?;
+        } else {
+            write!(f, "variant {:?}", self.variant_idx)?;
+        }
+        match self.window_count {
+            Some(n) => write!(f, ", {} window(s)", n)?,
+            None if self.position_idx.is_some() => write!(f, ", end behaviour `from`")?,
+            None => (),
+        }
+        if let Some(idx) = self.position_idx {
+            write!(f, ", position {}", idx)?;
+        }
+        match &self.kind {
+            ValidationErrorKind::EmptyContainer => write!(f, ": contains no variants"),
+            ValidationErrorKind::NoPositions => write!(f, ": has no positions"),
+            ValidationErrorKind::WrongPositionCount { found } => {
+                write!(f, ": contains {} position(s)", found)
+            }
+            ValidationErrorKind::EmptyPosition => write!(f, ": has no width or height"),
+            ValidationErrorKind::OutOfBounds => write!(f, ": is outside of the layout's w and h"),
+            ValidationErrorKind::Overlap { other_idx } => {
+                write!(f, ": overlaps position {}", other_idx)
+            }
+            ValidationErrorKind::PositionIdxOutOfRange { len } => write!(
+                f,
+                ": end behaviour position_idx is out of range for {} position(s)",
+                len
+            ),
+            ValidationErrorKind::TooManyFromPositions { max } => {
+                write!(
+                    f,
+                    ": end behaviour `from` has more than {} position(s)",
+                    max
+                )
+            }
+            ValidationErrorKind::EmptySplits => {
+                write!(f, ": Repeating end behaviour has no splits")
+            }
+            ValidationErrorKind::InvalidSplitRatio { split_idx } => {
+                write!(f, ": ratio of split {} is not between 0 and 1", split_idx)
+            }
+            ValidationErrorKind::InvalidSplitOffset { split_idx } => {
+                write!(f, ": offset of split {} is out of range", split_idx)
+            }
+            ValidationErrorKind::InvalidRatio => {
+                write!(f, ": end behaviour ratio is not between 0 and 1")
+            }
+            ValidationErrorKind::InvalidSize => {
+                write!(f, ": w and h are not greater than 0")
+            }
+            ValidationErrorKind::MissingOverflowVariant { variant_idx } => {
+                write!(
+                    f,
+                    ": overflow policy uses variant {:?}, which does not exist",
+                    variant_idx
+                )
+            }
+        }
+    }
+}
+
+fn overlaps(a: &position::UnitPosition, b: &position::UnitPosition, epsilon: f64) -> bool {
+    return a.x() < b.x() + b.w() - epsilon
+        && b.x() < a.x() + a.w() - epsilon
+        && a.y() < b.y() + b.h() - epsilon
+        && b.y() < a.y() + a.h() - epsilon;
+}
+
+fn validate_positions(
+    positions: &[position::UnitPosition],
+    w: f64,
+    h: f64,
+    error: &dyn Fn(usize, ValidationErrorKind) -> ValidationError,
+) -> Vec<ValidationError> {
+    // Positions that share an edge may not add up exactly in floating point
+    let epsilon = f64::max(w, h) * 1e-9;
+    let mut ret = Vec::new();
+    for (i, position) in positions.iter().enumerate() {
+        if !(position.w() > 0.0 && position.h() > 0.0) {
+            ret.push(error(i, ValidationErrorKind::EmptyPosition));
+            continue;
+        }
+        if position.x() < -epsilon
+            || position.y() < -epsilon
+            || position.x() + position.w() > w + epsilon
+            || position.y() + position.h() > h + epsilon
+        {
+            ret.push(error(i, ValidationErrorKind::OutOfBounds));
+        }
+        for (j, other) in positions.iter().enumerate().take(i) {
+            if overlaps(position, other, epsilon) {
+                ret.push(error(i, ValidationErrorKind::Overlap { other_idx: j }));
+            }
+        }
+    }
+    return ret;
+}
+
+/// Checks a variant of a layout of size `w` by `h`.
+pub fn validate_variant(
+    user_variant: &user_layout::UserVariant,
+    w: f64,
+    h: f64,
+    variant_idx: &[usize],
+) -> Vec<ValidationError> {
+    let mut ret = Vec::new();
+    let error = |window_count: Option<usize>, position_idx: Option<usize>, kind| ValidationError {
+        variant_idx: Vec::from(variant_idx),
+        window_count,
+        position_idx,
+        kind,
+    };
+    if user_variant.positions.is_empty() {
+        ret.push(error(None, None, ValidationErrorKind::NoPositions));
+    }
+    for (i, positions) in user_variant.positions.iter().enumerate() {
+        if positions.len() != i + 1 {
+            ret.push(error(
+                Some(i + 1),
+                None,
+                ValidationErrorKind::WrongPositionCount {
+                    found: positions.len(),
+                },
+            ));
+        }
+        ret.append(&mut validate_positions(positions, w, h, &|j, kind| {
+            error(Some(i + 1), Some(j), kind)
+        }));
+    }
+    let end_behaviour = &user_variant.end_behaviour;
+    let base = match &end_behaviour.from {
+        Some(from) => {
+            let max = user_variant.positions.len() + 1;
+            if from.len() > max {
+                ret.push(error(
+                    None,
+                    None,
+                    ValidationErrorKind::TooManyFromPositions { max },
+                ));
+            }
+            ret.append(&mut validate_positions(from, w, h, &|j, kind| {
+                error(None, Some(j), kind)
+            }));
+            Some(from)
+        }
+        None => user_variant.positions.last(),
+    };
+    match base {
+        Some(positions) if end_behaviour.position_idx >= positions.len() => {
+            ret.push(error(
+                None,
+                None,
+                ValidationErrorKind::PositionIdxOutOfRange {
+                    len: positions.len(),
+                },
+            ));
+        }
+        _ => (),
+    }
+    match &end_behaviour.behaviour {
+        variant::EndBehaviourType::Repeating { splits } => {
+            if splits.is_empty() {
+                ret.push(error(None, None, ValidationErrorKind::EmptySplits));
+            }
+            for (i, split) in splits.iter().enumerate() {
+                if !(split.ratio() > 0.0 && split.ratio() < 1.0) {
+                    ret.push(error(
+                        None,
+                        None,
+                        ValidationErrorKind::InvalidSplitRatio { split_idx: i },
+                    ));
+                }
+                let max_offset = if i == 0 { splits.len() } else { i };
+                if split.offset() > max_offset {
+                    ret.push(error(
+                        None,
+                        None,
+                        ValidationErrorKind::InvalidSplitOffset { split_idx: i },
+                    ));
+                }
+            }
+        }
+        variant::EndBehaviourType::MasterStack {
+            master_ratio: ratio,
+            ..
+        }
+        | variant::EndBehaviourType::Spiral { ratio, .. }
+        | variant::EndBehaviourType::Dwindle { ratio, .. }
+            if !(*ratio > 0.0 && *ratio < 1.0) =>
+        {
+            ret.push(error(None, None, ValidationErrorKind::InvalidRatio));
+        }
+        _ => (),
+    }
+    return ret;
+}
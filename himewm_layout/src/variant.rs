@@ -1,7 +1,7 @@
-use crate::{position, user_layout};
+use crate::{padding, position, user_layout, weights};
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 pub enum Direction {
     Up,
     Down,
@@ -18,22 +18,95 @@ impl Direction {
             Self::Right => Self::Left,
         }
     }
+
+    pub fn clockwise(&self) -> Self {
+        match self {
+            Self::Up => Self::Right,
+            Self::Right => Self::Down,
+            Self::Down => Self::Left,
+            Self::Left => Self::Up,
+        }
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum EndBehaviourType {
-    Directional { direction: Direction },
-    Repeating { splits: Vec<RepeatingSplit> },
+    Directional {
+        direction: Direction,
+    },
+    Repeating {
+        splits: Vec<RepeatingSplit>,
+    },
+    MasterStack {
+        master_count: usize,
+        master_ratio: f64,
+        stack_direction: Direction,
+    },
+    /// Each window takes `ratio` of the remaining space, which moves around
+    /// the screen clockwise starting from `direction`.
+    Spiral {
+        ratio: f64,
+        direction: Direction,
+    },
+    /// Each window takes `ratio` of the remaining space, which alternates
+    /// between moving in `direction` and the direction clockwise of it.
+    Dwindle {
+        ratio: f64,
+        direction: Direction,
+    },
+    /// Arranges the windows in a grid of equal cells that is recomputed for
+    /// every window count. The grid is as close to square as possible unless
+    /// `columns` or `rows` is set, with `columns` taking precedence.
+    Grid {
+        #[serde(default)]
+        columns: Option<usize>,
+        #[serde(default)]
+        rows: Option<usize>,
+        #[serde(default)]
+        orientation: Orientation,
+    },
 }
 
+/// The order in which the cells of a grid are filled. The last row (or
+/// column) is stretched if it is not full.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub enum Orientation {
+    /// Fill each row from left to right.
+    #[default]
+    Horizontal,
+    /// Fill each column from top to bottom.
+    Vertical,
+}
+
+pub const MIN_MASTER_RATIO: f64 = 0.05;
+
+pub const MAX_MASTER_RATIO: f64 = 0.95;
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct EndBehaviour {
+    /// The positions that arrangements for more windows than were written are
+    /// generated from, keeping every position other than `position_idx`. If
+    /// this is `None`, the last written positions are used. A `Repeating` end
+    /// behaviour with `from` set regenerates its first arrangement from it and
+    /// extends that arrangement afterwards.
     from: Option<Vec<position::Position>>,
     position_idx: usize,
     behaviour: EndBehaviourType,
 }
 
 impl EndBehaviour {
+    pub fn new(
+        from: Option<Vec<position::Position>>,
+        position_idx: usize,
+        behaviour: EndBehaviourType,
+    ) -> Self {
+        Self {
+            from,
+            position_idx,
+            behaviour,
+        }
+    }
+
     pub fn from(&self) -> &Option<Vec<position::Position>> {
         &self.from
     }
@@ -41,12 +114,25 @@ impl EndBehaviour {
     pub fn from_mut(&mut self) -> &mut Option<Vec<position::Position>> {
         &mut self.from
     }
+
+    pub fn position_idx(&self) -> usize {
+        self.position_idx
+    }
+
+    pub fn behaviour(&self) -> &EndBehaviourType {
+        &self.behaviour
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct RepeatingSplit {
     direction: Direction,
     ratio: f64,
+    /// Which tile is split, relative to the current round of splits: `0` is the
+    /// last tile created before the round began and `k` is the tile created by
+    /// split `k - 1`. The first split of a round looks at the previous round
+    /// instead, so this can be at most the index of the split, or the number of
+    /// splits for the first one.
     offset: usize,
 }
 
@@ -58,6 +144,24 @@ impl RepeatingSplit {
             offset,
         }
     }
+
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+/// A resize made to an arrangement that was generated by the end behaviour,
+/// which is made again whenever the arrangement is generated again.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct GeneratedResize {
+    n: usize,
+    idx: usize,
+    direction: Direction,
+    delta: i32,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -66,22 +170,238 @@ pub struct Variant {
     internal_positions: Vec<Vec<position::Position>>,
     manual_positions_until: usize,
     end_behaviour: EndBehaviour,
+    #[serde(default)]
+    generated_resizes: Vec<GeneratedResize>,
 }
 
 impl From<user_layout::UserVariant> for Variant {
+    /// Treats the units of the variant as pixels.
     fn from(value: user_layout::UserVariant) -> Self {
-        let positions_len = value.positions.len();
+        return value.resolve(1.0, 1.0, &position::Position::new(0, 0, 1, 1));
+    }
+}
+
+/// Shrinks a position by the inner padding, or by the edge padding on the
+/// sides that touch the edges of `monitor_rect`.
+fn internal_position(
+    position: &position::Position,
+    padding: &padding::Padding,
+    monitor_rect: &position::Position,
+) -> position::Position {
+    let left = if position.x() == monitor_rect.x() {
+        padding.left
+    } else {
+        padding.horizontal
+    };
+    let right = if position.x() + position.w() == monitor_rect.x() + monitor_rect.w() {
+        padding.right
+    } else {
+        padding.horizontal
+    };
+    let top = if position.y() == monitor_rect.y() {
+        padding.top
+    } else {
+        padding.vertical
+    };
+    let bottom = if position.y() + position.h() == monitor_rect.y() + monitor_rect.h() {
+        padding.bottom
+    } else {
+        padding.vertical
+    };
+    return position::Position::new(
+        position.x() + left,
+        position.y() + top,
+        position.w() - left - right,
+        position.h() - top - bottom,
+    );
+}
 
+fn split_evenly(region: &position::Position, n: usize, vertical: bool) -> Vec<position::Position> {
+    let mut ret = Vec::new();
+    let total = if vertical { region.h() } else { region.w() };
+    let mut offset = 0;
+    for i in 0..n {
+        let size = if i == n - 1 {
+            total - offset
+        } else {
+            (total as f64 / n as f64 * (i + 1) as f64).round() as i32 - offset
+        };
+        if vertical {
+            ret.push(position::Position::new(
+                region.x(),
+                region.y() + offset,
+                region.w(),
+                size,
+            ));
+        } else {
+            ret.push(position::Position::new(
+                region.x() + offset,
+                region.y(),
+                size,
+                region.h(),
+            ));
+        }
+        offset += size;
+    }
+    return ret;
+}
+
+fn master_stack_positions(
+    region: &position::Position,
+    n: usize,
+    master_count: usize,
+    master_ratio: f64,
+    stack_direction: &Direction,
+) -> Vec<position::Position> {
+    let vertical = match stack_direction {
+        Direction::Left | Direction::Right => true,
+        Direction::Up | Direction::Down => false,
+    };
+    if master_count == 0 || n <= master_count {
+        return split_evenly(region, n, vertical);
+    }
+    let master_ratio = master_ratio.clamp(MIN_MASTER_RATIO, MAX_MASTER_RATIO);
+    let mut master_region = region.clone();
+    let mut stack_region = region.clone();
+    match stack_direction {
+        Direction::Left | Direction::Right => {
+            let master_w = (master_ratio * region.w() as f64).round() as i32;
+            master_region.set_w(master_w);
+            stack_region.set_w(region.w() - master_w);
+            match stack_direction {
+                Direction::Right => stack_region.set_x(region.x() + master_w),
+                _ => master_region.set_x(region.x() + region.w() - master_w),
+            }
+        }
+        Direction::Up | Direction::Down => {
+            let master_h = (master_ratio * region.h() as f64).round() as i32;
+            master_region.set_h(master_h);
+            stack_region.set_h(region.h() - master_h);
+            match stack_direction {
+                Direction::Down => stack_region.set_y(region.y() + master_h),
+                _ => master_region.set_y(region.y() + region.h() - master_h),
+            }
+        }
+    }
+    let mut ret = split_evenly(&master_region, master_count, vertical);
+    ret.append(&mut split_evenly(&stack_region, n - master_count, vertical));
+    return ret;
+}
+
+/// Splits `region` into `n` tiles, where each tile takes `ratio` of the space
+/// left by the previous ones and `direction_of` gives the direction in which
+/// the space left after the `i`th tile lies.
+fn spiral_positions(
+    region: &position::Position,
+    n: usize,
+    ratio: f64,
+    direction_of: impl Fn(usize) -> Direction,
+) -> Vec<position::Position> {
+    let mut ret = Vec::new();
+    let mut remaining = region.clone();
+    for i in 0..n.saturating_sub(1) {
+        let mut tile = remaining.clone();
+        match direction_of(i) {
+            Direction::Up | Direction::Down => {
+                let h = (ratio * remaining.h() as f64).round() as i32;
+                tile.set_h(h);
+                remaining.set_h(remaining.h() - h);
+            }
+            Direction::Left | Direction::Right => {
+                let w = (ratio * remaining.w() as f64).round() as i32;
+                tile.set_w(w);
+                remaining.set_w(remaining.w() - w);
+            }
+        }
+        match direction_of(i) {
+            Direction::Up => tile.set_y(remaining.y() + remaining.h()),
+            Direction::Down => remaining.set_y(tile.y() + tile.h()),
+            Direction::Left => tile.set_x(remaining.x() + remaining.w()),
+            Direction::Right => remaining.set_x(tile.x() + tile.w()),
+        }
+        ret.push(tile);
+    }
+    ret.push(remaining);
+    return ret;
+}
+
+/// Splits `region` into a grid of `n` cells.
+fn grid_positions(
+    region: &position::Position,
+    n: usize,
+    columns: Option<usize>,
+    rows: Option<usize>,
+    orientation: &Orientation,
+) -> Vec<position::Position> {
+    let vertical = match orientation {
+        Orientation::Horizontal => true,
+        Orientation::Vertical => false,
+    };
+    // The number of cells in each row, or in each column if the orientation
+    // is vertical
+    let per_line = match (columns.filter(|c| *c > 0), rows.filter(|r| *r > 0)) {
+        (Some(columns), _) => match orientation {
+            Orientation::Horizontal => std::cmp::min(columns, n),
+            Orientation::Vertical => n.div_ceil(std::cmp::min(columns, n)),
+        },
+        (None, Some(rows)) => match orientation {
+            Orientation::Horizontal => n.div_ceil(std::cmp::min(rows, n)),
+            Orientation::Vertical => std::cmp::min(rows, n),
+        },
+        (None, None) => {
+            // Pick the grid whose least square cell is closest to square,
+            // including the cells of a stretched last line
+            let (along, across) = if vertical {
+                (region.w() as f64, region.h() as f64)
+            } else {
+                (region.h() as f64, region.w() as f64)
+            };
+            let mut best = n;
+            let mut best_score = f64::INFINITY;
+            for per_line in 1..=n {
+                let lines = n.div_ceil(per_line);
+                let last = n - per_line * (lines - 1);
+                let score = [per_line, last]
+                    .iter()
+                    .map(|count| {
+                        ((along / *count as f64) / (across / lines as f64))
+                            .ln()
+                            .abs()
+                    })
+                    .fold(0.0, f64::max);
+                if score < best_score - 1e-9 {
+                    best = per_line;
+                    best_score = score;
+                }
+            }
+            best
+        }
+    };
+    let lines = n.div_ceil(per_line);
+    let mut ret = Vec::new();
+    for (i, line) in split_evenly(region, lines, vertical).iter().enumerate() {
+        let count = if i == lines - 1 {
+            n - per_line * (lines - 1)
+        } else {
+            per_line
+        };
+        ret.append(&mut split_evenly(line, count, !vertical));
+    }
+    return ret;
+}
+
+impl Variant {
+    pub fn new(positions: Vec<Vec<position::Position>>, end_behaviour: EndBehaviour) -> Self {
+        let positions_len = positions.len();
         return Self {
-            positions: value.positions,
+            positions,
             internal_positions: Vec::new(),
             manual_positions_until: positions_len,
-            end_behaviour: value.end_behaviour,
+            end_behaviour,
+            generated_resizes: Vec::new(),
         };
     }
-}
 
-impl Variant {
     pub fn positions(&self) -> &Vec<Vec<position::Position>> {
         &self.positions
     }
@@ -90,6 +410,12 @@ impl Variant {
         &mut self.positions
     }
 
+    /// The number of positions that were written by the user rather than
+    /// generated by the end behaviour.
+    pub fn manual_positions_until(&self) -> usize {
+        self.manual_positions_until
+    }
+
     pub fn end_behaviour(&self) -> &EndBehaviour {
         &self.end_behaviour
     }
@@ -98,39 +424,18 @@ impl Variant {
         &mut self.end_behaviour
     }
 
-    pub fn update(
-        &mut self,
-        window_padding: i32,
-        edge_padding: i32,
-        monitor_rect: &position::Position,
-    ) {
+    pub fn update(&mut self, padding: &padding::Padding, monitor_rect: &position::Position) {
         self.internal_positions = Vec::new();
         let mut len = 0;
         for positions in &self.positions {
             self.internal_positions.push(Vec::new());
             len += 1;
             for position in positions {
-                let mut internal_position = position::Position::new(
-                    position.x() + window_padding,
-                    position.y() + window_padding,
-                    position.w() - 2 * window_padding,
-                    position.h() - 2 * window_padding,
-                );
-                if position.x() == monitor_rect.x() {
-                    internal_position.set_x(internal_position.x() - window_padding + edge_padding);
-                    internal_position.set_w(internal_position.w() + window_padding - edge_padding);
-                }
-                if position.y() == monitor_rect.y() {
-                    internal_position.set_y(internal_position.y() - window_padding + edge_padding);
-                    internal_position.set_h(internal_position.h() + window_padding - edge_padding);
-                }
-                if position.x() + position.w() == monitor_rect.x() + monitor_rect.w() {
-                    internal_position.set_w(internal_position.w() + window_padding - edge_padding);
-                }
-                if position.y() + position.h() == monitor_rect.y() + monitor_rect.h() {
-                    internal_position.set_h(internal_position.h() + window_padding - edge_padding);
-                }
-                self.internal_positions[len - 1].push(internal_position);
+                self.internal_positions[len - 1].push(internal_position(
+                    position,
+                    padding,
+                    monitor_rect,
+                ));
             }
         }
     }
@@ -190,6 +495,61 @@ impl Variant {
         );
     }
 
+    /// Computes the arrangement for `n` windows from `from`, or the last
+    /// manual positions, without using the arrangements for fewer windows.
+    /// Returns `None` if the end behaviour only extends the arrangement for one
+    /// less window.
+    fn recompute(&self, n: usize) -> Option<Vec<position::Position>> {
+        let mut positions = match &self.end_behaviour.from {
+            Some(positions) => positions.clone(),
+            None => self.positions[self.manual_positions_until - 1].clone(),
+        };
+        let region = positions[self.end_behaviour.position_idx].clone();
+        let count = std::cmp::max((n + 1).saturating_sub(positions.len()), 1);
+        let mut tiles = match &self.end_behaviour.behaviour {
+            EndBehaviourType::MasterStack {
+                master_count,
+                master_ratio,
+                stack_direction,
+            } => master_stack_positions(
+                &region,
+                count,
+                *master_count,
+                *master_ratio,
+                stack_direction,
+            ),
+            EndBehaviourType::Spiral { ratio, direction } => {
+                spiral_positions(&region, count, *ratio, |i| {
+                    let mut ret = direction.clone();
+                    for _ in 0..i % 4 {
+                        ret = ret.clockwise();
+                    }
+                    ret
+                })
+            }
+            EndBehaviourType::Dwindle { ratio, direction } => {
+                spiral_positions(&region, count, *ratio, |i| {
+                    if i % 2 == 0 {
+                        direction.clone()
+                    } else {
+                        direction.clockwise()
+                    }
+                })
+            }
+            EndBehaviourType::Grid {
+                columns,
+                rows,
+                orientation,
+            } => grid_positions(&region, count, *columns, *rows, orientation),
+            EndBehaviourType::Directional { .. } | EndBehaviourType::Repeating { .. } => {
+                return None;
+            }
+        };
+        positions[self.end_behaviour.position_idx] = tiles.remove(0);
+        positions.append(&mut tiles);
+        return Some(positions);
+    }
+
     pub fn extend(&mut self) {
         match self.end_behaviour.behaviour.to_owned() {
             EndBehaviourType::Directional { direction } => {
@@ -202,73 +562,49 @@ impl Variant {
                             .push(self.positions[self.manual_positions_until - 1].clone());
                     }
                 };
-                let mut first_iteration = true;
                 let last_idx = self.positions.len() - 1;
-                match direction {
-                    Direction::Up => {
-                        let h = (self.positions[last_idx][self.end_behaviour.position_idx].h()
-                            as f64
-                            / (self.positions.len() - self.positions[last_idx].len() + 1) as f64)
-                            .round() as i32;
-                        while self.positions[last_idx].len() < self.positions.len() {
-                            let split_idx = if first_iteration {
-                                self.end_behaviour.position_idx
-                            } else {
-                                self.positions[last_idx].len() - 1
-                            };
-                            let at = self.positions[last_idx][split_idx].h() - h;
-                            self.split(last_idx, split_idx, Direction::Up, at);
-                            first_iteration = false;
-                        }
-                    }
-                    Direction::Down => {
-                        let h = (self.positions[last_idx][self.end_behaviour.position_idx].h()
-                            as f64
-                            / (self.positions.len() - self.positions[last_idx].len() + 1) as f64)
-                            .round() as i32;
-                        while self.positions[last_idx].len() < self.positions.len() {
-                            let split_idx = if first_iteration {
-                                self.end_behaviour.position_idx
-                            } else {
-                                self.positions[last_idx].len() - 1
-                            };
-                            self.split(last_idx, split_idx, Direction::Down, h);
-                            first_iteration = false;
-                        }
-                    }
-                    Direction::Left => {
-                        let w = (self.positions[last_idx][self.end_behaviour.position_idx].w()
-                            as f64
-                            / (self.positions.len() - self.positions[last_idx].len() + 1) as f64)
-                            .round() as i32;
-                        while self.positions[last_idx].len() < self.positions.len() {
-                            let split_idx = if first_iteration {
-                                self.end_behaviour.position_idx
-                            } else {
-                                self.positions[last_idx].len() - 1
-                            };
-                            let at = self.positions[last_idx][split_idx].w() - w;
-                            self.split(last_idx, split_idx, Direction::Left, at);
-                            first_iteration = false;
-                        }
+                let position_idx = self.end_behaviour.position_idx;
+                let vertical = match direction {
+                    Direction::Up | Direction::Down => true,
+                    Direction::Left | Direction::Right => false,
+                };
+                let size_of = |position: &position::Position| {
+                    if vertical {
+                        position.h()
+                    } else {
+                        position.w()
                     }
-                    Direction::Right => {
-                        let w = (self.positions[last_idx][self.end_behaviour.position_idx].w()
-                            as f64
-                            / (self.positions.len() - self.positions[last_idx].len() + 1) as f64)
-                            .round() as i32;
-                        while self.positions[last_idx].len() < self.positions.len() {
-                            let split_idx = if first_iteration {
-                                self.end_behaviour.position_idx
-                            } else {
-                                self.positions[last_idx].len() - 1
-                            };
-                            self.split(last_idx, split_idx, Direction::Right, w);
-                            first_iteration = false;
+                };
+                // Each split takes its share of the tile at `position_idx`,
+                // rounded so that the shares always add up to the whole tile
+                let count = self.positions.len() - self.positions[last_idx].len() + 1;
+                let total = size_of(&self.positions[last_idx][position_idx]);
+                let mut taken = 0;
+                for i in 0..count - 1 {
+                    let split_idx = if i == 0 {
+                        position_idx
+                    } else {
+                        self.positions[last_idx].len() - 1
+                    };
+                    let size =
+                        (total as f64 * (i + 1) as f64 / count as f64).round() as i32 - taken;
+                    taken += size;
+                    let at = match direction {
+                        Direction::Down | Direction::Right => size,
+                        Direction::Up | Direction::Left => {
+                            size_of(&self.positions[last_idx][split_idx]) - size
                         }
-                    }
+                    };
+                    self.split(last_idx, split_idx, direction.to_owned(), at);
                 }
             }
+            EndBehaviourType::MasterStack { .. }
+            | EndBehaviourType::Spiral { .. }
+            | EndBehaviourType::Dwindle { .. }
+            | EndBehaviourType::Grid { .. } => {
+                let positions = self.recompute(self.positions.len() + 1).unwrap();
+                self.positions.push(positions);
+            }
             EndBehaviourType::Repeating { splits } => match &self.end_behaviour.from {
                 Some(positions) if self.positions.len() == self.manual_positions_until => {
                     self.positions.push(positions.clone());
@@ -310,6 +646,194 @@ impl Variant {
         }
     }
 
+    /// Generates the positions of the end behaviour again, keeping the resizes
+    /// that were made to them.
+    fn reset_generated_positions(&mut self) {
+        self.positions.truncate(self.manual_positions_until);
+        let generated_resizes = self.generated_resizes.clone();
+        for resize in generated_resizes {
+            self.move_edge(resize.n, resize.idx, &resize.direction, resize.delta);
+        }
+    }
+
+    pub fn master_count(&self) -> Option<usize> {
+        match self.end_behaviour.behaviour {
+            EndBehaviourType::MasterStack { master_count, .. } => Some(master_count),
+            _ => None,
+        }
+    }
+
+    pub fn master_ratio(&self) -> Option<f64> {
+        match self.end_behaviour.behaviour {
+            EndBehaviourType::MasterStack { master_ratio, .. } => Some(master_ratio),
+            _ => None,
+        }
+    }
+
+    /// Returns `false` if the end behaviour is not `MasterStack`.
+    pub fn increment_master_count(&mut self) -> bool {
+        match &mut self.end_behaviour.behaviour {
+            EndBehaviourType::MasterStack { master_count, .. } => {
+                *master_count += 1;
+            }
+            _ => return false,
+        }
+        self.reset_generated_positions();
+        return true;
+    }
+
+    /// Returns `false` if the end behaviour is not `MasterStack` or there are
+    /// no master windows left.
+    pub fn decrement_master_count(&mut self) -> bool {
+        match &mut self.end_behaviour.behaviour {
+            EndBehaviourType::MasterStack { master_count, .. } if *master_count > 0 => {
+                *master_count -= 1;
+            }
+            _ => return false,
+        }
+        self.reset_generated_positions();
+        return true;
+    }
+
+    /// Adds `step` to the master ratio, keeping it between `MIN_MASTER_RATIO`
+    /// and `MAX_MASTER_RATIO`. Returns `false` if the end behaviour is not
+    /// `MasterStack` or the ratio did not change.
+    pub fn grow_master(&mut self, step: f64) -> bool {
+        match &mut self.end_behaviour.behaviour {
+            EndBehaviourType::MasterStack { master_ratio, .. } => {
+                let new_ratio = (*master_ratio + step).clamp(MIN_MASTER_RATIO, MAX_MASTER_RATIO);
+                if new_ratio == *master_ratio {
+                    return false;
+                }
+                *master_ratio = new_ratio;
+            }
+            _ => return false,
+        }
+        self.reset_generated_positions();
+        return true;
+    }
+
+    pub fn shrink_master(&mut self, step: f64) -> bool {
+        return self.grow_master(-step);
+    }
+
+    /// Moves the `direction` edge of the tile at `idx` in the arrangement for
+    /// `n` windows outwards by `delta`, or inwards if `delta` is negative.
+    ///
+    /// Every tile that shares the edge is resized along with it so that the
+    /// arrangement stays consistent, and no tile is shrunk to nothing. Edges on
+    /// the outside of the arrangement cannot be moved. Returns `false` if
+    /// nothing changed.
+    pub fn resize(&mut self, n: usize, idx: usize, direction: Direction, delta: i32) -> bool {
+        if !self.move_edge(n, idx, &direction, delta) {
+            return false;
+        }
+        if n > self.manual_positions_until {
+            self.generated_resizes.push(GeneratedResize {
+                n,
+                idx,
+                direction,
+                delta,
+            });
+        }
+        return true;
+    }
+
+    fn move_edge(&mut self, n: usize, idx: usize, direction: &Direction, delta: i32) -> bool {
+        if n == 0 || idx >= n || delta == 0 {
+            return false;
+        }
+        while self.positions.len() < n {
+            self.extend();
+        }
+        let tiles = &mut self.positions[n - 1];
+        if idx >= tiles.len() {
+            return false;
+        }
+        let horizontal = match direction {
+            Direction::Left | Direction::Right => true,
+            Direction::Up | Direction::Down => false,
+        };
+        // (start, end) along the axis being resized, then (start, end) along
+        // the edge
+        let extents = |tile: &position::Position| {
+            if horizontal {
+                (tile.x(), tile.x() + tile.w(), tile.y(), tile.y() + tile.h())
+            } else {
+                (tile.y(), tile.y() + tile.h(), tile.x(), tile.x() + tile.w())
+            }
+        };
+        let (near, far, mut edge_start, mut edge_end) = extents(&tiles[idx]);
+        let edge = match direction {
+            Direction::Left | Direction::Up => near,
+            Direction::Right | Direction::Down => far,
+        };
+        let new_edge = match direction {
+            Direction::Left | Direction::Up => edge - delta,
+            Direction::Right | Direction::Down => edge + delta,
+        };
+        let mut before = Vec::new();
+        let mut after = Vec::new();
+        loop {
+            let mut changed = false;
+            for (i, tile) in tiles.iter().enumerate() {
+                let (tile_near, tile_far, tile_start, tile_end) = extents(tile);
+                if tile_start >= edge_end || tile_end <= edge_start {
+                    continue;
+                }
+                if tile_far == edge && !before.contains(&i) {
+                    before.push(i);
+                } else if tile_near == edge && !after.contains(&i) {
+                    after.push(i);
+                } else {
+                    continue;
+                }
+                edge_start = std::cmp::min(edge_start, tile_start);
+                edge_end = std::cmp::max(edge_end, tile_end);
+                changed = true;
+            }
+            if !changed {
+                break;
+            }
+        }
+        if before.is_empty() || after.is_empty() {
+            return false;
+        }
+        let lowest = before
+            .iter()
+            .map(|i| extents(&tiles[*i]).0 + 1)
+            .max()
+            .unwrap();
+        let highest = after
+            .iter()
+            .map(|i| extents(&tiles[*i]).1 - 1)
+            .min()
+            .unwrap();
+        let new_edge = new_edge.clamp(lowest, std::cmp::max(lowest, highest));
+        if new_edge == edge {
+            return false;
+        }
+        for i in before {
+            let tile = &mut tiles[i];
+            if horizontal {
+                tile.set_w(new_edge - tile.x());
+            } else {
+                tile.set_h(new_edge - tile.y());
+            }
+        }
+        for i in after {
+            let tile = &mut tiles[i];
+            if horizontal {
+                tile.set_w(tile.x() + tile.w() - new_edge);
+                tile.set_x(new_edge);
+            } else {
+                tile.set_h(tile.y() + tile.h() - new_edge);
+                tile.set_y(new_edge);
+            }
+        }
+        return true;
+    }
+
     pub fn get_internal_positions(
         &mut self,
         n: usize,
@@ -320,7 +844,28 @@ impl Variant {
         while self.positions.len() < n {
             self.extend();
         }
-        self.update(window_padding, edge_padding, monitor_rect);
+        self.update(
+            &padding::Padding::uniform(window_padding, edge_padding),
+            monitor_rect,
+        );
         return &self.internal_positions[n - 1];
     }
+
+    /// Like `get_internal_positions`, but with the space redistributed by
+    /// `weights` (see `weights::apply_weights`) before padding is applied.
+    pub fn get_weighted_internal_positions(
+        &mut self,
+        n: usize,
+        weights: &[f64],
+        padding: &padding::Padding,
+        monitor_rect: &position::Position,
+    ) -> Vec<position::Position> {
+        while self.positions.len() < n {
+            self.extend();
+        }
+        return weights::apply_weights(&self.positions[n - 1], monitor_rect, weights)
+            .iter()
+            .map(|position| internal_position(position, padding, monitor_rect))
+            .collect();
+    }
 }
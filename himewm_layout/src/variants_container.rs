@@ -1,5 +1,4 @@
-use serde::{Deserialize, Serialize};
-use serde_json::value::RawValue;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
 pub enum VariantsContainerReturn<'a, T> {
     Container(&'a VariantsContainer<T>),
@@ -11,56 +10,94 @@ pub enum VariantsContainerReturnMut<'a, T> {
     Variant(&'a mut T),
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+/// Variants written as nested arrays, where an array of arrays is a
+/// container and an array of objects is a list of variants.
+#[derive(Clone, Debug)]
 pub enum VariantsContainer<T> {
     Container(Vec<VariantsContainer<T>>),
     Variants(Vec<T>),
 }
 
-impl<T> VariantsContainer<T> {
-    pub fn from_raw_value(raw: &RawValue) -> serde_json::Result<Self>
-    where
-        for<'a> T: Deserialize<'a>,
-    {
-        let raw_str = raw.get();
-        let normalized_string = raw_str.replace([' ', '\n', '\t'], "");
-        let data = normalized_string.as_bytes();
-        let mut input_string = String::new();
-        let mut inner_array_count = None;
-        for i in 0..data.len() {
-            match data[i] {
-                b'[' if i + 1 < data.len() => match inner_array_count {
-                    Some(count) => {
-                        inner_array_count = Some(count + 1);
-                        input_string.push('[');
-                    }
-                    None => {
-                        if data[i + 1] == b'[' {
-                            input_string += r#"{"Container":["#;
-                        } else {
-                            input_string += r#"{"Variants":["#;
-                            inner_array_count = Some(0);
-                        }
-                    }
-                },
-                b']' => match inner_array_count {
-                    Some(count) if count > 0 => {
-                        input_string.push(']');
-                        inner_array_count = Some(count - 1);
-                    }
-                    _ => {
-                        input_string += "]}";
-                        inner_array_count = None;
-                    }
-                },
-                _ => {
-                    input_string.push(data[i] as char);
-                }
+impl<T: Serialize> Serialize for VariantsContainer<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            VariantsContainer::Container(inner) => inner.serialize(serializer),
+            VariantsContainer::Variants(inner) => inner.serialize(serializer),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for VariantsContainer<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(VariantsContainerVisitor(std::marker::PhantomData))
+    }
+}
+
+struct VariantsContainerVisitor<T>(std::marker::PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> de::Visitor<'de> for VariantsContainerVisitor<T> {
+    type Value = VariantsContainer<T>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("an array of variants or an array of arrays of variants")
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut containers = Vec::new();
+        let mut variants = Vec::new();
+        while let Some(element) = seq.next_element::<Element<T>>()? {
+            match element {
+                Element::Container(container) => containers.push(container),
+                Element::Variant(variant) => variants.push(variant),
             }
+            if !containers.is_empty() && !variants.is_empty() {
+                return Err(de::Error::custom(
+                    "an array cannot contain both variants and arrays of variants",
+                ));
+            }
+        }
+        if containers.is_empty() {
+            return Ok(VariantsContainer::Variants(variants));
+        } else {
+            return Ok(VariantsContainer::Container(containers));
         }
-        return serde_json::from_str(&input_string);
     }
+}
+
+/// An element of an array in a `VariantsContainer`, which is either a nested
+/// array or a variant.
+enum Element<T> {
+    Container(VariantsContainer<T>),
+    Variant(T),
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Element<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ElementVisitor(std::marker::PhantomData))
+    }
+}
+
+struct ElementVisitor<T>(std::marker::PhantomData<T>);
+
+impl<'de, T: Deserialize<'de>> de::Visitor<'de> for ElementVisitor<T> {
+    type Value = Element<T>;
 
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a variant or an array of variants")
+    }
+
+    fn visit_seq<A: de::SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        return VariantsContainerVisitor(std::marker::PhantomData)
+            .visit_seq(seq)
+            .map(Element::Container);
+    }
+
+    fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        return T::deserialize(de::value::MapAccessDeserializer::new(map)).map(Element::Variant);
+    }
+}
+
+impl<T> VariantsContainer<T> {
     pub fn len(&self) -> usize {
         match self {
             VariantsContainer::Container(inner) => {
@@ -174,10 +211,28 @@ impl<T> VariantsContainer<T> {
         }
     }
 
+    /// The index of every variant, in order.
+    pub fn variant_idxs(&self) -> Vec<Vec<usize>> {
+        let mut ret = Vec::new();
+        let mut stack = vec![vec![]];
+        while let Some(current_idx) = stack.pop() {
+            match self.get(&current_idx) {
+                VariantsContainerReturn::Container(container) => {
+                    for i in (0..container.len()).rev() {
+                        stack.push([current_idx.as_slice(), &[i]].concat());
+                    }
+                }
+                VariantsContainerReturn::Variant(_) => {
+                    ret.push(current_idx);
+                }
+            }
+        }
+        return ret;
+    }
+
     pub fn callback_all<F: FnMut(&mut T)>(&mut self, mut cb: F) {
         let mut stack = vec![vec![]];
-        while !stack.is_empty() {
-            let current_idx = stack.pop().unwrap();
+        while let Some(current_idx) = stack.pop() {
             match self.get_mut(&current_idx) {
                 VariantsContainerReturnMut::Container(container) => {
                     for i in 0..container.len() {
@@ -194,43 +249,47 @@ impl<T> VariantsContainer<T> {
 
 impl<T: Clone> VariantsContainer<T> {
     pub fn map<U, F: Fn(T) -> U>(&self, cb: F) -> VariantsContainer<U> {
+        self.map_with_idx(|_, variant| cb(variant))
+    }
+
+    /// Like `map`, but `cb` is also given the index of each variant.
+    pub fn map_with_idx<U, F: Fn(&[usize], T) -> U>(&self, cb: F) -> VariantsContainer<U> {
         let mut ret = match self {
             VariantsContainer::Container(_) => VariantsContainer::Container(Vec::new()),
             VariantsContainer::Variants(_) => VariantsContainer::Variants(Vec::new()),
         };
         let mut stack = vec![vec![]];
-        while !stack.is_empty() {
-            let current_idx = stack.pop().unwrap();
+        while let Some(current_idx) = stack.pop() {
             if let VariantsContainerReturn::Container(container) = self.get(&current_idx) {
                 match container {
                     VariantsContainer::Container(inner) => {
-                        if let VariantsContainerReturnMut::Container(ret_container) =
-                            ret.get_mut(&current_idx)
+                        if let VariantsContainerReturnMut::Container(
+                            VariantsContainer::Container(ret_inner),
+                        ) = ret.get_mut(&current_idx)
                         {
-                            if let VariantsContainer::Container(ret_inner) = ret_container {
-                                for i in 0..inner.len() {
-                                    match inner[i] {
-                                        VariantsContainer::Container(_) => {
-                                            ret_inner
-                                                .push(VariantsContainer::Container(Vec::new()));
-                                        }
-                                        VariantsContainer::Variants(_) => {
-                                            ret_inner.push(VariantsContainer::Variants(Vec::new()));
-                                        }
+                            for (i, inner_container) in inner.iter().enumerate() {
+                                match inner_container {
+                                    VariantsContainer::Container(_) => {
+                                        ret_inner.push(VariantsContainer::Container(Vec::new()));
+                                    }
+                                    VariantsContainer::Variants(_) => {
+                                        ret_inner.push(VariantsContainer::Variants(Vec::new()));
                                     }
-                                    stack.push([current_idx.as_slice(), &[i]].concat());
                                 }
+                                stack.push([current_idx.as_slice(), &[i]].concat());
                             }
                         }
                     }
                     VariantsContainer::Variants(inner) => {
-                        if let VariantsContainerReturnMut::Container(ret_container) =
-                            ret.get_mut(&current_idx)
+                        if let VariantsContainerReturnMut::Container(VariantsContainer::Variants(
+                            ret_inner,
+                        )) = ret.get_mut(&current_idx)
                         {
-                            if let VariantsContainer::Variants(ret_inner) = ret_container {
-                                for variant in inner {
-                                    ret_inner.push(cb(variant.to_owned()));
-                                }
+                            for (i, variant) in inner.iter().enumerate() {
+                                ret_inner.push(cb(
+                                    &[current_idx.as_slice(), &[i]].concat(),
+                                    variant.to_owned(),
+                                ));
                             }
                         }
                     }
@@ -0,0 +1,203 @@
+use crate::position;
+
+pub const DEFAULT_WEIGHT: f64 = 1.0;
+
+pub const MIN_WEIGHT: f64 = 0.1;
+
+fn weight(weights: &[f64], idx: usize) -> f64 {
+    return weights.get(idx).copied().unwrap_or(DEFAULT_WEIGHT);
+}
+
+/// The start and size of a position along the x axis, or the y axis if
+/// `vertical`.
+fn span(position: &position::Position, vertical: bool) -> (i32, i32) {
+    if vertical {
+        return (position.y(), position.h());
+    } else {
+        return (position.x(), position.w());
+    }
+}
+
+fn set_span(position: &mut position::Position, vertical: bool, start: i32, size: i32) {
+    if vertical {
+        position.set_y(start);
+        position.set_h(size);
+    } else {
+        position.set_x(start);
+        position.set_w(size);
+    }
+}
+
+/// The tiles between two lines along an axis.
+struct Group {
+    start: i32,
+    size: i32,
+    tiles: Vec<usize>,
+}
+
+/// Splits the tiles in `original` into the groups between every line along
+/// the axis that does not cut through a tile. Returns `None` if there is no
+/// such line.
+fn groups(
+    original: &position::Position,
+    tiles: &[usize],
+    positions: &[position::Position],
+    vertical: bool,
+) -> Option<Vec<Group>> {
+    let (start, size) = span(original, vertical);
+    let end = start + size;
+    let mut cuts = vec![start, end];
+    for i in tiles {
+        let (tile_start, tile_size) = span(&positions[*i], vertical);
+        for cut in [tile_start, tile_start + tile_size] {
+            if cut <= start || cut >= end || cuts.contains(&cut) {
+                continue;
+            }
+            let cuts_tile = tiles.iter().any(|j| {
+                let (other_start, other_size) = span(&positions[*j], vertical);
+                other_start < cut && cut < other_start + other_size
+            });
+            if !cuts_tile {
+                cuts.push(cut);
+            }
+        }
+    }
+    if cuts.len() == 2 {
+        return None;
+    }
+    cuts.sort();
+    let mut ret = Vec::new();
+    for k in 0..cuts.len() - 1 {
+        let group_tiles = tiles
+            .iter()
+            .copied()
+            .filter(|i| {
+                let (tile_start, _) = span(&positions[*i], vertical);
+                tile_start >= cuts[k] && tile_start < cuts[k + 1]
+            })
+            .collect();
+        ret.push(Group {
+            start: cuts[k],
+            size: cuts[k + 1] - cuts[k],
+            tiles: group_tiles,
+        });
+    }
+    return Some(ret);
+}
+
+fn weigh(
+    region: &position::Position,
+    original: &position::Position,
+    tiles: &[usize],
+    positions: &[position::Position],
+    weights: &[f64],
+    ret: &mut Vec<position::Position>,
+) {
+    if tiles.len() == 1 {
+        ret[tiles[0]] = region.clone();
+        return;
+    }
+    for vertical in [false, true] {
+        let groups = match groups(original, tiles, positions, vertical) {
+            Some(groups) => groups,
+            None => continue,
+        };
+        // Each group keeps its share of the space, scaled by the average
+        // weight of its tiles
+        let weighted: Vec<f64> = groups
+            .iter()
+            .map(|group| {
+                let factor = if group.tiles.is_empty() {
+                    DEFAULT_WEIGHT
+                } else {
+                    group.tiles.iter().map(|i| weight(weights, *i)).sum::<f64>()
+                        / group.tiles.len() as f64
+                };
+                group.size as f64 * factor
+            })
+            .collect();
+        let total_weighted: f64 = weighted.iter().sum();
+        let (region_start, region_size) = span(region, vertical);
+        let mut cumulative = 0.0;
+        let mut offset = 0;
+        for (k, group) in groups.iter().enumerate() {
+            cumulative += weighted[k];
+            let end = if k == groups.len() - 1 || total_weighted <= 0.0 {
+                region_size
+            } else {
+                (region_size as f64 * cumulative / total_weighted).round() as i32
+            };
+            let mut group_region = region.clone();
+            set_span(
+                &mut group_region,
+                vertical,
+                region_start + offset,
+                end - offset,
+            );
+            let mut group_original = original.clone();
+            set_span(&mut group_original, vertical, group.start, group.size);
+            if !group.tiles.is_empty() {
+                weigh(
+                    &group_region,
+                    &group_original,
+                    &group.tiles,
+                    positions,
+                    weights,
+                    ret,
+                );
+            }
+            offset = end;
+        }
+        return;
+    }
+    // The tiles cannot be separated by a straight line, so they are scaled
+    // from `original` into `region` as they are. Edges are mapped rather than
+    // sizes so that tiles that shared an edge still do.
+    let map = |value: i32, vertical: bool| {
+        let (original_start, original_size) = span(original, vertical);
+        let (region_start, region_size) = span(region, vertical);
+        if original_size == 0 {
+            return region_start;
+        }
+        return region_start
+            + ((value - original_start) as f64 * region_size as f64 / original_size as f64).round()
+                as i32;
+    };
+    for i in tiles {
+        let position = &positions[*i];
+        let left = map(position.x(), false);
+        let top = map(position.y(), true);
+        let right = map(position.x() + position.w(), false);
+        let bottom = map(position.y() + position.h(), true);
+        ret[*i] = position::Position::new(left, top, right - left, bottom - top);
+    }
+}
+
+/// Redistributes the space in an arrangement of positions so that the tile at
+/// each index takes up space in proportion to its weight, where missing
+/// weights count as `DEFAULT_WEIGHT`.
+///
+/// Space is moved along the lines that split the arrangement, with each side
+/// of a line scaled by the average weight of the tiles on it, so equal weights
+/// leave the arrangement unchanged.
+pub fn apply_weights(
+    positions: &[position::Position],
+    monitor_rect: &position::Position,
+    weights: &[f64],
+) -> Vec<position::Position> {
+    if positions.len() < 2 || (0..positions.len()).all(|i| weight(weights, i) == weight(weights, 0))
+    {
+        return positions.to_owned();
+    }
+    let mut ret = positions.to_owned();
+    let tiles: Vec<usize> = (0..positions.len()).collect();
+    weigh(
+        monitor_rect,
+        monitor_rect,
+        &tiles,
+        positions,
+        weights,
+        &mut ret,
+    );
+    return ret;
+}
@@ -0,0 +1,101 @@
+use himewm_layout::{
+    layout::Layout, position::Position, user_layout::UserLayout, variant::Direction,
+};
+
+fn layout(json: &str) -> Layout {
+    let user_layout: UserLayout = serde_json::from_str(json).unwrap();
+    return Layout::from(user_layout);
+}
+
+fn layout_json(behaviour: &str) -> String {
+    return format!(
+        r#"{{
+            "w": 1.0,
+            "h": 1.0,
+            "default_variant_idx": [0],
+            "variants": [
+                {{
+                    "positions": [
+                        [[0.0, 0.0, 1.0, 1.0]],
+                        [[0.0, 0.0, 0.333333, 1.0], [0.333333, 0.0, 0.666667, 1.0]]
+                    ],
+                    "end_behaviour": {{"from": null, "position_idx": 1, "behaviour": {}}}
+                }}
+            ]
+        }}"#,
+        behaviour
+    );
+}
+
+const DOWN: &str = r#"{"Directional": {"direction": "Down"}}"#;
+
+const MASTER_STACK: &str =
+    r#"{"MasterStack": {"master_count": 1, "master_ratio": 0.5, "stack_direction": "Right"}}"#;
+
+const REPEATING: &str = r#"{"Repeating": {"splits": [
+    {"direction": "Down", "ratio": 0.5, "offset": 0},
+    {"direction": "Right", "ratio": 0.5, "offset": 0}
+]}}"#;
+
+fn assert_same_positions(a: &mut Layout, b: &mut Layout) {
+    for n in 1..=8 {
+        assert_eq!(
+            a.get_internal_positions(&[0], n, 0, 0),
+            b.get_internal_positions(&[0], n, 0, 0),
+            "{} windows",
+            n
+        );
+    }
+}
+
+#[test]
+fn unchanged_layout_keeps_its_units() {
+    let json = layout_json(DOWN);
+    let exported = layout(&json).to_json();
+    let original: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let exported: serde_json::Value = serde_json::from_str(&exported).unwrap();
+    assert_eq!(original, exported);
+}
+
+#[test]
+fn exported_layout_reloads_identically() {
+    let rect = Position::new(1920, 0, 1366, 767);
+    for behaviour in [DOWN, MASTER_STACK, REPEATING] {
+        let mut original = layout(&layout_json(behaviour)).resolve(rect.clone());
+        original
+            .variants_mut()
+            .get_innermost_mut(&[0])
+            .resize(2, 0, Direction::Right, 100);
+        let _ = original.get_internal_positions(&[0], 5, 0, 0);
+        let mut reloaded = layout(&original.to_json()).resolve(rect.clone());
+        assert_same_positions(&mut original, &mut reloaded);
+    }
+}
+
+#[test]
+fn resized_generated_positions_are_exported() {
+    let rect = Position::new(0, 0, 1920, 1080);
+    let mut original = layout(&layout_json(MASTER_STACK)).resolve(rect.clone());
+    original
+        .variants_mut()
+        .get_innermost_mut(&[0])
+        .resize(4, 3, Direction::Up, 50);
+    assert!(original
+        .variants_mut()
+        .get_innermost_mut(&[0])
+        .grow_master(0.1));
+    original
+        .variants_mut()
+        .get_innermost_mut(&[0])
+        .resize(4, 3, Direction::Up, 50);
+    let exported = original.to_json();
+    let mut reloaded = layout(&exported).resolve(rect);
+    assert_same_positions(&mut original, &mut reloaded);
+    assert_eq!(
+        layout(&exported)
+            .variants()
+            .get_innermost(&[0])
+            .master_ratio(),
+        Some(0.6)
+    );
+}
@@ -0,0 +1,323 @@
+use himewm_layout::{
+    position::Position,
+    variant::{Direction, EndBehaviour, EndBehaviourType, Orientation, RepeatingSplit, Variant},
+};
+
+const MAX_WINDOWS: usize = 50;
+
+const CASES: usize = 200;
+
+/// A small deterministic generator so that failures are reproducible.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        return self.0;
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        return (self.next() % n as u64) as usize;
+    }
+
+    fn ratio(&mut self) -> f64 {
+        return 0.2 + self.below(61) as f64 / 100.0;
+    }
+
+    fn direction(&mut self) -> Direction {
+        return [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ][self.below(4)]
+        .clone();
+    }
+}
+
+fn rects() -> Vec<Position> {
+    return vec![
+        Position::new(0, 0, 1920, 1080),
+        Position::new(1920, 0, 1366, 767),
+        Position::new(-1081, -300, 1081, 1919),
+    ];
+}
+
+/// Manual positions where each arrangement splits a tile of the previous one.
+fn manual_positions(rng: &mut Rng, rect: &Position) -> Vec<Vec<Position>> {
+    let mut variant = Variant::new(
+        vec![vec![rect.clone()]],
+        EndBehaviour::new(
+            None,
+            0,
+            EndBehaviourType::Directional {
+                direction: Direction::Down,
+            },
+        ),
+    );
+    let count = 1 + rng.below(4);
+    while variant.positions().len() < count {
+        let last = variant.positions().last().unwrap().clone();
+        variant.positions_mut().push(last.clone());
+        let j = rng.below(last.len());
+        let direction = rng.direction();
+        let size = match direction {
+            Direction::Up | Direction::Down => last[j].h(),
+            Direction::Left | Direction::Right => last[j].w(),
+        };
+        let at = (size as f64 * rng.ratio()).round() as i32;
+        variant.split(variant.positions().len() - 1, j, direction, at);
+    }
+    return variant.positions().to_owned();
+}
+
+/// Splits where every offset points at a tile that exists when it is used.
+fn repeating_splits(rng: &mut Rng) -> Vec<RepeatingSplit> {
+    let len = 1 + rng.below(3);
+    return (0..len)
+        .map(|k| {
+            let offset = if k == 0 {
+                rng.below(len + 1)
+            } else {
+                rng.below(k + 1)
+            };
+            RepeatingSplit::new(rng.direction(), rng.ratio(), offset)
+        })
+        .collect();
+}
+
+fn behaviour(rng: &mut Rng) -> EndBehaviourType {
+    match rng.below(6) {
+        0 => {
+            return EndBehaviourType::Directional {
+                direction: rng.direction(),
+            };
+        }
+        1 => {
+            return EndBehaviourType::MasterStack {
+                master_count: rng.below(4),
+                master_ratio: rng.ratio(),
+                stack_direction: rng.direction(),
+            };
+        }
+        2 => {
+            return EndBehaviourType::Spiral {
+                ratio: rng.ratio(),
+                direction: rng.direction(),
+            };
+        }
+        3 => {
+            return EndBehaviourType::Dwindle {
+                ratio: rng.ratio(),
+                direction: rng.direction(),
+            };
+        }
+        4 => {
+            return EndBehaviourType::Grid {
+                columns: [None, Some(1 + rng.below(4))][rng.below(2)],
+                rows: [None, Some(1 + rng.below(4))][rng.below(2)],
+                orientation: [Orientation::Horizontal, Orientation::Vertical][rng.below(2)].clone(),
+            };
+        }
+        _ => {
+            return EndBehaviourType::Repeating {
+                splits: repeating_splits(rng),
+            };
+        }
+    }
+}
+
+fn assert_tiles_the_rect(tiles: &[Position], rect: &Position, context: &str) {
+    for (i, tile) in tiles.iter().enumerate() {
+        assert!(
+            tile.w() >= 0 && tile.h() >= 0,
+            "{context}: tile {i} has negative size {tile:?}"
+        );
+        assert!(
+            tile.x() >= rect.x()
+                && tile.y() >= rect.y()
+                && tile.x() + tile.w() <= rect.x() + rect.w()
+                && tile.y() + tile.h() <= rect.y() + rect.h(),
+            "{context}: tile {i} is outside of the rect {tile:?}"
+        );
+        for (j, other) in tiles.iter().enumerate().take(i) {
+            assert!(
+                tile.x() >= other.x() + other.w()
+                    || other.x() >= tile.x() + tile.w()
+                    || tile.y() >= other.y() + other.h()
+                    || other.y() >= tile.y() + tile.h(),
+                "{context}: tiles {j} and {i} overlap {other:?} {tile:?}"
+            );
+        }
+    }
+    let area: i64 = tiles.iter().map(|t| t.w() as i64 * t.h() as i64).sum();
+    assert_eq!(
+        area,
+        rect.w() as i64 * rect.h() as i64,
+        "{context}: tiles do not cover the rect"
+    );
+}
+
+fn for_each_case(mut cb: impl FnMut(&mut Rng, &Position, usize)) {
+    let mut rng = Rng(0x9e3779b97f4a7c15);
+    for case in 0..CASES {
+        for rect in rects() {
+            cb(&mut rng, &rect, case);
+        }
+    }
+}
+
+#[test]
+fn generated_arrangements_tile_the_rect() {
+    for_each_case(|rng, rect, case| {
+        let positions = manual_positions(rng, rect);
+        let position_idx = rng.below(positions.last().unwrap().len());
+        let behaviour = behaviour(rng);
+        let context = format!("case {case}, {rect:?}, {behaviour:?}, position_idx {position_idx}");
+        let mut variant = Variant::new(
+            positions.clone(),
+            EndBehaviour::new(None, position_idx, behaviour),
+        );
+        for n in 1..=MAX_WINDOWS {
+            let tiles = variant.get_internal_positions(n, 0, 0, rect).to_owned();
+            assert_eq!(tiles.len(), n, "{context}: {n} windows");
+            assert_tiles_the_rect(&tiles, rect, &format!("{context}, {n} windows"));
+        }
+    });
+}
+
+#[test]
+fn manual_positions_are_used_as_written() {
+    for_each_case(|rng, rect, _| {
+        let positions = manual_positions(rng, rect);
+        let position_idx = rng.below(positions.last().unwrap().len());
+        let mut variant = Variant::new(
+            positions.clone(),
+            EndBehaviour::new(None, position_idx, behaviour(rng)),
+        );
+        let _ = variant.get_internal_positions(MAX_WINDOWS, 0, 0, rect);
+        for (i, manual) in positions.iter().enumerate() {
+            assert_eq!(variant.get_internal_positions(i + 1, 0, 0, rect), manual);
+        }
+    });
+}
+
+#[test]
+fn from_none_extends_from_the_last_manual_positions() {
+    for_each_case(|rng, rect, case| {
+        let positions = manual_positions(rng, rect);
+        let position_idx = rng.below(positions.last().unwrap().len());
+        let behaviour = behaviour(rng);
+        let mut from_none = Variant::new(
+            positions.clone(),
+            EndBehaviour::new(None, position_idx, behaviour.clone()),
+        );
+        let mut from_last = Variant::new(
+            positions.clone(),
+            EndBehaviour::new(
+                Some(positions.last().unwrap().clone()),
+                position_idx,
+                behaviour.clone(),
+            ),
+        );
+        // `Repeating` regenerates every arrangement from `from` when it is set,
+        // so it only matches for the first generated arrangement
+        let until = match behaviour {
+            EndBehaviourType::Repeating { .. } => positions.len() + 1,
+            _ => MAX_WINDOWS,
+        };
+        for n in positions.len() + 1..=until {
+            assert_eq!(
+                from_none.get_internal_positions(n, 0, 0, rect),
+                from_last.get_internal_positions(n, 0, 0, rect),
+                "case {case}, {behaviour:?}, {n} windows"
+            );
+        }
+    });
+}
+
+#[test]
+fn from_some_keeps_the_other_from_positions() {
+    for_each_case(|rng, rect, case| {
+        let positions = manual_positions(rng, rect);
+        let from = manual_positions(rng, rect).pop().unwrap();
+        if from.len() > positions.len() + 1 {
+            return;
+        }
+        let position_idx = rng.below(from.len());
+        let behaviour = match behaviour(rng) {
+            EndBehaviourType::Repeating { .. } => EndBehaviourType::Directional {
+                direction: rng.direction(),
+            },
+            behaviour => behaviour,
+        };
+        let mut variant = Variant::new(
+            positions.clone(),
+            EndBehaviour::new(Some(from.clone()), position_idx, behaviour.clone()),
+        );
+        for n in positions.len() + 1..=MAX_WINDOWS {
+            let tiles = variant.get_internal_positions(n, 0, 0, rect).to_owned();
+            for (j, tile) in from.iter().enumerate() {
+                if j != position_idx {
+                    assert_eq!(
+                        &tiles[j], tile,
+                        "case {case}, {behaviour:?}, {n} windows, position {j}"
+                    );
+                }
+            }
+            assert_tiles_the_rect(&tiles, rect, &format!("case {case}, {n} windows"));
+        }
+    });
+}
+
+#[test]
+fn directional_snapshot() {
+    let rect = Position::new(0, 0, 1000, 600);
+    let mut variant = Variant::new(
+        vec![vec![rect.clone()]],
+        EndBehaviour::new(
+            None,
+            0,
+            EndBehaviourType::Directional {
+                direction: Direction::Left,
+            },
+        ),
+    );
+    assert_eq!(
+        variant.get_internal_positions(3, 0, 0, &rect),
+        &vec![
+            Position::new(667, 0, 333, 600),
+            Position::new(333, 0, 334, 600),
+            Position::new(0, 0, 333, 600),
+        ]
+    );
+}
+
+#[test]
+fn repeating_snapshot() {
+    let rect = Position::new(0, 0, 1000, 600);
+    let mut variant = Variant::new(
+        vec![vec![rect.clone()]],
+        EndBehaviour::new(
+            None,
+            0,
+            EndBehaviourType::Repeating {
+                splits: vec![
+                    RepeatingSplit::new(Direction::Right, 0.5, 1),
+                    RepeatingSplit::new(Direction::Down, 0.5, 1),
+                ],
+            },
+        ),
+    );
+    assert_eq!(
+        variant.get_internal_positions(4, 0, 0, &rect),
+        &vec![
+            Position::new(0, 0, 500, 600),
+            Position::new(500, 0, 250, 300),
+            Position::new(500, 300, 500, 300),
+            Position::new(750, 0, 250, 300),
+        ]
+    );
+}
@@ -0,0 +1,81 @@
+use himewm_layout::{position::Position, user_layout::UserVariant, variant::Variant};
+
+fn variant(grid: &str, w: i32, h: i32) -> Variant {
+    let user_variant: UserVariant = serde_json::from_str(&format!(
+        r#"{{
+            "positions": [[[0, 0, {w}, {h}]]],
+            "end_behaviour": {{"from": null, "position_idx": 0, "behaviour": {{"Grid": {grid}}}}}
+        }}"#
+    ))
+    .unwrap();
+    return Variant::from(user_variant);
+}
+
+fn positions(variant: &mut Variant, n: usize, w: i32, h: i32) -> Vec<Position> {
+    return variant
+        .get_internal_positions(n, 0, 0, &Position::new(0, 0, w, h))
+        .to_owned();
+}
+
+#[test]
+fn nine_windows_make_equal_cells() {
+    let mut variant = variant("{}", 1200, 900);
+    let tiles = positions(&mut variant, 9, 1200, 900);
+    for (i, tile) in tiles.iter().enumerate() {
+        assert_eq!(
+            tile,
+            &Position::new(400 * (i as i32 % 3), 300 * (i as i32 / 3), 400, 300)
+        );
+    }
+}
+
+#[test]
+fn every_arrangement_is_rebalanced() {
+    let mut variant = variant("{}", 1920, 1080);
+    let _ = positions(&mut variant, 9, 1920, 1080);
+    assert_eq!(
+        positions(&mut variant, 4, 1920, 1080),
+        vec![
+            Position::new(0, 0, 960, 540),
+            Position::new(960, 0, 960, 540),
+            Position::new(0, 540, 960, 540),
+            Position::new(960, 540, 960, 540),
+        ]
+    );
+    assert_eq!(
+        positions(&mut variant, 3, 1920, 1080),
+        vec![
+            Position::new(0, 0, 640, 1080),
+            Position::new(640, 0, 640, 1080),
+            Position::new(1280, 0, 640, 1080),
+        ]
+    );
+}
+
+#[test]
+fn last_row_is_stretched() {
+    let mut variant = variant(r#"{"columns": 2}"#, 1000, 900);
+    assert_eq!(
+        positions(&mut variant, 5, 1000, 900),
+        vec![
+            Position::new(0, 0, 500, 300),
+            Position::new(500, 0, 500, 300),
+            Position::new(0, 300, 500, 300),
+            Position::new(500, 300, 500, 300),
+            Position::new(0, 600, 1000, 300),
+        ]
+    );
+}
+
+#[test]
+fn vertical_orientation_fills_columns() {
+    let mut variant = variant(r#"{"rows": 2, "orientation": "Vertical"}"#, 900, 1000);
+    assert_eq!(
+        positions(&mut variant, 3, 900, 1000),
+        vec![
+            Position::new(0, 0, 450, 500),
+            Position::new(0, 500, 450, 500),
+            Position::new(450, 0, 450, 1000),
+        ]
+    );
+}
@@ -0,0 +1,183 @@
+use himewm_layout::{
+    position::Position,
+    user_layout::UserVariant,
+    variant::{Direction, Variant},
+};
+
+fn variant(master_count: usize, stack_direction: &str) -> Variant {
+    let user_variant: UserVariant = serde_json::from_str(&format!(
+        r#"{{
+            "positions": [[[0, 0, 1000, 600]]],
+            "end_behaviour": {{
+                "from": null,
+                "position_idx": 0,
+                "behaviour": {{
+                    "MasterStack": {{
+                        "master_count": {master_count},
+                        "master_ratio": 0.6,
+                        "stack_direction": "{stack_direction}"
+                    }}
+                }}
+            }}
+        }}"#
+    ))
+    .unwrap();
+    return Variant::from(user_variant);
+}
+
+fn positions(variant: &mut Variant, n: usize) -> Vec<Position> {
+    return variant
+        .get_internal_positions(n, 0, 0, &Position::new(0, 0, 1000, 600))
+        .to_owned();
+}
+
+#[test]
+fn single_window_fills_region() {
+    let mut variant = variant(1, "Right");
+    assert_eq!(
+        positions(&mut variant, 1),
+        vec![Position::new(0, 0, 1000, 600)]
+    );
+}
+
+#[test]
+fn stack_is_split_evenly() {
+    let mut variant = variant(1, "Right");
+    assert_eq!(
+        positions(&mut variant, 4),
+        vec![
+            Position::new(0, 0, 600, 600),
+            Position::new(600, 0, 400, 200),
+            Position::new(600, 200, 400, 200),
+            Position::new(600, 400, 400, 200),
+        ]
+    );
+}
+
+#[test]
+fn stack_direction_places_stack() {
+    let mut variant = variant(1, "Up");
+    assert_eq!(
+        positions(&mut variant, 3),
+        vec![
+            Position::new(0, 240, 1000, 360),
+            Position::new(0, 0, 500, 240),
+            Position::new(500, 0, 500, 240),
+        ]
+    );
+}
+
+#[test]
+fn tiles_cover_region_for_any_window_count() {
+    for stack_direction in ["Up", "Down", "Left", "Right"] {
+        for master_count in 0..4 {
+            let mut variant = variant(master_count, stack_direction);
+            for n in 1..20 {
+                let tiles = positions(&mut variant, n);
+                assert_eq!(tiles.len(), n);
+                let area: i32 = tiles.iter().map(|p| p.w() * p.h()).sum();
+                assert_eq!(area, 1000 * 600);
+            }
+        }
+    }
+}
+
+#[test]
+fn master_count_and_ratio_can_be_adjusted() {
+    let mut variant = variant(1, "Right");
+    assert_eq!(positions(&mut variant, 3)[0], Position::new(0, 0, 600, 600));
+    assert!(variant.increment_master_count());
+    assert_eq!(variant.master_count(), Some(2));
+    assert_eq!(
+        positions(&mut variant, 3),
+        vec![
+            Position::new(0, 0, 600, 300),
+            Position::new(0, 300, 600, 300),
+            Position::new(600, 0, 400, 600),
+        ]
+    );
+    assert!(variant.grow_master(0.1));
+    assert_eq!(positions(&mut variant, 3)[0], Position::new(0, 0, 700, 300));
+    assert!(variant.shrink_master(1.0));
+    assert_eq!(variant.master_ratio(), Some(0.05));
+    assert!(!variant.shrink_master(0.1));
+    assert!(variant.decrement_master_count());
+    assert!(variant.decrement_master_count());
+    assert!(!variant.decrement_master_count());
+    assert_eq!(
+        positions(&mut variant, 2),
+        vec![
+            Position::new(0, 0, 1000, 300),
+            Position::new(0, 300, 1000, 300)
+        ]
+    );
+}
+
+#[test]
+fn adjusting_other_end_behaviours_does_nothing() {
+    let user_variant: UserVariant = serde_json::from_str(
+        r#"{
+            "positions": [[[0, 0, 1000, 600]]],
+            "end_behaviour": {
+                "from": null,
+                "position_idx": 0,
+                "behaviour": {"Directional": {"direction": "Right"}}
+            }
+        }"#,
+    )
+    .unwrap();
+    let mut variant = Variant::from(user_variant);
+    assert!(!variant.increment_master_count());
+    assert!(!variant.grow_master(0.1));
+    assert_eq!(variant.master_count(), None);
+}
+
+#[test]
+fn master_ratio_is_clamped_when_tiling() {
+    let user_variant: UserVariant = serde_json::from_str(
+        r#"{
+            "positions": [[[0, 0, 1000, 600]]],
+            "end_behaviour": {
+                "from": null,
+                "position_idx": 0,
+                "behaviour": {
+                    "MasterStack": {
+                        "master_count": 1,
+                        "master_ratio": 1.5,
+                        "stack_direction": "Right"
+                    }
+                }
+            }
+        }"#,
+    )
+    .unwrap();
+    let mut variant = Variant::from(user_variant);
+    assert_eq!(
+        positions(&mut variant, 2),
+        vec![
+            Position::new(0, 0, 950, 600),
+            Position::new(950, 0, 50, 600)
+        ]
+    );
+}
+
+#[test]
+fn resizes_are_kept_when_the_master_changes() {
+    let mut variant = variant(1, "Right");
+    assert!(variant.resize(3, 1, Direction::Down, 50));
+    assert!(variant.grow_master(0.1));
+    assert_eq!(
+        positions(&mut variant, 3),
+        vec![
+            Position::new(0, 0, 700, 600),
+            Position::new(700, 0, 300, 350),
+            Position::new(700, 350, 300, 250),
+        ]
+    );
+    assert!(variant.increment_master_count());
+    assert!(variant.decrement_master_count());
+    assert_eq!(
+        positions(&mut variant, 3)[1],
+        Position::new(700, 0, 300, 350)
+    );
+}
@@ -0,0 +1,75 @@
+use himewm_layout::{neighbours::nearest_in_direction, position::Position, variant::Direction};
+
+/// A master on the left with two windows stacked on the right.
+fn master_stack() -> Vec<Option<Position>> {
+    return vec![
+        Some(Position::new(0, 0, 1000, 1000)),
+        Some(Position::new(1000, 0, 1000, 500)),
+        Some(Position::new(1000, 500, 1000, 500)),
+    ];
+}
+
+#[test]
+fn nearest_tile_is_found_in_each_direction() {
+    let tiles = master_stack();
+    let bottom = tiles[2].clone().unwrap();
+    assert_eq!(
+        nearest_in_direction(&bottom, &tiles, &Direction::Up),
+        Some(1)
+    );
+    assert_eq!(
+        nearest_in_direction(&bottom, &tiles, &Direction::Left),
+        Some(0)
+    );
+    assert_eq!(
+        nearest_in_direction(&bottom, &tiles, &Direction::Down),
+        None
+    );
+    assert_eq!(
+        nearest_in_direction(&bottom, &tiles, &Direction::Right),
+        None
+    );
+}
+
+#[test]
+fn ties_go_to_the_closest_centre() {
+    let tiles = master_stack();
+    let master = tiles[0].clone().unwrap();
+    let tall = Position::new(0, 0, 1000, 600);
+    assert_eq!(
+        nearest_in_direction(&master, &tiles, &Direction::Right),
+        Some(1)
+    );
+    assert_eq!(
+        nearest_in_direction(&Position::new(0, 400, 1000, 600), &tiles, &Direction::Right),
+        Some(2)
+    );
+    assert_eq!(
+        nearest_in_direction(&tall, &tiles, &Direction::Right),
+        Some(1)
+    );
+}
+
+#[test]
+fn tiles_that_line_up_are_preferred_over_closer_ones() {
+    let tiles = vec![
+        Some(Position::new(100, 600, 100, 100)),
+        Some(Position::new(500, 0, 100, 100)),
+    ];
+    let from = Position::new(0, 0, 100, 100);
+    assert_eq!(
+        nearest_in_direction(&from, &tiles, &Direction::Right),
+        Some(1)
+    );
+}
+
+#[test]
+fn untiled_and_overlapping_positions_are_skipped() {
+    let from = Position::new(0, 0, 1000, 1000);
+    let tiles = vec![
+        None,
+        Some(from.clone()),
+        Some(Position::new(500, 0, 1000, 1000)),
+    ];
+    assert_eq!(nearest_in_direction(&from, &tiles, &Direction::Right), None);
+}
@@ -0,0 +1,131 @@
+use himewm_layout::{
+    layout::Layout, overflow::OverflowPolicy, padding::Padding, position::Position,
+    user_layout::UserLayout,
+};
+
+fn layout(overflow: &str) -> Layout {
+    let user_layout: UserLayout = serde_json::from_str(&format!(
+        r#"{{
+            "w": 1.0,
+            "h": 1.0,
+            "min_w": 0.3,
+            "overflow": {overflow},
+            "default_variant_idx": [0],
+            "variants": [
+                {{
+                    "positions": [[[0.0, 0.0, 1.0, 1.0]]],
+                    "end_behaviour": {{
+                        "from": null,
+                        "position_idx": 0,
+                        "behaviour": {{"Directional": {{"direction": "Right"}}}}
+                    }}
+                }},
+                {{
+                    "positions": [[[0.0, 0.0, 1.0, 1.0]]],
+                    "end_behaviour": {{
+                        "from": null,
+                        "position_idx": 0,
+                        "behaviour": {{"Directional": {{"direction": "Down"}}}}
+                    }}
+                }}
+            ]
+        }}"#
+    ))
+    .unwrap();
+    return Layout::from(user_layout).resolve(Position::new(0, 0, 1200, 900));
+}
+
+fn positions(layout: &mut Layout, n: usize, min_sizes: &[(i32, i32)]) -> Vec<Option<Position>> {
+    return layout.get_fitted_internal_positions(&[0], n, &[], min_sizes, &Padding::default());
+}
+
+#[test]
+fn min_size_is_resolved_against_the_monitor() {
+    assert_eq!(layout("\"Monocle\"").min_size(), (360, 0));
+}
+
+#[test]
+fn windows_that_fit_are_tiled_normally() {
+    let mut layout = layout("\"Float\"");
+    assert_eq!(
+        positions(&mut layout, 3, &[]),
+        vec![
+            Some(Position::new(0, 0, 400, 900)),
+            Some(Position::new(400, 0, 400, 900)),
+            Some(Position::new(800, 0, 400, 900)),
+        ]
+    );
+}
+
+#[test]
+fn ignore_tiles_every_window() {
+    let mut layout = layout("\"Ignore\"");
+    assert!(positions(&mut layout, 4, &[])
+        .iter()
+        .all(|position| position.as_ref().is_some_and(|p| p.w() == 300)));
+}
+
+#[test]
+fn monocle_stacks_windows_in_the_last_tile_that_fits() {
+    let mut layout = layout("\"Monocle\"");
+    assert_eq!(
+        positions(&mut layout, 5, &[]),
+        vec![
+            Some(Position::new(0, 0, 400, 900)),
+            Some(Position::new(400, 0, 400, 900)),
+            Some(Position::new(800, 0, 400, 900)),
+            Some(Position::new(800, 0, 400, 900)),
+            Some(Position::new(800, 0, 400, 900)),
+        ]
+    );
+}
+
+#[test]
+fn window_min_sizes_apply_to_their_own_tile() {
+    let mut layout = layout("\"Monocle\"");
+    assert_eq!(
+        positions(&mut layout, 3, &[(0, 0), (0, 0), (500, 0)]),
+        vec![
+            Some(Position::new(0, 0, 600, 900)),
+            Some(Position::new(600, 0, 600, 900)),
+            Some(Position::new(600, 0, 600, 900)),
+        ]
+    );
+}
+
+#[test]
+fn variant_spills_to_the_other_variant() {
+    let mut layout = layout("{\"Variant\": [1]}");
+    assert_eq!(
+        positions(&mut layout, 4, &[]),
+        vec![
+            Some(Position::new(0, 0, 1200, 225)),
+            Some(Position::new(0, 225, 1200, 225)),
+            Some(Position::new(0, 450, 1200, 225)),
+            Some(Position::new(0, 675, 1200, 225)),
+        ]
+    );
+}
+
+#[test]
+fn float_leaves_windows_that_do_not_fit_untiled() {
+    let mut layout = layout("\"Float\"");
+    assert_eq!(
+        positions(&mut layout, 5, &[]),
+        vec![
+            Some(Position::new(0, 0, 400, 900)),
+            Some(Position::new(400, 0, 400, 900)),
+            Some(Position::new(800, 0, 400, 900)),
+            None,
+            None,
+        ]
+    );
+}
+
+#[test]
+fn overflow_is_exported() {
+    let layout = layout("{\"Variant\": [1]}");
+    let exported: UserLayout = serde_json::from_str(&layout.to_json()).unwrap();
+    assert_eq!(exported.min_w, 0.3);
+    assert_eq!(exported.overflow, OverflowPolicy::Variant(vec![1]));
+}
@@ -0,0 +1,107 @@
+use himewm_layout::{
+    layout::Layout,
+    padding::{Padding, PaddingOverrides},
+    position::Position,
+    user_layout::UserLayout,
+};
+
+fn layout(padding: &str) -> Layout {
+    let user_layout: UserLayout = serde_json::from_str(&format!(
+        r#"{{
+            "w": 1.0,
+            "h": 1.0,
+            "padding": {padding},
+            "default_variant_idx": [0],
+            "variants": [
+                {{
+                    "positions": [[[0.0, 0.0, 1.0, 1.0]]],
+                    "end_behaviour": {{
+                        "from": null,
+                        "position_idx": 0,
+                        "behaviour": {{"Directional": {{"direction": "Right"}}}}
+                    }}
+                }}
+            ]
+        }}"#
+    ))
+    .unwrap();
+    return Layout::from(user_layout).resolve(Position::new(0, 0, 1200, 900));
+}
+
+fn padding() -> Padding {
+    return Padding {
+        horizontal: 5,
+        vertical: 0,
+        top: 30,
+        bottom: 10,
+        left: 20,
+        right: 0,
+    };
+}
+
+#[test]
+fn uniform_padding_matches_window_and_edge_padding() {
+    let mut layout = layout("{}");
+    assert_eq!(
+        layout.get_weighted_internal_positions(&[0], 2, &[], &Padding::uniform(5, 10)),
+        layout.get_internal_positions(&[0], 2, 5, 10).clone()
+    );
+}
+
+#[test]
+fn each_edge_has_its_own_padding() {
+    let mut layout = layout("{}");
+    assert_eq!(
+        layout.get_weighted_internal_positions(&[0], 1, &[], &padding()),
+        vec![Position::new(20, 30, 1180, 860)]
+    );
+}
+
+#[test]
+fn inner_gaps_apply_between_tiles() {
+    let mut layout = layout("{}");
+    assert_eq!(
+        layout.get_weighted_internal_positions(&[0], 2, &[], &padding()),
+        vec![
+            Position::new(20, 30, 575, 860),
+            Position::new(605, 30, 595, 860),
+        ]
+    );
+}
+
+#[test]
+fn layout_overrides_only_change_the_sides_given() {
+    let layout = layout(r#"{"horizontal": 0, "top": 0}"#);
+    let padding = layout.padding().apply(&padding());
+    assert_eq!(padding.horizontal, 0);
+    assert_eq!(padding.top, 0);
+    assert_eq!(padding.left, 20);
+    assert_eq!(padding.bottom, 10);
+}
+
+#[test]
+fn later_overrides_take_precedence() {
+    let layout_overrides = PaddingOverrides {
+        left: Some(1),
+        right: Some(2),
+        ..Default::default()
+    };
+    let monitor_overrides = PaddingOverrides {
+        right: Some(3),
+        ..Default::default()
+    };
+    let padding = layout_overrides
+        .overridden_by(&monitor_overrides)
+        .apply(&Padding::default());
+    assert_eq!((padding.left, padding.right), (1, 3));
+}
+
+#[test]
+fn padding_is_exported() {
+    let layout = layout(r#"{"left": 4}"#);
+    let exported: UserLayout = serde_json::from_str(&layout.to_json()).unwrap();
+    assert_eq!(exported.padding.left, Some(4));
+    assert!(exported.padding.right.is_none());
+    let layout = self::layout("{}");
+    assert!(!layout.to_json().contains("padding"));
+}
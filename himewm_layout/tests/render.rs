@@ -0,0 +1,95 @@
+use himewm_layout::{
+    layout::Layout,
+    position::Position,
+    render::{self, RenderOptions},
+    user_layout::UserLayout,
+};
+
+fn layout(behaviour: &str) -> Layout {
+    let json = format!(
+        r#"{{
+            "w": 1.0,
+            "h": 1.0,
+            "default_variant_idx": [0],
+            "variants": [
+                {{
+                    "positions": [[[0, 0, 1, 1]], [[0, 0, 0.5, 1], [0.5, 0, 0.5, 1]]],
+                    "end_behaviour": {{"from": null, "position_idx": 1, "behaviour": {}}}
+                }}
+            ]
+        }}"#,
+        behaviour
+    );
+    let user_layout: UserLayout = serde_json::from_str(&json).unwrap();
+    return Layout::from(user_layout).resolve(Position::new(0, 0, 1600, 900));
+}
+
+fn options() -> RenderOptions {
+    return RenderOptions {
+        columns: 24,
+        rows: 8,
+        ..Default::default()
+    };
+}
+
+#[test]
+fn ascii_directional() {
+    let mut layout = layout(r#"{"Directional": {"direction": "Down"}}"#);
+    assert_eq!(
+        render::ascii(&mut layout, &[0], 3, &options()),
+        "\
++----------++----------+
+|          ||    1     |
+|          ||          |
+|    0     |+----------+
+|          |+----------+
+|          ||    2     |
+|          ||          |
++----------++----------+"
+    );
+}
+
+#[test]
+fn ascii_master_stack_with_padding() {
+    let mut layout = layout(
+        r#"{"MasterStack": {"master_count": 1, "master_ratio": 0.5, "stack_direction": "Right"}}"#,
+    );
+    let options = RenderOptions {
+        window_padding: 50,
+        edge_padding: 100,
+        ..options()
+    };
+    assert_eq!(
+        render::ascii(&mut layout, &[0], 4, &options),
+        "
+  +-------+  +--+  +--+
+  |       |  |  |  |2 |
+  |   0   |  |1 |  +--+
+  |       |  |  |  +--+
+  |       |  |  |  |3 |
+  +-------+  +--+  +--+
+"
+    );
+}
+
+#[test]
+fn ascii_no_windows_is_empty() {
+    let mut layout = layout(r#"{"Directional": {"direction": "Down"}}"#);
+    assert_eq!(
+        render::ascii(&mut layout, &[0], 0, &options()),
+        "\n".repeat(7)
+    );
+}
+
+#[test]
+fn svg_has_a_rect_and_label_per_window() {
+    let mut layout = layout(r#"{"Directional": {"direction": "Right"}}"#);
+    let svg = render::svg(&mut layout, &[0], 5, &RenderOptions::default());
+    assert!(
+        svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"1600\" height=\"900\"")
+    );
+    assert_eq!(svg.matches("<rect ").count(), 6);
+    assert_eq!(svg.matches("<text ").count(), 5);
+    assert!(svg.contains("<rect x=\"0\" y=\"0\" width=\"800\" height=\"900\" fill=\"#e06c75\""));
+    assert!(svg.trim_end().ends_with("</svg>"));
+}
@@ -0,0 +1,88 @@
+use himewm_layout::{
+    position::Position,
+    user_layout::UserVariant,
+    variant::{Direction, Variant},
+};
+
+// A full-height tile on the left, with the right half split into two.
+fn variant() -> Variant {
+    let user_variant: UserVariant = serde_json::from_str(
+        r#"{
+            "positions": [
+                [[0, 0, 1000, 600]],
+                [[0, 0, 500, 600], [500, 0, 500, 600]],
+                [[0, 0, 500, 600], [500, 0, 500, 300], [500, 300, 500, 300]]
+            ],
+            "end_behaviour": {
+                "from": null,
+                "position_idx": 2,
+                "behaviour": {"Directional": {"direction": "Down"}}
+            }
+        }"#,
+    )
+    .unwrap();
+    return Variant::from(user_variant);
+}
+
+#[test]
+fn resizing_moves_shared_edge_of_every_neighbour() {
+    let mut variant = variant();
+    assert!(variant.resize(3, 1, Direction::Left, 100));
+    assert_eq!(
+        variant.positions()[2],
+        vec![
+            Position::new(0, 0, 400, 600),
+            Position::new(400, 0, 600, 300),
+            Position::new(400, 300, 600, 300),
+        ]
+    );
+}
+
+#[test]
+fn negative_delta_shrinks() {
+    let mut variant = variant();
+    assert!(variant.resize(3, 0, Direction::Right, -100));
+    assert_eq!(variant.positions()[2][0], Position::new(0, 0, 400, 600));
+    assert_eq!(variant.positions()[2][2], Position::new(400, 300, 600, 300));
+}
+
+#[test]
+fn resizing_only_affects_one_arrangement() {
+    let mut variant = variant();
+    assert!(variant.resize(3, 1, Direction::Down, 50));
+    assert_eq!(
+        variant.positions()[2],
+        vec![
+            Position::new(0, 0, 500, 600),
+            Position::new(500, 0, 500, 350),
+            Position::new(500, 350, 500, 250),
+        ]
+    );
+    assert_eq!(variant.positions()[1][0], Position::new(0, 0, 500, 600));
+}
+
+#[test]
+fn outer_edges_cannot_move() {
+    let mut variant = variant();
+    assert!(!variant.resize(3, 0, Direction::Left, 10));
+    assert!(!variant.resize(3, 1, Direction::Up, 10));
+    assert!(!variant.resize(1, 0, Direction::Right, 10));
+}
+
+#[test]
+fn resizing_is_clamped() {
+    let mut variant = variant();
+    assert!(variant.resize(3, 0, Direction::Right, 10000));
+    assert_eq!(variant.positions()[2][0], Position::new(0, 0, 999, 600));
+    assert_eq!(variant.positions()[2][1], Position::new(999, 0, 1, 300));
+    assert!(!variant.resize(3, 0, Direction::Right, 10));
+}
+
+#[test]
+fn resizing_generated_arrangement_extends_variant() {
+    let mut variant = variant();
+    assert!(variant.resize(5, 0, Direction::Right, 100));
+    assert_eq!(variant.positions().len(), 5);
+    let area: i32 = variant.positions()[4].iter().map(|p| p.w() * p.h()).sum();
+    assert_eq!(area, 1000 * 600);
+}
@@ -0,0 +1,85 @@
+use himewm_layout::{position::Position, user_layout::UserVariant, variant::Variant};
+
+fn variant(behaviour: &str, direction: &str) -> Variant {
+    let user_variant: UserVariant = serde_json::from_str(&format!(
+        r#"{{
+            "positions": [[[0, 0, 1600, 800]]],
+            "end_behaviour": {{
+                "from": null,
+                "position_idx": 0,
+                "behaviour": {{"{behaviour}": {{"ratio": 0.5, "direction": "{direction}"}}}}
+            }}
+        }}"#
+    ))
+    .unwrap();
+    return Variant::from(user_variant);
+}
+
+fn positions(variant: &mut Variant, n: usize) -> Vec<Position> {
+    return variant
+        .get_internal_positions(n, 0, 0, &Position::new(0, 0, 1600, 800))
+        .to_owned();
+}
+
+#[test]
+fn spiral_rotates_clockwise() {
+    let mut variant = variant("Spiral", "Right");
+    assert_eq!(
+        positions(&mut variant, 5),
+        vec![
+            Position::new(0, 0, 800, 800),
+            Position::new(800, 0, 800, 400),
+            Position::new(1200, 400, 400, 400),
+            Position::new(800, 600, 400, 200),
+            Position::new(800, 400, 400, 200),
+        ]
+    );
+}
+
+#[test]
+fn dwindle_alternates_towards_one_corner() {
+    let mut variant = variant("Dwindle", "Right");
+    assert_eq!(
+        positions(&mut variant, 5),
+        vec![
+            Position::new(0, 0, 800, 800),
+            Position::new(800, 0, 800, 400),
+            Position::new(800, 400, 400, 400),
+            Position::new(1200, 400, 400, 200),
+            Position::new(1200, 600, 400, 200),
+        ]
+    );
+}
+
+#[test]
+fn starting_direction_is_configurable() {
+    let mut variant = variant("Dwindle", "Up");
+    assert_eq!(
+        positions(&mut variant, 3),
+        vec![
+            Position::new(0, 400, 1600, 400),
+            Position::new(0, 0, 800, 400),
+            Position::new(800, 0, 800, 400),
+        ]
+    );
+}
+
+#[test]
+fn single_window_fills_region() {
+    let mut variant = variant("Spiral", "Left");
+    assert_eq!(
+        positions(&mut variant, 1),
+        vec![Position::new(0, 0, 1600, 800)]
+    );
+}
+
+#[test]
+fn handles_many_windows() {
+    for behaviour in ["Spiral", "Dwindle"] {
+        let mut variant = variant(behaviour, "Down");
+        let tiles = positions(&mut variant, 100);
+        assert_eq!(tiles.len(), 100);
+        let area: i64 = tiles.iter().map(|t| t.w() as i64 * t.h() as i64).sum();
+        assert_eq!(area, 1600 * 800);
+    }
+}
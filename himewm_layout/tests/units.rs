@@ -0,0 +1,119 @@
+use himewm_layout::{layout::Layout, position::Position, user_layout::UserLayout};
+
+fn layout(json: &str) -> Layout {
+    let user_layout: UserLayout = serde_json::from_str(json).unwrap();
+    return Layout::from(user_layout);
+}
+
+const THIRDS: &str = r#"{
+    "w": 1.0,
+    "h": 1.0,
+    "default_variant_idx": [0],
+    "variants": [
+        {
+            "positions": [
+                [[0.0, 0.0, 1.0, 1.0]],
+                [[0.0, 0.0, 0.5, 1.0], [0.5, 0.0, 0.5, 1.0]],
+                [[0.0, 0.0, 0.333333, 1.0], [0.333333, 0.0, 0.333334, 0.5], [0.333333, 0.5, 0.333334, 0.5], [0.666667, 0.0, 0.333333, 1.0]]
+            ],
+            "end_behaviour": {
+                "from": null,
+                "position_idx": 0,
+                "behaviour": {"Directional": {"direction": "Down"}}
+            }
+        }
+    ]
+}"#;
+
+fn assert_tiles_fill(tiles: &[Position], rect: &Position) {
+    let area: i64 = tiles.iter().map(|p| p.w() as i64 * p.h() as i64).sum();
+    assert_eq!(area, rect.w() as i64 * rect.h() as i64);
+    for tile in tiles {
+        assert!(tile.x() >= rect.x() && tile.x() + tile.w() <= rect.x() + rect.w());
+        assert!(tile.y() >= rect.y() && tile.y() + tile.h() <= rect.y() + rect.h());
+    }
+}
+
+#[test]
+fn fractional_layout_resolves_without_seams() {
+    for rect in [
+        Position::new(0, 0, 1920, 1080),
+        Position::new(1920, 0, 1366, 767),
+        Position::new(-1081, -300, 1081, 1919),
+    ] {
+        let mut layout = layout(THIRDS).resolve(rect.clone());
+        for n in 1..=3 {
+            let tiles = layout.get_internal_positions(&[0], n, 0, 0).to_owned();
+            assert_tiles_fill(&tiles, &rect);
+        }
+        let tiles = layout.get_internal_positions(&[0], 3, 0, 0).to_owned();
+        assert_eq!(tiles[0].x() + tiles[0].w(), tiles[1].x());
+        assert_eq!(tiles[1].y() + tiles[1].h(), tiles[2].y());
+        assert_eq!(tiles[1].x() + tiles[1].w(), tiles[3].x());
+    }
+}
+
+#[test]
+fn grid_units_resolve_exactly() {
+    let mut layout = layout(
+        r#"{
+            "w": 12,
+            "h": 12,
+            "default_variant_idx": [0],
+            "variants": [
+                {
+                    "positions": [[[0, 0, 12, 12]], [[0, 0, 7, 12], [7, 0, 5, 12]]],
+                    "end_behaviour": {
+                        "from": null,
+                        "position_idx": 1,
+                        "behaviour": {"Directional": {"direction": "Down"}}
+                    }
+                }
+            ]
+        }"#,
+    )
+    .resolve(Position::new(0, 0, 1001, 601));
+    assert_eq!(
+        layout.get_internal_positions(&[0], 2, 0, 0),
+        &vec![
+            Position::new(0, 0, 584, 601),
+            Position::new(584, 0, 417, 601)
+        ]
+    );
+}
+
+#[test]
+fn resolving_repeatedly_does_not_accumulate_rounding() {
+    let pixels = layout(
+        r#"{
+            "w": 1920,
+            "h": 1080,
+            "default_variant_idx": [0],
+            "variants": [
+                {
+                    "positions": [[[0, 0, 1920, 1080]], [[0, 0, 641, 1080], [641, 0, 1279, 1080]]],
+                    "end_behaviour": {
+                        "from": null,
+                        "position_idx": 1,
+                        "behaviour": {"Directional": {"direction": "Down"}}
+                    }
+                }
+            ]
+        }"#,
+    );
+    let rect = Position::new(0, 0, 1366, 767);
+    let mut once = pixels.resolve(rect.clone());
+    let mut layout = pixels.clone();
+    for other in [
+        Position::new(0, 0, 1001, 333),
+        Position::new(5, 7, 2561, 1441),
+        rect.clone(),
+    ] {
+        layout = layout.resolve(other);
+    }
+    assert_eq!(
+        layout.get_internal_positions(&[0], 2, 0, 0),
+        once.get_internal_positions(&[0], 2, 0, 0)
+    );
+    assert_tiles_fill(once.get_internal_positions(&[0], 2, 0, 0), &rect);
+}
@@ -0,0 +1,193 @@
+use himewm_layout::{
+    layout::Layout,
+    user_layout::UserLayout,
+    validation::{ValidationError, ValidationErrorKind},
+};
+
+fn layout(variants: &str) -> Layout {
+    let json = format!(
+        r#"{{"w": 1.0, "h": 1.0, "default_variant_idx": [0], "variants": {}}}"#,
+        variants
+    );
+    let user_layout: UserLayout = serde_json::from_str(&json).unwrap();
+    return Layout::from(user_layout);
+}
+
+fn variant(positions: &str, position_idx: usize, behaviour: &str) -> String {
+    return format!(
+        r#"{{"positions": {}, "end_behaviour": {{"from": null, "position_idx": {}, "behaviour": {}}}}}"#,
+        positions, position_idx, behaviour
+    );
+}
+
+const DOWN: &str = r#"{"Directional": {"direction": "Down"}}"#;
+
+const HALVES: &str = "[[[0, 0, 1, 1]], [[0, 0, 0.5, 1], [0.5, 0, 0.5, 1]]]";
+
+fn error(
+    variant_idx: &[usize],
+    window_count: Option<usize>,
+    position_idx: Option<usize>,
+    kind: ValidationErrorKind,
+) -> ValidationError {
+    return ValidationError {
+        variant_idx: Vec::from(variant_idx),
+        window_count,
+        position_idx,
+        kind,
+    };
+}
+
+#[test]
+fn valid_layout_passes() {
+    let thirds = "[[[0, 0, 1, 1]], [[0, 0, 0.5, 1], [0.5, 0, 0.5, 1]], \
+        [[0, 0, 0.1, 1], [0.1, 0, 0.2, 1], [0.30000000000000004, 0, 0.7, 1]]]";
+    let variants = format!(
+        "[[{}, {}]]",
+        variant(HALVES, 1, DOWN),
+        variant(thirds, 2, DOWN)
+    );
+    assert_eq!(layout(&variants).validate(), Ok(()));
+}
+
+#[test]
+fn overlapping_and_out_of_bounds_positions_are_reported() {
+    let positions = "[[[0, 0, 1, 1]], [[0, 0, 0.6, 1], [0.5, 0, 0.6, 1]]]";
+    let variants = format!("[{}]", variant(positions, 0, DOWN));
+    assert_eq!(
+        layout(&variants).validate(),
+        Err(vec![
+            error(&[0], Some(2), Some(1), ValidationErrorKind::OutOfBounds),
+            error(
+                &[0],
+                Some(2),
+                Some(1),
+                ValidationErrorKind::Overlap { other_idx: 0 }
+            ),
+        ])
+    );
+}
+
+#[test]
+fn wrong_position_count_is_reported() {
+    let positions = "[[[0, 0, 1, 1]], [[0, 0, 1, 1]]]";
+    let variants = format!("[{}]", variant(positions, 0, DOWN));
+    assert_eq!(
+        layout(&variants).validate(),
+        Err(vec![error(
+            &[0],
+            Some(2),
+            None,
+            ValidationErrorKind::WrongPositionCount { found: 1 }
+        )])
+    );
+}
+
+#[test]
+fn end_behaviour_errors_are_reported_with_variant_path() {
+    let variants = format!(
+        "[[{}], [{}, {}]]",
+        variant(HALVES, 0, DOWN),
+        variant(HALVES, 2, DOWN),
+        variant(HALVES, 1, r#"{"Repeating": {"splits": []}}"#)
+    );
+    let errors = layout(&variants).validate().unwrap_err();
+    assert_eq!(
+        errors,
+        vec![
+            error(
+                &[1, 0],
+                None,
+                None,
+                ValidationErrorKind::PositionIdxOutOfRange { len: 2 }
+            ),
+            error(&[1, 1], None, None, ValidationErrorKind::EmptySplits),
+        ]
+    );
+    assert_eq!(
+        errors[0].to_string(),
+        "variant [1, 0]: end behaviour position_idx is out of range for 2 position(s)"
+    );
+}
+
+#[test]
+fn display_names_window_count_and_position() {
+    let positions = "[[[0, 0, 1, 1]], [[0, 0, 0.5, 1], [0.5, 0, 0.5, 0]]]";
+    let variants = format!("[{}]", variant(positions, 0, DOWN));
+    let errors = layout(&variants).validate().unwrap_err();
+    assert_eq!(
+        errors[0].to_string(),
+        "variant [0], 2 window(s), position 1: has no width or height"
+    );
+}
+
+#[test]
+fn out_of_range_split_offsets_are_reported() {
+    let splits = r#"{"Repeating": {"splits": [
+        {"direction": "Down", "ratio": 0.5, "offset": 2},
+        {"direction": "Right", "ratio": 0.5, "offset": 1},
+        {"direction": "Down", "ratio": 0.5, "offset": 5}
+    ]}}"#;
+    let variants = format!("[{}]", variant(HALVES, 1, splits));
+    let errors = layout(&variants).validate().unwrap_err();
+    assert_eq!(
+        errors,
+        vec![error(
+            &[0],
+            None,
+            None,
+            ValidationErrorKind::InvalidSplitOffset { split_idx: 2 }
+        )]
+    );
+    assert_eq!(
+        errors[0].to_string(),
+        "variant [0]: offset of split 2 is out of range"
+    );
+}
+
+#[test]
+fn master_ratio_outside_zero_and_one_is_reported() {
+    let master_stack = r#"{"MasterStack": {
+        "master_count": 1,
+        "master_ratio": 1.5,
+        "stack_direction": "Down"
+    }}"#;
+    let variants = format!("[{}]", variant(HALVES, 1, master_stack));
+    assert_eq!(
+        layout(&variants).validate(),
+        Err(vec![error(
+            &[0],
+            None,
+            None,
+            ValidationErrorKind::InvalidRatio
+        )])
+    );
+}
+
+#[test]
+fn layout_size_and_overflow_variant_are_checked() {
+    let json = format!(
+        r#"{{"w": 0.0, "h": 1.0, "overflow": {{"Variant": [3]}}, "default_variant_idx": [0], "variants": [{}]}}"#,
+        variant("[[[0, 0, 0, 1]]]", 0, DOWN)
+    );
+    let user_layout: UserLayout = serde_json::from_str(&json).unwrap();
+    let errors = Layout::from(user_layout).validate().unwrap_err();
+    assert_eq!(
+        errors[..2],
+        [
+            error(&[], None, None, ValidationErrorKind::InvalidSize),
+            error(
+                &[],
+                None,
+                None,
+                ValidationErrorKind::MissingOverflowVariant {
+                    variant_idx: vec![3]
+                }
+            ),
+        ]
+    );
+    assert_eq!(
+        errors[0].to_string(),
+        "layout: w and h are not greater than 0"
+    );
+}
@@ -0,0 +1,84 @@
+use himewm_layout::{user_layout::UserLayout, variants_container::VariantsContainer};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+struct Named {
+    name: String,
+}
+
+fn named(name: &str) -> Named {
+    return Named {
+        name: String::from(name),
+    };
+}
+
+#[test]
+fn nested_arrays_become_containers() {
+    let container: VariantsContainer<Named> =
+        serde_json::from_str(r#"[[{"name": "a"}, {"name": "b"}], [[{"name": "c"}]]]"#).unwrap();
+    match container {
+        VariantsContainer::Container(inner) => {
+            assert_eq!(inner.len(), 2);
+            match &inner[0] {
+                VariantsContainer::Variants(variants) => {
+                    assert_eq!(variants, &vec![named("a"), named("b")]);
+                }
+                VariantsContainer::Container(_) => panic!("expected variants"),
+            }
+            match &inner[1] {
+                VariantsContainer::Container(inner) => assert_eq!(inner.len(), 1),
+                VariantsContainer::Variants(_) => panic!("expected a container"),
+            }
+        }
+        VariantsContainer::Variants(_) => panic!("expected a container"),
+    }
+}
+
+#[test]
+fn strings_with_whitespace_and_brackets_are_preserved() {
+    let container: VariantsContainer<Named> =
+        serde_json::from_str(r#"[{"name": "[a b]\t[[c]]"}]"#).unwrap();
+    match container {
+        VariantsContainer::Variants(variants) => {
+            assert_eq!(variants, vec![named("[a b]\t[[c]]")]);
+        }
+        VariantsContainer::Container(_) => panic!("expected variants"),
+    }
+}
+
+#[test]
+fn round_trips_to_nested_arrays() {
+    let json = r#"[[{"name":"a [x]"}],[[{"name":"b"},{"name":"c"}]]]"#;
+    let container: VariantsContainer<Named> = serde_json::from_str(json).unwrap();
+    assert_eq!(serde_json::to_string(&container).unwrap(), json);
+}
+
+#[test]
+fn mixing_variants_and_arrays_is_an_error() {
+    let result: Result<VariantsContainer<Named>, _> =
+        serde_json::from_str(r#"[{"name": "a"}, [{"name": "b"}]]"#);
+    assert!(result.is_err());
+}
+
+#[test]
+fn errors_point_into_the_original_file() {
+    let json = r#"{
+    "w": 1,
+    "h": 1,
+    "default_variant_idx": [0],
+    "variants": [
+        [
+            {
+                "positions": [[[0, 0, 1, 1]]],
+                "end_behaviour": {
+                    "from": null,
+                    "position_idx": "zero",
+                    "behaviour": {"Directional": {"direction": "Down"}}
+                }
+            }
+        ]
+    ]
+}"#;
+    let error = serde_json::from_str::<UserLayout>(json).err().unwrap();
+    assert_eq!(error.line(), 11);
+}
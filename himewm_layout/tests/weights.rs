@@ -0,0 +1,106 @@
+use himewm_layout::{position::Position, weights::apply_weights};
+
+fn master_stack() -> Vec<Position> {
+    return vec![
+        Position::new(0, 0, 1000, 1000),
+        Position::new(1000, 0, 1000, 500),
+        Position::new(1000, 500, 1000, 500),
+    ];
+}
+
+fn rect() -> Position {
+    return Position::new(0, 0, 2000, 1000);
+}
+
+#[test]
+fn equal_weights_keep_the_arrangement() {
+    assert_eq!(apply_weights(&master_stack(), &rect(), &[]), master_stack());
+    assert_eq!(
+        apply_weights(&master_stack(), &rect(), &[2.0, 2.0, 2.0]),
+        master_stack()
+    );
+}
+
+#[test]
+fn weight_grows_a_window_along_the_split() {
+    assert_eq!(
+        apply_weights(&master_stack(), &rect(), &[3.0]),
+        vec![
+            Position::new(0, 0, 1500, 1000),
+            Position::new(1500, 0, 500, 500),
+            Position::new(1500, 500, 500, 500),
+        ]
+    );
+}
+
+#[test]
+fn nested_splits_are_weighted_within_their_group() {
+    assert_eq!(
+        apply_weights(&master_stack(), &rect(), &[1.0, 1.0, 3.0]),
+        vec![
+            Position::new(0, 0, 667, 1000),
+            Position::new(667, 0, 1333, 250),
+            Position::new(667, 250, 1333, 750),
+        ]
+    );
+}
+
+#[test]
+fn missing_weights_default_to_one() {
+    assert_eq!(
+        apply_weights(&master_stack(), &rect(), &[1.0, 0.5]),
+        apply_weights(&master_stack(), &rect(), &[1.0, 0.5, 1.0])
+    );
+}
+
+#[test]
+fn arrangements_without_a_straight_split_are_unchanged() {
+    // A pinwheel, where every line through the rect cuts a window
+    let pinwheel = vec![
+        Position::new(0, 0, 200, 100),
+        Position::new(200, 0, 100, 200),
+        Position::new(100, 200, 200, 100),
+        Position::new(0, 100, 100, 200),
+        Position::new(100, 100, 100, 100),
+    ];
+    let rect = Position::new(0, 0, 300, 300);
+    assert_eq!(apply_weights(&pinwheel, &rect, &[5.0]), pinwheel);
+}
+
+#[test]
+fn arrangements_without_a_straight_split_are_scaled_into_their_group() {
+    // The same pinwheel next to a window that takes space from it
+    let positions = vec![
+        Position::new(0, 0, 200, 100),
+        Position::new(200, 0, 100, 200),
+        Position::new(100, 200, 200, 100),
+        Position::new(0, 100, 100, 200),
+        Position::new(100, 100, 100, 100),
+        Position::new(300, 0, 300, 300),
+    ];
+    let rect = Position::new(0, 0, 600, 300);
+    let tiles = apply_weights(&positions, &rect, &[1.0, 1.0, 1.0, 1.0, 1.0, 2.0]);
+    assert_eq!(
+        tiles,
+        vec![
+            Position::new(0, 0, 133, 100),
+            Position::new(133, 0, 67, 200),
+            Position::new(67, 200, 133, 100),
+            Position::new(0, 100, 67, 200),
+            Position::new(67, 100, 66, 100),
+            Position::new(200, 0, 400, 300),
+        ]
+    );
+    let area: i32 = tiles.iter().map(|tile| tile.w() * tile.h()).sum();
+    assert_eq!(area, rect.w() * rect.h());
+}
+
+#[test]
+fn weighted_positions_cover_the_rect() {
+    let tiles = apply_weights(&master_stack(), &rect(), &[0.3, 2.5, 0.7]);
+    let area: i32 = tiles.iter().map(|tile| tile.w() * tile.h()).sum();
+    assert_eq!(area, rect().w() * rect().h());
+    for tile in &tiles {
+        assert!(tile.w() > 0 && tile.h() > 0);
+    }
+}